@@ -9,6 +9,7 @@ enum ContractStatus: String, CaseIterable {
     case claimed = "Claimed"
     case executing = "Executing"
     case verifying = "Verifying"
+    case awaitingApproval = "Awaiting Approval"
     case completed = "Completed"
     case failed = "Failed"
     case rollingBack = "Rolling Back"
@@ -22,6 +23,7 @@ enum ContractStatus: String, CaseIterable {
         case .claimed: return "person.fill"
         case .executing: return "bolt.fill"
         case .verifying: return "magnifyingglass"
+        case .awaitingApproval: return "hand.raised.fill"
         case .completed: return "checkmark.circle.fill"
         case .failed: return "xmark.circle.fill"
         case .rollingBack: return "arrow.uturn.backward"
@@ -37,6 +39,7 @@ enum ContractStatus: String, CaseIterable {
         case .claimed: return .purple
         case .executing: return .blue
         case .verifying: return .cyan
+        case .awaitingApproval: return .pink
         case .completed: return .green
         case .failed: return .red
         case .rollingBack: return .yellow
@@ -45,23 +48,80 @@ enum ContractStatus: String, CaseIterable {
         }
     }
 
+    init(ffi: FfiContractStatus) {
+        switch ffi {
+        case .pending: self = .pending
+        case .ready: self = .ready
+        case .claimed: self = .claimed
+        case .executing: self = .executing
+        case .verifying: self = .verifying
+        case .awaitingApproval: self = .awaitingApproval
+        case .completed: self = .completed
+        case .failed: self = .failed
+        case .rollingBack: self = .rollingBack
+        case .rolledBack: self = .rolledBack
+        case .cancelled: self = .cancelled
+        }
+    }
+
+    /// Attention order from stead-core, shared with `stead attention` and the dashboard
+    private static let priorityOrder: [ContractStatus] = attentionOrder().map(ContractStatus.init(ffi:))
+
     /// Lower number = higher attention priority
     var attentionPriority: Int {
+        Self.priorityOrder.firstIndex(of: self) ?? Self.priorityOrder.count
+    }
+}
+
+/// Aggregate project state, shown as the menu-bar icon
+enum AttentionState {
+    case idle
+    case working
+    case needsInput
+    case failing
+
+    init(ffi: FfiAttentionState) {
+        switch ffi {
+        case .idle: self = .idle
+        case .working: self = .working
+        case .needsInput: self = .needsInput
+        case .failing: self = .failing
+        }
+    }
+
+    var icon: String {
         switch self {
-        case .failed: return 0
-        case .rollingBack: return 1
-        case .verifying: return 2
-        case .completed: return 3
-        case .executing: return 4
-        case .claimed: return 5
-        case .ready: return 6
-        case .pending: return 7
-        case .rolledBack: return 8
-        case .cancelled: return 9
+        case .idle: return "square.stack.3d.up"
+        case .working: return "square.stack.3d.up.fill"
+        case .needsInput: return "exclamationmark.bubble.fill"
+        case .failing: return "xmark.octagon.fill"
         }
     }
 }
 
+struct AttentionItem: Identifiable {
+    let id: String
+    let reason: String
+    let title: String
+    let detail: String?
+    let since: String
+
+    init(ffi: FfiAttentionItem) {
+        self.id = ffi.contractId ?? ffi.sessionId ?? ffi.title
+        self.reason = {
+            switch ffi.reason {
+            case .failedVerification: return "Failed verification"
+            case .approvalPending: return "Approval pending"
+            case .waitingOnQuestion: return "Waiting on a question"
+            case .staleClaim: return "Stale claim"
+            }
+        }()
+        self.title = ffi.title
+        self.detail = ffi.detail
+        self.since = ffi.since
+    }
+}
+
 struct ContractItem: Identifiable {
     let id: String
     let task: String
@@ -78,20 +138,7 @@ struct ContractItem: Identifiable {
         self.id = ffi.id
         self.task = ffi.task
         self.verification = ffi.verification
-        self.status = {
-            switch ffi.status {
-            case .pending: return .pending
-            case .ready: return .ready
-            case .claimed: return .claimed
-            case .executing: return .executing
-            case .verifying: return .verifying
-            case .completed: return .completed
-            case .failed: return .failed
-            case .rollingBack: return .rollingBack
-            case .rolledBack: return .rolledBack
-            case .cancelled: return .cancelled
-            }
-        }()
+        self.status = ContractStatus(ffi: ffi.status)
         self.createdAt = ffi.createdAt
         self.completedAt = ffi.completedAt
         self.output = ffi.output
@@ -105,6 +152,8 @@ enum CliType: String, CaseIterable {
     case claude = "Claude"
     case codex = "Codex"
     case openCode = "OpenCode"
+    case gemini = "Gemini"
+    case aider = "Aider"
     case universal = "Universal"
 
     var icon: String {
@@ -112,6 +161,8 @@ enum CliType: String, CaseIterable {
         case .claude: return "brain"
         case .codex: return "terminal"
         case .openCode: return "chevron.left.forwardslash.chevron.right"
+        case .gemini: return "sparkles"
+        case .aider: return "text.bubble"
         case .universal: return "globe"
         }
     }
@@ -134,6 +185,8 @@ struct SessionItem: Identifiable {
             case .claude: return .claude
             case .codex: return .codex
             case .openCode: return .openCode
+            case .gemini: return .gemini
+            case .aider: return .aider
             case .universal: return .universal
             }
         }()
@@ -146,14 +199,36 @@ struct SessionItem: Identifiable {
     }
 }
 
+// MARK: - Change notifications
+
+/// Forwards contract changes from the Rust watcher (a background thread) to the store
+final class ContractChangeObserver: ContractObserver {
+    private weak var store: SteadStore?
+
+    init(store: SteadStore) {
+        self.store = store
+    }
+
+    func onEvents(events: [FfiContractEvent]) {
+        Task { @MainActor [weak store] in store?.loadContracts() }
+    }
+
+    func onError(message: String) {
+        Task { @MainActor [weak store] in store?.errorMessage = "Watching contracts failed: \(message)" }
+    }
+}
+
 // MARK: - Store
 
 @MainActor
 class SteadStore: ObservableObject {
     @Published var contracts: [ContractItem] = []
     @Published var sessions: [SessionItem] = []
+    @Published var attentionState: AttentionState = .idle
+    @Published var attentionItems: [AttentionItem] = []
     @Published var selectedTab: Tab = .contracts
     @Published var errorMessage: String?
+    private var subscription: ContractSubscription?
 
     enum Tab {
         case contracts
@@ -163,6 +238,18 @@ class SteadStore: ObservableObject {
     func refresh() {
         loadContracts()
         loadSessions()
+        startWatching()
+    }
+
+    /// Reload contracts whenever they change instead of waiting for a manual refresh
+    func startWatching() {
+        guard subscription == nil else { return }
+        let cwd = FileManager.default.currentDirectoryPath
+        subscription = try? watchContracts(
+            cwd: cwd,
+            intervalMs: 0,
+            observer: ContractChangeObserver(store: self)
+        )
     }
 
     func loadContracts() {
@@ -175,6 +262,39 @@ class SteadStore: ObservableObject {
             contracts = []
             errorMessage = "Failed to load contracts: \(error.localizedDescription)"
         }
+        loadAttention()
+    }
+
+    /// What needs the user, ranked by stead-core
+    func loadAttention() {
+        let cwd = FileManager.default.currentDirectoryPath
+        guard let report = try? attention(cwd: cwd, includeSessions: true) else {
+            attentionState = .idle
+            attentionItems = []
+            return
+        }
+        attentionState = AttentionState(ffi: report.state)
+        attentionItems = report.items.map { AttentionItem(ffi: $0) }
+    }
+
+    func approve(_ contract: ContractItem) {
+        let cwd = FileManager.default.currentDirectoryPath
+        do {
+            _ = try approveContract(id: contract.id, cwd: cwd, approver: NSUserName())
+            loadContracts()
+        } catch {
+            errorMessage = "Failed to approve contract: \(error.localizedDescription)"
+        }
+    }
+
+    func reject(_ contract: ContractItem, reason: String) {
+        let cwd = FileManager.default.currentDirectoryPath
+        do {
+            _ = try rejectContract(id: contract.id, cwd: cwd, approver: NSUserName(), reason: reason)
+            loadContracts()
+        } catch {
+            errorMessage = "Failed to reject contract: \(error.localizedDescription)"
+        }
     }
 
     func loadSessions() {
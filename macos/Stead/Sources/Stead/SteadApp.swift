@@ -5,8 +5,10 @@ struct SteadApp: App {
     @StateObject private var store = SteadStore()
 
     var body: some Scene {
-        MenuBarExtra("Stead", systemImage: "square.stack.3d.up") {
+        MenuBarExtra {
             MenuBarView(store: store)
+        } label: {
+            Image(systemName: store.attentionState.icon)
         }
         .menuBarExtraStyle(.window)
 
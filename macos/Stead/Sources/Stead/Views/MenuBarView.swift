@@ -21,6 +21,29 @@ struct MenuBarView: View {
 
             Divider()
 
+            // What needs the user, most urgent first
+            if !store.attentionItems.isEmpty {
+                VStack(alignment: .leading, spacing: 6) {
+                    Text("Needs you")
+                        .font(.caption.bold())
+                    ForEach(store.attentionItems.prefix(5)) { item in
+                        VStack(alignment: .leading, spacing: 1) {
+                            Text(item.title)
+                                .font(.caption)
+                                .lineLimit(1)
+                            Text(item.detail.map { "\(item.reason): \($0)" } ?? item.reason)
+                                .font(.caption2)
+                                .foregroundStyle(.secondary)
+                                .lineLimit(1)
+                        }
+                    }
+                }
+                .padding(.horizontal, 12)
+                .padding(.vertical, 8)
+
+                Divider()
+            }
+
             // Contract summary
             if store.contracts.isEmpty {
                 Text("No contracts")
@@ -23,7 +23,7 @@ struct SessionListView: View {
                     Text("No Sessions")
                         .font(.title3)
                         .foregroundStyle(.secondary)
-                    Text("Sessions from Claude, Codex, and OpenCode appear here")
+                    Text("Sessions from Claude, Codex, OpenCode, Gemini, and Aider appear here")
                         .font(.caption)
                         .foregroundStyle(.tertiary)
                 }
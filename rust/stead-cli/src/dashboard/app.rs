@@ -0,0 +1,156 @@
+//! Dashboard state, independent of the terminal
+
+use stead_core::schema::{Contract, ContractStatus};
+use stead_core::usf::SessionSummary;
+
+/// Sessions shown under the selected contract
+pub const RECENT_SESSIONS: usize = 5;
+
+/// One line of the contract list
+#[derive(Debug, PartialEq)]
+pub enum Row<'a> {
+    Header(ContractStatus, usize),
+    Contract(&'a Contract),
+}
+
+pub struct App {
+    /// Contracts in attention order, newest first within a status
+    contracts: Vec<Contract>,
+    selected: usize,
+    /// Recent sessions for this project, newest first
+    pub sessions: Vec<SessionSummary>,
+    /// Result of the last action, shown in the footer
+    pub message: Option<String>,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(contracts: Vec<Contract>) -> Self {
+        let mut app = Self {
+            contracts: Vec::new(),
+            selected: 0,
+            sessions: Vec::new(),
+            message: None,
+            quit: false,
+        };
+        app.set_contracts(contracts);
+        app
+    }
+
+    /// Replace the contract list, keeping the selection on the same
+    /// contract when it still exists
+    pub fn set_contracts(&mut self, mut contracts: Vec<Contract>) {
+        let selected_id = self.selected().map(|c| c.id.clone());
+        stead_core::attention::sort_contracts(&mut contracts);
+        self.contracts = contracts;
+        self.selected = selected_id
+            .and_then(|id| self.contracts.iter().position(|c| c.id == id))
+            .unwrap_or(self.selected)
+            .min(self.contracts.len().saturating_sub(1));
+    }
+
+    pub fn selected(&self) -> Option<&Contract> {
+        self.contracts.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.contracts.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn contract_count(&self) -> usize {
+        self.contracts.len()
+    }
+
+    /// List rows with a header before each status group, and the index of
+    /// the selected contract's row
+    pub fn rows(&self) -> (Vec<Row<'_>>, Option<usize>) {
+        let mut rows = Vec::new();
+        let mut selected_row = None;
+        for (i, contract) in self.contracts.iter().enumerate() {
+            if i == 0 || self.contracts[i - 1].status != contract.status {
+                let count = self.contracts[i..]
+                    .iter()
+                    .take_while(|c| c.status == contract.status)
+                    .count();
+                rows.push(Row::Header(contract.status, count));
+            }
+            if i == self.selected {
+                selected_row = Some(rows.len());
+            }
+            rows.push(Row::Contract(contract));
+        }
+        (rows, selected_row)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_status(task: &str, status: ContractStatus) -> Contract {
+        let mut c = Contract::new(task, "true");
+        c.status = status;
+        c
+    }
+
+    #[test]
+    fn test_groups_in_attention_order() {
+        let app = App::new(vec![
+            with_status("done", ContractStatus::Completed),
+            with_status("broken", ContractStatus::Failed),
+            with_status("queued", ContractStatus::Pending),
+            with_status("also broken", ContractStatus::Failed),
+        ]);
+
+        let (rows, selected) = app.rows();
+        let headers: Vec<_> = rows
+            .iter()
+            .filter_map(|r| match r {
+                Row::Header(status, count) => Some((*status, *count)),
+                Row::Contract(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            headers,
+            vec![
+                (ContractStatus::Failed, 2),
+                (ContractStatus::Pending, 1),
+                (ContractStatus::Completed, 1),
+            ]
+        );
+        assert_eq!(rows.len(), 7);
+        // The first contract row sits under the first header
+        assert_eq!(selected, Some(1));
+    }
+
+    #[test]
+    fn test_selection_follows_contract() {
+        let pending = with_status("queued", ContractStatus::Pending);
+        let mut app = App::new(vec![
+            with_status("broken", ContractStatus::Failed),
+            pending.clone(),
+        ]);
+        app.select_next();
+        app.select_next();
+        assert_eq!(app.selected().unwrap().id, pending.id);
+
+        // A new failure sorts ahead; the selection stays on the same contract
+        app.set_contracts(vec![
+            with_status("broken", ContractStatus::Failed),
+            with_status("new failure", ContractStatus::Failed),
+            pending.clone(),
+        ]);
+        assert_eq!(app.selected().unwrap().id, pending.id);
+
+        app.set_contracts(Vec::new());
+        assert!(app.selected().is_none());
+        app.select_previous();
+        app.select_next();
+    }
+}
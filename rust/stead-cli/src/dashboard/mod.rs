@@ -0,0 +1,205 @@
+//! `stead dashboard` - live terminal view of a project's contracts
+//!
+//! Contracts are grouped by attention (failures first) and refreshed
+//! through the core watch API. The side panes show the selected
+//! contract, the tail of its output and recent sessions in the project.
+//! Verification runs on a worker thread so the view stays live.
+
+mod app;
+mod ui;
+
+use anyhow::Result;
+use app::App;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+use stead_core::config::Config;
+use stead_core::storage::watch::ContractWatcher;
+use stead_core::storage::{Storage, StorageBackend};
+use stead_core::usf::SessionSummary;
+
+/// How often the session list is rescanned
+const SESSION_REFRESH: Duration = Duration::from_secs(30);
+
+/// Work finished off the UI thread
+enum Message {
+    Sessions(Vec<SessionSummary>),
+    Verified(std::result::Result<String, String>),
+}
+
+/// Run the dashboard until the user quits
+pub fn run(
+    root: &Path,
+    backend: Option<StorageBackend>,
+    config: &Config,
+    storage: &dyn Storage,
+    interval: Duration,
+) -> Result<()> {
+    let mut app = App::new(storage.load_all_contracts()?);
+    let mut watcher = ContractWatcher::new(storage)?;
+    let (tx, rx) = mpsc::channel();
+    spawn_session_scanner(
+        root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+        tx.clone(),
+    );
+
+    let project = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| root.display().to_string());
+
+    let _guard = TerminalGuard::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(std::io::stdout()))?;
+
+    let mut next_poll = Instant::now() + interval;
+    while !app.quit {
+        terminal.draw(|frame| ui::draw(frame, &app, &project))?;
+
+        let timeout = next_poll.saturating_duration_since(Instant::now());
+        if event::poll(timeout)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    let ctrl_c = key.modifiers.contains(KeyModifiers::CONTROL)
+                        && key.code == KeyCode::Char('c');
+                    if ctrl_c {
+                        app.quit = true;
+                    } else {
+                        handle_key(key.code, &mut app, root, backend, config, storage, &tx);
+                    }
+                }
+            }
+        }
+
+        if Instant::now() >= next_poll {
+            next_poll = Instant::now() + interval;
+            if !watcher.poll(storage)?.is_empty() {
+                app.set_contracts(storage.load_all_contracts()?);
+            }
+        }
+        drain(&rx, &mut app);
+    }
+
+    Ok(())
+}
+
+fn handle_key(
+    code: KeyCode,
+    app: &mut App,
+    root: &Path,
+    backend: Option<StorageBackend>,
+    config: &Config,
+    storage: &dyn Storage,
+    tx: &Sender<Message>,
+) {
+    use stead_core::commands::{cancel, claim};
+
+    let id = app.selected().map(|c| c.id.clone());
+    let result = match (code, id) {
+        (KeyCode::Char('q') | KeyCode::Esc, _) => {
+            app.quit = true;
+            return;
+        }
+        (KeyCode::Down | KeyCode::Char('j'), _) => {
+            app.select_next();
+            return;
+        }
+        (KeyCode::Up | KeyCode::Char('k'), _) => {
+            app.select_previous();
+            return;
+        }
+        (KeyCode::Char('c'), Some(id)) => claim::claim(&id, &config.executor.owner, storage)
+            .map(|c| format!("Claimed {} as {}", c.id, config.executor.owner)),
+        (KeyCode::Char('x'), Some(id)) => {
+            cancel::cancel(&id, storage).map(|c| format!("Cancelled {}", c.id))
+        }
+        (KeyCode::Char('r'), Some(id)) => retry(&id, storage),
+        (KeyCode::Char('v'), Some(id)) => {
+            spawn_verify(root.to_path_buf(), backend, id.clone(), tx.clone());
+            Ok(format!("Verifying {}...", id))
+        }
+        _ => return,
+    };
+
+    app.message = Some(result.unwrap_or_else(|e| format!("Error: {}", e)));
+    // Reflect our own write right away; the watcher reports it again later
+    if let Ok(contracts) = storage.load_all_contracts() {
+        app.set_contracts(contracts);
+    }
+}
+
+/// Re-queue a failed contract
+fn retry(id: &str, storage: &dyn Storage) -> Result<String> {
+    let mut contract = storage
+        .load_contract(id)?
+        .ok_or_else(|| anyhow::anyhow!("Contract not found: {}", id))?;
+    contract.retry()?;
+    storage.update_contract(&contract)?;
+    Ok(format!("Re-queued {}", id))
+}
+
+/// Verify on a worker thread with its own storage handle; the watcher
+/// picks up the result
+fn spawn_verify(root: PathBuf, backend: Option<StorageBackend>, id: String, tx: Sender<Message>) {
+    std::thread::spawn(move || {
+        let result = (|| -> Result<String> {
+            let (config, storage) = stead_core::commands::open_project(&root, backend)?;
+            let contract = storage
+                .load_contract(&id)?
+                .ok_or_else(|| anyhow::anyhow!("Contract not found: {}", id))?;
+            let contract = stead_core::commands::verify::verify(contract, &config, &*storage)?;
+            Ok(format!(
+                "Verification of {}: {}",
+                contract.id, contract.status
+            ))
+        })();
+        let _ = tx.send(Message::Verified(result.map_err(|e| e.to_string())));
+    });
+}
+
+/// Rescan sessions for this project until the dashboard exits
+fn spawn_session_scanner(root: PathBuf, tx: Sender<Message>) {
+    std::thread::spawn(move || loop {
+        let sessions: Vec<SessionSummary> = stead_core::usf::adapters::discover_all_sessions()
+            .into_iter()
+            .filter(|s| Path::new(&s.project_path).starts_with(&root))
+            .take(app::RECENT_SESSIONS)
+            .collect();
+        if tx.send(Message::Sessions(sessions)).is_err() {
+            return;
+        }
+        std::thread::sleep(SESSION_REFRESH);
+    });
+}
+
+fn drain(rx: &Receiver<Message>, app: &mut App) {
+    while let Ok(message) = rx.try_recv() {
+        match message {
+            Message::Sessions(sessions) => app.sessions = sessions,
+            Message::Verified(result) => {
+                app.message = Some(result.unwrap_or_else(|e| format!("Error: {}", e)));
+            }
+        }
+    }
+}
+
+/// Raw mode and the alternate screen, restored on drop (including panics)
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> Result<Self> {
+        terminal::enable_raw_mode()?;
+        crossterm::execute!(std::io::stdout(), EnterAlternateScreen)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(std::io::stdout(), LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
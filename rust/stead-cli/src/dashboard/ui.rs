@@ -0,0 +1,165 @@
+//! Dashboard rendering
+
+use super::app::{App, Row, RECENT_SESSIONS};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+use stead_core::schema::{Contract, ContractStatus};
+
+const KEYS: &str = "↑/↓ select  c claim  x cancel  v verify  r retry  q quit";
+
+pub fn draw(frame: &mut Frame, app: &App, project: &str) {
+    let [body, footer] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .areas(frame.area());
+    let [list, side] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .areas(body);
+    let [details, output, sessions] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(8),
+            Constraint::Min(3),
+            Constraint::Length(RECENT_SESSIONS as u16 + 2),
+        ])
+        .areas(side);
+
+    draw_list(frame, app, project, list);
+    draw_details(frame, app.selected(), details);
+    draw_output(frame, app.selected(), output);
+    draw_sessions(frame, app, sessions);
+
+    let footer_text = match &app.message {
+        Some(message) => format!("{}  |  {}", message, KEYS),
+        None => KEYS.to_string(),
+    };
+    frame.render_widget(
+        Paragraph::new(footer_text).style(Style::default().fg(Color::DarkGray)),
+        footer,
+    );
+}
+
+fn draw_list(frame: &mut Frame, app: &App, project: &str, area: Rect) {
+    let (rows, selected) = app.rows();
+    let items: Vec<ListItem> = rows
+        .iter()
+        .map(|row| match row {
+            Row::Header(status, count) => ListItem::new(Line::from(Span::styled(
+                format!("{} ({})", status, count),
+                Style::default()
+                    .fg(status_color(*status))
+                    .add_modifier(Modifier::BOLD),
+            ))),
+            Row::Contract(c) => ListItem::new(format!("  {:16} {}", c.id, c.task)),
+        })
+        .collect();
+
+    let title = format!(" {} — {} contract(s) ", project, app.contract_count());
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(selected);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_details(frame: &mut Frame, contract: Option<&Contract>, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title(" Contract ");
+    let Some(c) = contract else {
+        frame.render_widget(Paragraph::new("No contracts").block(block), area);
+        return;
+    };
+
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:8} ", name), Style::default().fg(Color::DarkGray)),
+            Span::raw(value),
+        ])
+    };
+    let lines = vec![
+        field("ID", c.id.clone()),
+        Line::from(vec![
+            Span::styled(
+                format!("{:8} ", "Status"),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                c.status.to_string(),
+                Style::default().fg(status_color(c.status)),
+            ),
+        ]),
+        field("Owner", c.owner.clone().unwrap_or_else(|| "-".to_string())),
+        field("Task", c.task.clone()),
+        field("Verify", c.verification.clone()),
+        field("Created", c.created_at.format("%Y-%m-%d %H:%M").to_string()),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+fn draw_output(frame: &mut Frame, contract: Option<&Contract>, area: Rect) {
+    let output = contract.and_then(|c| c.output.as_deref()).unwrap_or("");
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<&str> = output.lines().collect();
+    let tail = lines[lines.len().saturating_sub(visible)..].join("\n");
+    frame.render_widget(
+        Paragraph::new(tail).block(Block::default().borders(Borders::ALL).title(" Output ")),
+        area,
+    );
+}
+
+fn draw_sessions(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Recent sessions ");
+    if app.sessions.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No sessions for this project")
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block),
+            area,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = app
+        .sessions
+        .iter()
+        .take(RECENT_SESSIONS)
+        .map(|s| {
+            ListItem::new(format!(
+                "{} {:8} {:>4} msgs  {}",
+                s.last_modified
+                    .with_timezone(&chrono::Local)
+                    .format("%m-%d %H:%M"),
+                s.cli.as_str(),
+                s.message_count,
+                s.title
+            ))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(block), area);
+}
+
+fn status_color(status: ContractStatus) -> Color {
+    match status {
+        ContractStatus::Failed => Color::Red,
+        ContractStatus::AwaitingApproval => Color::Magenta,
+        ContractStatus::RollingBack => Color::Yellow,
+        ContractStatus::Executing => Color::Blue,
+        ContractStatus::Verifying => Color::Cyan,
+        ContractStatus::Claimed => Color::LightMagenta,
+        ContractStatus::Ready => Color::LightYellow,
+        ContractStatus::Completed => Color::Green,
+        ContractStatus::Pending | ContractStatus::RolledBack | ContractStatus::Cancelled => {
+            Color::Gray
+        }
+    }
+}
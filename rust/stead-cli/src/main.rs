@@ -1,58 +1,288 @@
 //! stead CLI - Operating environment for agent-driven development
 //!
 //! Commands:
+//! - init: Create .stead/config.toml and contract storage
 //! - run: Create and execute a contract
 //! - list: List contracts with optional filtering
+//! - search: Full-text search over contracts
 //! - show: Display contract details
 //! - verify: Re-run contract verification
+//! - approve/reject: Resolve contracts awaiting human sign-off
+//! - export/import: Move contracts between projects and machines
+//! - watch: Stream contract changes
+//! - attention: What needs a human, most urgent first
+//! - dashboard: Live terminal dashboard (`tui` feature)
+//! - archive/gc: Retire old contracts and reclaim space in .stead/
+//! - db: SQLite maintenance (migrations, integrity checks)
 //! - session: Browse AI CLI sessions
+//! - usage: Token and cost totals across AI CLI sessions
+
+#[cfg(feature = "tui")]
+mod dashboard;
 
 use clap::Parser;
-use stead_core::cli::{Cli, Commands, SessionCommands};
-use stead_core::commands;
+use stead_core::cli::{Cli, Commands, DbCommands, SessionCommands};
+use stead_core::config::Config;
+use stead_core::storage::archive::Archive;
+use stead_core::storage::Storage;
+use stead_core::{commands, project};
 
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let root = || project::resolve(cli.project.as_deref());
+    // Config and contract storage for the project, honouring --storage
+    let open = || -> anyhow::Result<(Config, Box<dyn Storage>)> {
+        commands::open_project(&root()?, cli.storage)
+    };
 
     match cli.command {
-        Commands::Run { task, verify } => {
-            commands::run::execute(&task, &verify, cli.json)?;
+        Commands::Init { register } => {
+            commands::init::execute_with_cwd(cli.storage, register, cli.json, &root()?)?;
+        }
+        Commands::Run {
+            task,
+            verify,
+            requires_approval,
+        } => {
+            let (config, storage) = open()?;
+            commands::run::execute_with_storage(
+                &task,
+                &verify,
+                requires_approval,
+                cli.json,
+                &config,
+                &*storage,
+            )?;
         }
-        Commands::Create { task, verify } => {
-            commands::create::execute(&task, &verify, cli.json)?;
+        Commands::Create {
+            task,
+            verify,
+            parent,
+            await_children,
+            requires_approval,
+            tags,
+        } => {
+            let options = commands::create::CreateOptions {
+                parent,
+                await_children,
+                requires_approval,
+                tags,
+            };
+            let (config, storage) = open()?;
+            commands::create::execute_with_storage(
+                &task, &verify, &options, cli.json, &config, &*storage,
+            )?;
+        }
+        Commands::List {
+            status,
+            owner,
+            tags,
+            limit,
+            offset,
+            all_projects,
+        } => {
+            let options = commands::list::ListOptions {
+                status,
+                owner,
+                tags,
+                limit,
+                offset,
+            };
+            if all_projects {
+                commands::list::execute_all_projects(&options, cli.json, &root()?)?;
+            } else {
+                let (_, storage) = open()?;
+                commands::list::execute_with_storage(&options, cli.json, &*storage)?;
+            }
         }
-        Commands::List { status } => {
-            commands::list::execute(status.as_deref(), cli.json)?;
+        Commands::Search {
+            query,
+            status,
+            tags,
+            limit,
+            archived,
+        } => {
+            let options = commands::search::SearchOptions {
+                status,
+                tags,
+                limit,
+            };
+            if archived {
+                let archive = Archive::new(&root()?);
+                commands::search::execute_with_archive(&query, &options, cli.json, &archive)?;
+            } else {
+                let (_, storage) = open()?;
+                commands::search::execute_with_storage(&query, &options, cli.json, &*storage)?;
+            }
         }
         Commands::Show { id } => {
-            commands::show::execute(&id, cli.json)?;
+            let (_, storage) = open()?;
+            commands::show::execute_with_storage(&id, cli.json, &*storage)?;
         }
         Commands::Verify { id } => {
-            commands::verify::execute(&id, cli.json)?;
+            let (config, storage) = open()?;
+            commands::verify::execute_with_storage(&id, cli.json, &config, &*storage)?;
         }
         Commands::Claim { id, owner } => {
-            commands::claim::execute(&id, &owner, cli.json)?;
+            let (config, storage) = open()?;
+            let owner = owner.unwrap_or(config.executor.owner);
+            commands::claim::execute_with_storage(&id, &owner, cli.json, &*storage)?;
         }
         Commands::Cancel { id } => {
-            commands::cancel::execute(&id, cli.json)?;
+            let (_, storage) = open()?;
+            commands::cancel::execute_with_storage(&id, cli.json, &*storage)?;
+        }
+        Commands::Approve { id, approver } => {
+            let (config, storage) = open()?;
+            commands::approve::execute_with_storage(
+                &id,
+                approver.as_deref(),
+                cli.json,
+                &config,
+                &*storage,
+            )?;
         }
+        Commands::Reject {
+            id,
+            reason,
+            approver,
+        } => {
+            let (config, storage) = open()?;
+            commands::reject::execute_with_storage(
+                &id,
+                &reason,
+                approver.as_deref(),
+                cli.json,
+                &config,
+                &*storage,
+            )?;
+        }
+        Commands::Export { format, status } => {
+            let (_, storage) = open()?;
+            commands::export::execute_with_storage(format, status.as_deref(), &*storage)?;
+        }
+        Commands::Import { file, on_conflict } => {
+            let (_, storage) = open()?;
+            commands::import::execute_with_storage(&file, on_conflict, cli.json, &*storage)?;
+        }
+        Commands::Archive {
+            older_than,
+            statuses,
+            dry_run,
+        } => {
+            let options = commands::archive::ArchiveOptions {
+                older_than,
+                statuses,
+                dry_run,
+            };
+            let (config, storage) = open()?;
+            let archive = Archive::new(&root()?);
+            commands::archive::execute_with_storage(
+                &options, cli.json, &config, &*storage, &archive,
+            )?;
+        }
+        Commands::Watch { interval_ms } => {
+            let (_, storage) = open()?;
+            commands::watch::execute_with_storage(
+                std::time::Duration::from_millis(interval_ms),
+                cli.json,
+                &*storage,
+            )?;
+        }
+        Commands::Attention { no_sessions } => {
+            let root = root()?;
+            let (config, storage) = commands::open_project(&root, cli.storage)?;
+            let sessions = if no_sessions {
+                Vec::new()
+            } else {
+                stead_core::attention::recent_sessions(&root, &config.attention)
+            };
+            commands::attention::execute_with_storage(cli.json, &config, &*storage, &sessions)?;
+        }
+        Commands::Dashboard { interval_ms } => {
+            #[cfg(feature = "tui")]
+            {
+                let (config, storage) = open()?;
+                dashboard::run(
+                    &root()?,
+                    cli.storage,
+                    &config,
+                    &*storage,
+                    std::time::Duration::from_millis(interval_ms),
+                )?;
+            }
+            #[cfg(not(feature = "tui"))]
+            {
+                let _ = interval_ms;
+                anyhow::bail!("This stead was built without the `tui` feature");
+            }
+        }
+        Commands::Gc { dry_run } => {
+            let (config, storage) = open()?;
+            commands::gc::execute_with_storage(dry_run, cli.json, &config, &*storage, &root()?)?;
+        }
+        Commands::Db { command } => match command {
+            DbCommands::Migrate { dry_run } => {
+                commands::db::migrate_with_cwd(dry_run, cli.json, &root()?)?;
+            }
+            DbCommands::Check { repair } => {
+                commands::db::check_with_cwd(repair, cli.json, &root()?)?;
+            }
+        },
         Commands::Session { command } => match command {
             SessionCommands::List {
                 cli: cli_filter,
                 project,
                 limit,
+                refresh,
             } => {
                 commands::session::list_sessions(
                     cli_filter.as_deref(),
                     project.as_deref(),
                     limit,
+                    refresh,
                     cli.json,
                 )?;
             }
-            SessionCommands::Show { id, full } => {
-                commands::session::show_session(&id, full, cli.json)?;
+            SessionCommands::Show { id, full, thinking } => {
+                commands::session::show_session(&id, full, thinking, cli.json)?;
+            }
+            SessionCommands::Follow {
+                id,
+                latest: _,
+                project,
+                from_start,
+                thinking,
+                interval_ms,
+            } => {
+                commands::session::follow_session(
+                    id.as_deref(),
+                    project.as_deref(),
+                    std::time::Duration::from_millis(interval_ms),
+                    from_start,
+                    thinking,
+                    cli.json,
+                )?;
             }
         },
+        Commands::Usage {
+            by,
+            since,
+            cli: cli_filter,
+            project,
+            format,
+            refresh,
+        } => {
+            let options = commands::usage::UsageOptions {
+                group: by,
+                since,
+                cli: cli_filter,
+                project,
+                format,
+                refresh,
+            };
+            commands::usage::execute(&options, cli.json)?;
+        }
     }
 
     Ok(())
@@ -103,7 +103,7 @@ fn test_show_contract() {
         .current_dir(tmp.path())
         .assert()
         .success()
-        .stdout(predicate::str::contains(&format!("Contract: {}", id)))
+        .stdout(predicate::str::contains(format!("Contract: {}", id)))
         .stdout(predicate::str::contains("Status: completed"));
 }
 
@@ -123,9 +123,9 @@ fn test_show_not_found() {
 fn test_verify_command() {
     let tmp = TempDir::new().unwrap();
 
-    // Run a contract first
+    // Create a contract first
     let output = stead()
-        .args(["run", "test task", "--verify", "echo verified", "--json"])
+        .args(["create", "test task", "--verify", "echo verified", "--json"])
         .current_dir(tmp.path())
         .output()
         .unwrap();
@@ -133,13 +133,20 @@ fn test_verify_command() {
     let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
     let id = json["id"].as_str().unwrap();
 
-    // Re-verify
     stead()
         .args(["verify", id])
         .current_dir(tmp.path())
         .assert()
         .success()
         .stdout(predicate::str::contains("PASSED"));
+
+    // A completed contract keeps its result
+    stead()
+        .args(["verify", id])
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be verified again"));
 }
 
 #[test]
@@ -213,6 +220,545 @@ fn test_list_invalid_status() {
         .stderr(predicate::str::contains("Invalid status"));
 }
 
+#[test]
+fn test_create_sub_contracts() {
+    let tmp = TempDir::new().unwrap();
+
+    let output = stead()
+        .args([
+            "create",
+            "parent task",
+            "--verify",
+            "true",
+            "--await-children",
+            "--json",
+        ])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let parent_id = json["id"].as_str().unwrap();
+
+    let output = stead()
+        .args([
+            "create",
+            "child task",
+            "--verify",
+            "true",
+            "--parent",
+            parent_id,
+            "--json",
+        ])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let child_id = json["id"].as_str().unwrap();
+    assert_eq!(json["parent_id"].as_str(), Some(parent_id));
+
+    // Show renders the tree
+    stead()
+        .args(["show", parent_id])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "└── {} [pending] child task",
+            child_id
+        )));
+
+    // Parent can't be verified while the child is incomplete
+    stead()
+        .args(["verify", parent_id])
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("incomplete child"));
+}
+
+#[test]
+fn test_approval_gate() {
+    let tmp = TempDir::new().unwrap();
+
+    let output = stead()
+        .args([
+            "create",
+            "release",
+            "--verify",
+            "true",
+            "--requires-approval",
+            "--json",
+        ])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let id = json["id"].as_str().unwrap();
+
+    stead()
+        .args(["verify", id])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("awaiting approval"));
+
+    stead()
+        .args(["approve", id, "--approver", "alice"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    stead()
+        .args(["show", id])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Status: completed"))
+        .stdout(predicate::str::contains("Approved by: alice"));
+}
+
+#[test]
+fn test_init_and_config() {
+    let tmp = TempDir::new().unwrap();
+
+    stead()
+        .arg("init")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("config.toml (created)"));
+    assert!(tmp.path().join(".stead/stead.db").exists());
+
+    std::fs::write(
+        tmp.path().join(".stead/config.toml"),
+        r#"
+tags = ["nightly"]
+
+[executor]
+command = "true"
+owner = "ci-bot"
+"#,
+    )
+    .unwrap();
+
+    stead()
+        .arg("init")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already exists"));
+
+    let output = stead()
+        .args(["run", "configured", "--verify", "true", "--json"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["status"], "completed");
+    assert_eq!(json["owner"], "ci-bot");
+    assert_eq!(json["tags"], serde_json::json!(["nightly"]));
+}
+
+#[test]
+fn test_db_migrate() {
+    let tmp = TempDir::new().unwrap();
+
+    stead()
+        .args(["db", "migrate", "--dry-run"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would apply"));
+    assert!(!tmp.path().join(".stead/stead.db").exists());
+
+    // Opening storage migrates automatically
+    stead()
+        .arg("list")
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    stead()
+        .args(["db", "migrate"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("up to date"));
+}
+
+#[test]
+fn test_jsonl_backend() {
+    let tmp = TempDir::new().unwrap();
+
+    stead()
+        .args(["--storage", "jsonl", "init"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+    assert!(!tmp.path().join(".stead/stead.db").exists());
+
+    // The configured backend is used without the flag
+    let output = stead()
+        .args(["create", "jsonl task", "--verify", "true", "--json"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let id = json["id"].as_str().unwrap();
+
+    stead()
+        .args(["cancel", id])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let content = std::fs::read_to_string(tmp.path().join(".stead/contracts.jsonl")).unwrap();
+    assert_eq!(content.lines().count(), 1);
+    assert!(content.contains("cancelled"));
+    assert!(!tmp.path().join(".stead/stead.db").exists());
+
+    stead()
+        .args(["db", "migrate"])
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("jsonl"));
+    stead()
+        .args(["db", "check"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // A damaged line fails reads instead of hiding the contract
+    let path = tmp.path().join(".stead/contracts.jsonl");
+    std::fs::write(&path, format!("{}{{invalid json\n", content)).unwrap();
+    stead()
+        .arg("list")
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("line 2"));
+    stead()
+        .args(["db", "check"])
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("line 2"));
+}
+
+#[test]
+fn test_export_import() {
+    let src = TempDir::new().unwrap();
+    let dst = TempDir::new().unwrap();
+
+    let output = stead()
+        .args(["create", "portable", "--verify", "true", "--json"])
+        .current_dir(src.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let id = json["id"].as_str().unwrap().to_string();
+
+    let export = stead()
+        .arg("export")
+        .current_dir(src.path())
+        .output()
+        .unwrap();
+    assert!(export.status.success());
+    let file = src.path().join("export.jsonl");
+    std::fs::write(&file, &export.stdout).unwrap();
+
+    // Import into a JSONL-backed project, twice: the second run conflicts
+    stead()
+        .args(["--storage", "jsonl", "init"])
+        .current_dir(dst.path())
+        .assert()
+        .success();
+    for expected in ["1 new", "1 skipped"] {
+        stead()
+            .arg("import")
+            .arg(&file)
+            .current_dir(dst.path())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(expected));
+    }
+
+    stead()
+        .args(["show", &id])
+        .current_dir(dst.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("portable"));
+
+    stead()
+        .args(["export", "--format", "csv"])
+        .current_dir(dst.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("id,status,task"));
+}
+
+#[test]
+fn test_search() {
+    let tmp = TempDir::new().unwrap();
+
+    for task in ["Add rate limiting to the API", "Write release notes"] {
+        stead()
+            .args(["create", task, "--verify", "true"])
+            .current_dir(tmp.path())
+            .assert()
+            .success();
+    }
+
+    stead()
+        .args(["search", "limiting"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("**limiting**"))
+        .stdout(predicate::str::contains("release").not());
+
+    let output = stead()
+        .args(["search", "rate", "--status", "completed", "--json"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json, serde_json::json!([]));
+}
+
+#[test]
+fn test_archive_and_gc() {
+    let tmp = TempDir::new().unwrap();
+
+    stead()
+        .args(["run", "rotate the logs", "--verify", "true"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // Too recent for the default 30-day retention
+    stead()
+        .args(["archive", "--dry-run"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Would archive 0 contract(s)"));
+
+    stead()
+        .args(["archive", "--older-than", "0m", "--status", "completed"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Archived 1 contract(s)"));
+
+    stead()
+        .arg("list")
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No contracts found"));
+
+    stead()
+        .args(["search", "logs", "--archived"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("**logs**"));
+
+    let output = stead()
+        .args(["gc", "--json"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["archived"], serde_json::json!([]));
+
+    stead()
+        .args(["archive", "--status", "ready"])
+        .current_dir(tmp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("only finished ones"));
+}
+
+#[test]
+fn test_watch_streams_ndjson() {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    use std::time::Duration;
+
+    let tmp = TempDir::new().unwrap();
+    stead()
+        .arg("init")
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let mut watcher = stead()
+        .args(["watch", "--json", "--interval-ms", "50"])
+        .current_dir(tmp.path())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = watcher.stdout.take().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Give the watcher time to take its initial snapshot
+    std::thread::sleep(Duration::from_millis(500));
+    stead()
+        .args(["create", "watched task", "--verify", "true"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let line = rx.recv_timeout(Duration::from_secs(20));
+    watcher.kill().unwrap();
+    watcher.wait().unwrap();
+    let event: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+    assert_eq!(event["event"], "created");
+    assert_eq!(event["contract"]["task"], "watched task");
+}
+
+#[test]
+fn test_attention() {
+    let tmp = TempDir::new().unwrap();
+
+    stead()
+        .args(["attention", "--no-sessions"])
+        .current_dir(tmp.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("State: idle"))
+        .stdout(predicate::str::contains("Nothing needs you."));
+
+    stead()
+        .args(["run", "broken task", "--verify", "false"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    let output = stead()
+        .args(["--json", "attention", "--no-sessions"])
+        .current_dir(tmp.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["state"], "failing");
+    assert_eq!(report["items"][0]["reason"], "failed_verification");
+    assert_eq!(report["items"][0]["title"], "broken task");
+}
+
+#[test]
+fn test_project_root_discovery() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::create_dir(tmp.path().join(".git")).unwrap();
+    let nested = tmp.path().join("src/auth");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    stead()
+        .args(["create", "root task", "--verify", "true"])
+        .current_dir(tmp.path())
+        .assert()
+        .success();
+
+    // Subdirectories share the root database instead of creating their own
+    stead()
+        .arg("list")
+        .current_dir(&nested)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("root task"));
+    assert!(!nested.join(".stead").exists());
+
+    // Explicit overrides
+    let other = TempDir::new().unwrap();
+    stead()
+        .args(["--project", tmp.path().to_str().unwrap(), "list"])
+        .current_dir(other.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("root task"));
+    stead()
+        .arg("list")
+        .env("STEAD_PROJECT", tmp.path())
+        .current_dir(other.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("root task"));
+}
+
+#[test]
+fn test_list_all_projects() {
+    let registry = TempDir::new().unwrap();
+    let registry_db = registry.path().join("registry.db");
+    let app = TempDir::new().unwrap();
+    let lib = TempDir::new().unwrap();
+
+    stead()
+        .args(["create", "app task", "--verify", "true"])
+        .env("STEAD_REGISTRY", &registry_db)
+        .current_dir(app.path())
+        .assert()
+        .success();
+
+    // Listing never turns the registry on
+    stead()
+        .args(["list", "--all-projects"])
+        .env("STEAD_REGISTRY", &registry_db)
+        .current_dir(app.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("stead init --register"));
+    assert!(!registry_db.exists());
+
+    // Enables the registry and registers the current project
+    stead()
+        .args(["init", "--register"])
+        .env("STEAD_REGISTRY", &registry_db)
+        .current_dir(app.path())
+        .assert()
+        .success();
+    stead()
+        .args(["list", "--all-projects"])
+        .env("STEAD_REGISTRY", &registry_db)
+        .current_dir(app.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("PROJECT"))
+        .stdout(predicate::str::contains("app task"));
+
+    // Other projects register themselves once the registry exists
+    stead()
+        .args(["create", "lib task", "--verify", "true"])
+        .env("STEAD_REGISTRY", &registry_db)
+        .current_dir(lib.path())
+        .assert()
+        .success();
+
+    let output = stead()
+        .args(["list", "--all-projects", "--json"])
+        .env("STEAD_REGISTRY", &registry_db)
+        .current_dir(app.path())
+        .output()
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let contracts = json.as_array().unwrap();
+    assert_eq!(contracts.len(), 2);
+    assert!(contracts.iter().all(|c| c["project"].is_string()));
+}
+
 // Session command tests
 
 #[test]
@@ -244,13 +790,439 @@ fn test_session_list_json() {
         .stdout(predicate::str::starts_with("["));
 }
 
+#[test]
+fn test_session_index() {
+    let home = TempDir::new().unwrap();
+    let project = home.path().join(".claude/projects/-work-app");
+    std::fs::create_dir_all(&project).unwrap();
+    let entry = |text: &str| {
+        serde_json::json!({
+            "type": "user",
+            "sessionId": "abc123",
+            "timestamp": "2026-01-01T10:00:00Z",
+            "cwd": "/work/app",
+            "message": {"role": "user", "content": [{"type": "text", "text": text}]}
+        })
+        .to_string()
+    };
+    let session_file = project.join("abc123.jsonl");
+    std::fs::write(&session_file, entry("Add a login page")).unwrap();
+    let index = home.path().join("sessions.db");
+
+    let list = || {
+        let output = stead()
+            .args(["--json", "session", "list"])
+            .env("HOME", home.path())
+            .env("STEAD_SESSION_INDEX", &index)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+    assert_eq!(list()[0]["title"], "Add a login page");
+    assert!(index.exists());
+
+    // A changed file is re-read from disk, not served from the index
+    std::fs::write(&session_file, entry("Add a signup page")).unwrap();
+    assert_eq!(list()[0]["title"], "Add a signup page");
+
+    stead()
+        .args(["session", "list", "--refresh"])
+        .env("HOME", home.path())
+        .env("STEAD_SESSION_INDEX", &index)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Add a signup page"));
+}
+
+#[test]
+fn test_session_cost() {
+    let home = TempDir::new().unwrap();
+    let project = home.path().join(".claude/projects/-work-app");
+    std::fs::create_dir_all(&project).unwrap();
+    let lines = [
+        serde_json::json!({
+            "type": "user", "uuid": "u1", "sessionId": "abc123", "cwd": "/work/app",
+            "timestamp": "2026-01-01T10:00:00Z",
+            "message": {"role": "user", "content": [{"type": "text", "text": "Add a login page"}]}
+        }),
+        serde_json::json!({
+            "type": "assistant", "uuid": "a1", "sessionId": "abc123",
+            "timestamp": "2026-01-01T10:00:05Z",
+            "message": {"id": "msg_1", "role": "assistant", "model": "claude-sonnet-4-5",
+                        "content": [{"type": "text", "text": "Done."}],
+                        "usage": {"input_tokens": 1000000, "output_tokens": 100000}}
+        }),
+    ];
+    let content: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    std::fs::write(project.join("abc123.jsonl"), content.join("\n")).unwrap();
+    // Sonnet repriced at $1/M in, $10/M out
+    let pricing = home.path().join("pricing.toml");
+    std::fs::write(
+        &pricing,
+        "[models.\"claude-sonnet-4\"]\ninput = 1.0\noutput = 10.0\n",
+    )
+    .unwrap();
+    let run = |args: &[&str]| {
+        stead()
+            .args(args)
+            .env("HOME", home.path())
+            .env("STEAD_SESSION_INDEX", home.path().join("sessions.db"))
+            .env("STEAD_PRICING", &pricing)
+            .assert()
+            .success()
+    };
+
+    let output = run(&["--json", "session", "list"])
+        .get_output()
+        .stdout
+        .clone();
+    let sessions: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(sessions[0]["model"], "claude-sonnet-4-5");
+    assert_eq!(sessions[0]["tokens"]["input"], 1_000_000);
+    assert_eq!(sessions[0]["tokens"]["output"], 100_000);
+    assert_eq!(sessions[0]["cost"], 2.0);
+
+    run(&["session", "list"]).stdout(predicate::str::contains("1.1M ~$2.00"));
+    run(&["session", "show", "claude-abc123"])
+        .stdout(predicate::str::contains("Tokens:   1.0M in, 100.0k out"))
+        .stdout(predicate::str::contains("Cost:     ~$2.00"));
+}
+
+#[test]
+fn test_usage_report() {
+    let home = TempDir::new().unwrap();
+    let session = |project: &str, id: &str, input: u64| {
+        let dir = home.path().join(".claude/projects").join(project);
+        std::fs::create_dir_all(&dir).unwrap();
+        let line = serde_json::json!({
+            "type": "assistant", "uuid": "a1", "sessionId": id,
+            "cwd": format!("/work/{}", project), "timestamp": "2026-01-01T10:00:05Z",
+            "message": {"id": "msg_1", "role": "assistant", "model": "claude-sonnet-4-5",
+                        "content": [{"type": "text", "text": "Done."}],
+                        "usage": {"input_tokens": input, "output_tokens": 0}}
+        });
+        std::fs::write(dir.join(format!("{}.jsonl", id)), line.to_string()).unwrap();
+    };
+    session("app", "s1", 1_000_000);
+    session("app", "s2", 1_000_000);
+    session("api", "s3", 3_000_000);
+    let run = |args: &[&str]| {
+        stead()
+            .args(args)
+            .env("HOME", home.path())
+            .env("STEAD_SESSION_INDEX", home.path().join("sessions.db"))
+            .env("STEAD_PRICING", home.path().join("pricing.toml"))
+            .assert()
+            .success()
+    };
+
+    let output = run(&["--json", "usage"]).get_output().stdout.clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(report["group"], "project");
+    assert_eq!(report["rows"][0]["key"], "/work/api");
+    assert_eq!(report["rows"][0]["cost"], 9.0);
+    assert_eq!(report["rows"][1]["sessions"], 2);
+    assert_eq!(report["total"]["tokens"]["input"], 5_000_000);
+    assert_eq!(report["total"]["cost"], 15.0);
+
+    run(&["usage", "--by", "model"])
+        .stdout(predicate::str::contains("claude-sonnet-4-5"))
+        .stdout(predicate::str::contains("~$15.00"));
+    run(&[
+        "usage",
+        "--by",
+        "cli",
+        "--format",
+        "csv",
+        "--project",
+        "app",
+    ])
+    .stdout(predicate::str::contains(
+        "claude,2,2000000,0,0,0,6.0000,true,0",
+    ));
+    run(&["usage", "--since", "1d"]).stdout(predicate::str::contains("No sessions found."));
+}
+
+#[test]
+fn test_session_show_thinking() {
+    let home = TempDir::new().unwrap();
+    let day = home.path().join(".codex/sessions/2026/01/04");
+    std::fs::create_dir_all(&day).unwrap();
+    let lines = [
+        serde_json::json!({"timestamp": "2026-01-04T10:00:00Z", "type": "session_meta",
+                           "payload": {"id": "xyz", "cwd": "/work/app"}}),
+        serde_json::json!({"timestamp": "2026-01-04T10:00:01Z", "type": "response_item",
+                           "payload": {"type": "reasoning",
+                                       "summary": [{"type": "summary_text", "text": "Weighing a rewrite"}]}}),
+        serde_json::json!({"timestamp": "2026-01-04T10:00:02Z", "type": "response_item",
+                           "payload": {"type": "message", "role": "assistant",
+                                       "content": [{"type": "text", "text": "Patched the parser."}]}}),
+    ];
+    let content: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    std::fs::write(day.join("rollout-2026-01-04-xyz.jsonl"), content.join("\n")).unwrap();
+    let show = |args: &[&str]| {
+        stead()
+            .args(["session", "show", "codex-xyz"])
+            .args(args)
+            .env("HOME", home.path())
+            .assert()
+            .success()
+    };
+
+    show(&["--full"])
+        .stdout(predicate::str::contains("Patched the parser."))
+        .stdout(predicate::str::contains("Weighing a rewrite").not());
+    show(&["--thinking"])
+        .stdout(predicate::str::contains("<thinking>"))
+        .stdout(predicate::str::contains("Weighing a rewrite"));
+}
+
+#[test]
+fn test_session_list_gemini() {
+    let home = TempDir::new().unwrap();
+    let chats = home.path().join(".gemini/tmp/0123abcd/chats");
+    std::fs::create_dir_all(&chats).unwrap();
+    let chat = serde_json::json!({
+        "sessionId": "5f2c9a1e-0000-4000-8000-000000000001",
+        "startTime": "2026-01-04T10:00:00Z",
+        "lastUpdated": "2026-01-04T10:00:05Z",
+        "messages": [
+            {"id": "m1", "timestamp": "2026-01-04T10:00:00Z", "type": "user",
+             "content": "Port the parser to Rust"}
+        ]
+    });
+    std::fs::write(
+        chats.join("session-2026-01-04T10-00-5f2c9a1e.json"),
+        chat.to_string(),
+    )
+    .unwrap();
+
+    let output = stead()
+        .args(["--json", "session", "list", "--cli", "gemini"])
+        .env("HOME", home.path())
+        .env("STEAD_SESSION_INDEX", home.path().join("sessions.db"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let sessions: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sessions[0]["cli"], "gemini");
+    assert_eq!(sessions[0]["title"], "Port the parser to Rust");
+
+    stead()
+        .args([
+            "session",
+            "show",
+            "gemini-5f2c9a1e-0000-4000-8000-000000000001",
+        ])
+        .env("HOME", home.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Source:   Gemini"));
+}
+
+#[test]
+fn test_session_list_aider() {
+    let home = TempDir::new().unwrap();
+    let code = TempDir::new().unwrap();
+    let project = code.path().join("app");
+    std::fs::create_dir(&project).unwrap();
+    std::fs::write(
+        project.join(".aider.chat.history.md"),
+        "# aider chat started at 2026-01-04 10:00:00\n\n\
+         #### add a hello function\n\n\
+         Done.\n\n\
+         > Applied edit to main.py\n",
+    )
+    .unwrap();
+
+    // Found through STEAD_PROJECT_ROOTS (a directory of projects)
+    let output = stead()
+        .args(["--json", "session", "list", "--cli", "aider"])
+        .env("HOME", home.path())
+        .env("STEAD_SESSION_INDEX", home.path().join("sessions.db"))
+        .env("STEAD_PROJECT_ROOTS", code.path())
+        .current_dir(home.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let sessions: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sessions[0]["cli"], "aider");
+    assert_eq!(sessions[0]["title"], "add a hello function");
+
+    // ...and from inside the project
+    let id = sessions[0]["id"].as_str().unwrap().to_string();
+    stead()
+        .args(["session", "show", &id, "--full"])
+        .env("HOME", home.path())
+        .current_dir(&project)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("TOOL CALL: edit"));
+}
+
+#[test]
+fn test_session_list_external_adapter() {
+    let home = TempDir::new().unwrap();
+    let script = home.path().join("acme.sh");
+    std::fs::write(
+        &script,
+        r#"case "$1" in
+  list) echo '[{"id": "42", "project_path": "/work/app", "title": "Port the parser",
+                "created": "2026-01-05T09:00:00Z", "message_count": 1}]' ;;
+  load) echo '{"id": "42", "version": "1.0.0", "source": {"cli": "universal"},
+               "project": {"path": "/work/app"},
+               "model": {"provider": "acme", "model": "acme-1"},
+               "timeline": [{"type": "user", "id": "u1",
+                             "timestamp": "2026-01-05T09:00:00Z", "content": "Port the parser"}],
+               "metadata": {"created": "2026-01-05T09:00:00Z",
+                            "last_modified": "2026-01-05T09:00:00Z"}}' ;;
+esac
+"#,
+    )
+    .unwrap();
+    let config = home.path().join("adapters.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "[[adapter]]\nname = \"acme\"\ncommand = \"sh\"\nargs = [{:?}]\n",
+            script.to_string_lossy()
+        ),
+    )
+    .unwrap();
+
+    let output = stead()
+        .args(["--json", "session", "list", "--cli", "acme"])
+        .env("HOME", home.path())
+        .env("STEAD_SESSION_INDEX", home.path().join("sessions.db"))
+        .env("STEAD_ADAPTERS", &config)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let sessions: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sessions.as_array().unwrap().len(), 1);
+    assert_eq!(sessions[0]["id"], "acme-42");
+    assert_eq!(sessions[0]["cli"], "universal");
+
+    stead()
+        .args(["session", "show", "acme-42", "--full"])
+        .env("HOME", home.path())
+        .env("STEAD_ADAPTERS", &config)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Port the parser"));
+}
+
+#[test]
+fn test_session_list_reports_failing_adapter() {
+    let home = TempDir::new().unwrap();
+    let config = home.path().join("adapters.toml");
+    std::fs::write(
+        &config,
+        "[[adapter]]\nname = \"broken\"\ncommand = \"sh\"\nargs = [\"-c\", \"echo offline >&2; exit 1\"]\n",
+    )
+    .unwrap();
+
+    stead()
+        .args(["session", "list"])
+        .env("HOME", home.path())
+        .env("STEAD_SESSION_INDEX", home.path().join("sessions.db"))
+        .env("STEAD_ADAPTERS", &config)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Warning: broken:"))
+        .stderr(predicate::str::contains("offline"));
+}
+
+#[test]
+fn test_session_follow_streams_appended_entries() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::process::Stdio;
+    use std::time::Duration;
+
+    let home = TempDir::new().unwrap();
+    let project = home.path().join(".claude/projects/-work-app");
+    std::fs::create_dir_all(&project).unwrap();
+    let entry = |text: &str| {
+        serde_json::json!({
+            "type": "user",
+            "sessionId": "abc123",
+            "timestamp": "2026-01-01T10:00:00Z",
+            "cwd": "/work/app",
+            "message": {"role": "user", "content": [{"type": "text", "text": text}]}
+        })
+        .to_string()
+    };
+    let session_file = project.join("abc123.jsonl");
+    std::fs::write(&session_file, format!("{}\n", entry("Add a login page"))).unwrap();
+
+    let mut follower = stead()
+        .args([
+            "--json",
+            "session",
+            "follow",
+            "--latest",
+            "--project",
+            "app",
+        ])
+        .args(["--interval-ms", "50"])
+        .env("HOME", home.path())
+        .env("STEAD_SESSION_INDEX", home.path().join("sessions.db"))
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let stdout = follower.stdout.take().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if tx.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+
+    // Give the follower time to read what's already there
+    std::thread::sleep(Duration::from_millis(500));
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&session_file)
+        .unwrap();
+    writeln!(file, "{}", entry("Now add tests")).unwrap();
+
+    // Only the appended entry is streamed, not the existing one
+    let line = rx.recv_timeout(Duration::from_secs(20));
+    follower.kill().unwrap();
+    follower.wait().unwrap();
+    let entry: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+    assert_eq!(entry["type"], "user");
+    assert_eq!(entry["content"], "Now add tests");
+}
+
 #[test]
 fn test_session_show_not_found() {
     stead()
         .args(["session", "show", "nonexistent-session-id"])
         .assert()
-        .success() // Command succeeds but prints error to stderr
-        .stderr(predicate::str::contains("Session not found"));
+        .failure()
+        .stderr(predicate::str::contains(
+            "Cannot load session nonexistent-session-id",
+        ))
+        .stderr(predicate::str::contains("Unknown CLI prefix: nonexistent"));
+}
+
+#[test]
+fn test_session_show_missing_session_fails() {
+    let tmp = TempDir::new().unwrap();
+    std::fs::create_dir_all(tmp.path().join(".claude/projects")).unwrap();
+
+    stead()
+        .env("HOME", tmp.path())
+        .args(["session", "show", "claude-does-not-exist"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Session not found: claude-does-not-exist",
+        ));
 }
 
 #[test]
@@ -0,0 +1,419 @@
+//! Attention priority: what needs a human, most urgent first
+//!
+//! Every frontend (CLI, terminal dashboard, macOS app) ranks contracts
+//! through this module so they agree on ordering. `assess` turns a
+//! project's contracts and recent sessions into a "needs me" list with a
+//! reason per item, plus one aggregate `AttentionState` for menu-bar icons.
+
+use crate::config::AttentionConfig;
+use crate::schema::{Contract, ContractStatus};
+use crate::usf::{TimelineEntry, UniversalSession, UniversalTool};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::path::Path;
+
+/// Contract statuses, most urgent first
+pub const STATUS_ORDER: [ContractStatus; 11] = [
+    ContractStatus::Failed,
+    ContractStatus::AwaitingApproval,
+    ContractStatus::RollingBack,
+    ContractStatus::Executing,
+    ContractStatus::Verifying,
+    ContractStatus::Claimed,
+    ContractStatus::Ready,
+    ContractStatus::Pending,
+    ContractStatus::Completed,
+    ContractStatus::RolledBack,
+    ContractStatus::Cancelled,
+];
+
+/// Position of `status` in `STATUS_ORDER` (lower = more urgent)
+pub fn status_rank(status: ContractStatus) -> usize {
+    STATUS_ORDER
+        .iter()
+        .position(|s| *s == status)
+        .unwrap_or(STATUS_ORDER.len())
+}
+
+/// Sort contracts by attention: status rank, then newest first
+pub fn sort_contracts(contracts: &mut [Contract]) {
+    contracts.sort_by(|a, b| {
+        status_rank(a.status)
+            .cmp(&status_rank(b.status))
+            .then(b.created_at.cmp(&a.created_at))
+    });
+}
+
+/// Why something needs a human, most urgent first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttentionReason {
+    /// Verification failed (or the change was rejected)
+    FailedVerification,
+    /// Verification passed; a human has to sign off
+    ApprovalPending,
+    /// An agent session in this project ended on a question
+    WaitingOnQuestion,
+    /// Claimed or executing for longer than `stale_claim_after_mins`
+    StaleClaim,
+}
+
+impl std::fmt::Display for AttentionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AttentionReason::FailedVerification => "failed verification",
+            AttentionReason::ApprovalPending => "approval pending",
+            AttentionReason::WaitingOnQuestion => "waiting on a question",
+            AttentionReason::StaleClaim => "stale claim",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// One thing that needs a human
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttentionItem {
+    pub reason: AttentionReason,
+    /// Contract the item is about (None for session items)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_id: Option<String>,
+    /// Session the item is about (None for contract items)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Contract task or session title
+    pub title: String,
+    /// Context for the reason: the question, the owner, the last output line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// When the condition began
+    pub since: DateTime<Utc>,
+}
+
+/// Aggregate state of a project, for menu-bar icons (ordered by severity)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttentionState {
+    /// Nothing running, nothing waiting
+    Idle,
+    /// Contracts are in flight; nothing needs a human
+    Working,
+    /// Something is waiting on a human (approval, question, stale claim)
+    NeedsInput,
+    /// At least one contract failed
+    Failing,
+}
+
+impl std::fmt::Display for AttentionState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            AttentionState::Idle => "idle",
+            AttentionState::Working => "working",
+            AttentionState::NeedsInput => "needs input",
+            AttentionState::Failing => "failing",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Result of `assess`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Attention {
+    pub state: AttentionState,
+    /// Items needing a human, by reason, then longest waiting first
+    pub items: Vec<AttentionItem>,
+    /// Contracts currently claimed, executing, verifying or rolling back
+    pub active: usize,
+}
+
+/// Statuses counted as in flight
+fn is_active(status: ContractStatus) -> bool {
+    matches!(
+        status,
+        ContractStatus::Claimed
+            | ContractStatus::Executing
+            | ContractStatus::Verifying
+            | ContractStatus::RollingBack
+    )
+}
+
+/// Rank what needs a human across a project's contracts and sessions
+pub fn assess(
+    contracts: &[Contract],
+    sessions: &[UniversalSession],
+    config: &AttentionConfig,
+    now: DateTime<Utc>,
+) -> Attention {
+    let stale_after = Duration::minutes(config.stale_claim_after_mins as i64);
+
+    let mut items: Vec<AttentionItem> = contracts
+        .iter()
+        .filter_map(|c| contract_item(c, stale_after, now))
+        .collect();
+    items.extend(sessions.iter().filter_map(question_item));
+    items.sort_by(|a, b| a.reason.cmp(&b.reason).then(a.since.cmp(&b.since)));
+
+    let active = contracts.iter().filter(|c| is_active(c.status)).count();
+    let state = match items.first().map(|i| i.reason) {
+        Some(AttentionReason::FailedVerification) => AttentionState::Failing,
+        Some(_) => AttentionState::NeedsInput,
+        None if active > 0 => AttentionState::Working,
+        None => AttentionState::Idle,
+    };
+
+    Attention {
+        state,
+        items,
+        active,
+    }
+}
+
+fn contract_item(c: &Contract, stale_after: Duration, now: DateTime<Utc>) -> Option<AttentionItem> {
+    let (reason, detail, since) = match c.status {
+        ContractStatus::Failed => {
+            // A rejection carries its reason; a failed run its last output line
+            let detail = match &c.approval {
+                Some(a) if !a.approved => a.reason.clone(),
+                _ => c
+                    .output
+                    .as_deref()
+                    .and_then(|o| o.lines().rev().find(|l| !l.trim().is_empty()))
+                    .map(|l| l.trim().to_string()),
+            };
+            (
+                AttentionReason::FailedVerification,
+                detail,
+                c.completed_at.unwrap_or(c.created_at),
+            )
+        }
+        ContractStatus::AwaitingApproval => (AttentionReason::ApprovalPending, None, c.created_at),
+        ContractStatus::Claimed | ContractStatus::Executing => {
+            let claimed_at = c.claimed_at?;
+            if now - claimed_at < stale_after {
+                return None;
+            }
+            (AttentionReason::StaleClaim, c.owner.clone(), claimed_at)
+        }
+        _ => return None,
+    };
+
+    Some(AttentionItem {
+        reason,
+        contract_id: Some(c.id.clone()),
+        session_id: None,
+        title: c.task.clone(),
+        detail,
+        since,
+    })
+}
+
+/// A session whose last exchange is the agent asking something: an
+/// unanswered ask tool call, or an assistant message ending in `?`
+fn question_item(session: &UniversalSession) -> Option<AttentionItem> {
+    let last = session
+        .timeline
+        .iter()
+        .rev()
+        .find(|e| !matches!(e, TimelineEntry::System(_)))?;
+
+    let (question, since) = match last {
+        TimelineEntry::ToolCall(call) if call.tool == UniversalTool::Ask => {
+            (ask_question(&call.input), call.timestamp)
+        }
+        TimelineEntry::Assistant(msg) if msg.content.trim_end().ends_with('?') => {
+            let question = msg.content.trim().lines().next_back().map(str::to_string);
+            (question, msg.timestamp)
+        }
+        _ => return None,
+    };
+
+    Some(AttentionItem {
+        reason: AttentionReason::WaitingOnQuestion,
+        contract_id: None,
+        session_id: Some(session.id.clone()),
+        title: session.title(),
+        detail: question,
+        since,
+    })
+}
+
+/// Question text from an ask tool's input (`question`, or the first of
+/// `questions` as Claude's AskUserQuestion sends them)
+fn ask_question(input: &serde_json::Value) -> Option<String> {
+    input
+        .get("question")
+        .or_else(|| input.get("questions")?.get(0)?.get("question"))
+        .and_then(|q| q.as_str())
+        .map(str::to_string)
+}
+
+/// Sessions for the project at `root` modified within the configured
+/// window, fully loaded
+pub fn recent_sessions(root: &Path, config: &AttentionConfig) -> Vec<UniversalSession> {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let cutoff = Utc::now() - Duration::hours(config.session_window_hours as i64);
+    let registry = crate::usf::adapters::AdapterRegistry::installed();
+    registry
+        .discover(false)
+        .sessions
+        .into_iter()
+        .take_while(|s| s.last_modified >= cutoff)
+        .filter(|s| Path::new(&s.project_path).starts_with(&root))
+        .filter_map(|s| registry.load(&s.id).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usf::{AssistantMessage, CliType, ToolCall, UserMessage};
+
+    fn with_status(task: &str, status: ContractStatus) -> Contract {
+        let mut c = Contract::new(task, "true");
+        c.status = status;
+        c
+    }
+
+    fn session(last: TimelineEntry) -> UniversalSession {
+        let mut s = UniversalSession::new(CliType::Claude, Some("abc".into()), "/p".into());
+        s.timeline.push(TimelineEntry::User(UserMessage {
+            id: "u1".into(),
+            timestamp: Utc::now(),
+            content: "Refactor the parser".into(),
+        }));
+        s.timeline.push(last);
+        s
+    }
+
+    #[test]
+    fn test_status_order() {
+        let mut contracts = vec![
+            with_status("done", ContractStatus::Completed),
+            with_status("queued", ContractStatus::Pending),
+            with_status("running", ContractStatus::Executing),
+            with_status("broken", ContractStatus::Failed),
+        ];
+        sort_contracts(&mut contracts);
+        let statuses: Vec<_> = contracts.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ContractStatus::Failed,
+                ContractStatus::Executing,
+                ContractStatus::Pending,
+                ContractStatus::Completed,
+            ]
+        );
+        assert!(status_rank(ContractStatus::Failed) < status_rank(ContractStatus::Verifying));
+    }
+
+    #[test]
+    fn test_reasons_are_ranked() {
+        let now = Utc::now();
+        let mut stale = with_status("stuck", ContractStatus::Claimed);
+        stale.owner = Some("agent-1".into());
+        stale.claimed_at = Some(now - Duration::hours(3));
+        let mut fresh = with_status("busy", ContractStatus::Executing);
+        fresh.claimed_at = Some(now - Duration::minutes(5));
+        let mut failed = with_status("broken", ContractStatus::Failed);
+        failed.output = Some("running tests\ntest auth ... FAILED\n\n".into());
+
+        let contracts = vec![
+            stale,
+            fresh,
+            with_status("review me", ContractStatus::AwaitingApproval),
+            failed,
+            with_status("done", ContractStatus::Completed),
+        ];
+        let attention = assess(&contracts, &[], &AttentionConfig::default(), now);
+
+        let reasons: Vec<_> = attention.items.iter().map(|i| i.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                AttentionReason::FailedVerification,
+                AttentionReason::ApprovalPending,
+                AttentionReason::StaleClaim,
+            ]
+        );
+        assert_eq!(
+            attention.items[0].detail.as_deref(),
+            Some("test auth ... FAILED")
+        );
+        assert_eq!(attention.items[2].detail.as_deref(), Some("agent-1"));
+        assert_eq!(attention.state, AttentionState::Failing);
+        assert_eq!(attention.active, 2);
+    }
+
+    #[test]
+    fn test_aggregate_state() {
+        let config = AttentionConfig::default();
+        let now = Utc::now();
+        assert_eq!(assess(&[], &[], &config, now).state, AttentionState::Idle);
+
+        // Claimed before claim times were recorded: active, never stale
+        let running = with_status("running", ContractStatus::Claimed);
+        assert_eq!(
+            assess(std::slice::from_ref(&running), &[], &config, now).state,
+            AttentionState::Working
+        );
+
+        let review = with_status("review", ContractStatus::AwaitingApproval);
+        assert_eq!(
+            assess(&[running, review], &[], &config, now).state,
+            AttentionState::NeedsInput
+        );
+    }
+
+    #[test]
+    fn test_sessions_waiting_on_a_question() {
+        let now = Utc::now();
+        let asked = session(TimelineEntry::ToolCall(ToolCall {
+            id: "t1".into(),
+            timestamp: now,
+            tool: UniversalTool::Ask,
+            input: serde_json::json!({"questions": [{"question": "Keep the old API?"}]}),
+            original_tool: Some("AskUserQuestion".into()),
+        }));
+        let wondering = session(TimelineEntry::Assistant(AssistantMessage {
+            id: "a1".into(),
+            timestamp: now,
+            content: "Done with step one.\nShould I also update the docs?".into(),
+            thinking: None,
+            usage: None,
+        }));
+        let finished = session(TimelineEntry::Assistant(AssistantMessage {
+            id: "a2".into(),
+            timestamp: now,
+            content: "All done.".into(),
+            thinking: None,
+            usage: None,
+        }));
+
+        let attention = assess(
+            &[],
+            &[asked, wondering, finished],
+            &AttentionConfig::default(),
+            now,
+        );
+        let details: Vec<_> = attention
+            .items
+            .iter()
+            .map(|i| (i.reason, i.detail.as_deref()))
+            .collect();
+        assert_eq!(
+            details,
+            vec![
+                (
+                    AttentionReason::WaitingOnQuestion,
+                    Some("Keep the old API?")
+                ),
+                (
+                    AttentionReason::WaitingOnQuestion,
+                    Some("Should I also update the docs?")
+                ),
+            ]
+        );
+        assert_eq!(attention.items[0].title, "Refactor the parser");
+        assert_eq!(attention.state, AttentionState::NeedsInput);
+    }
+}
@@ -1,6 +1,11 @@
 //! CLI argument parsing with clap
 
+use crate::commands::export::ExportFormat;
+use crate::commands::import::OnConflict;
+use crate::commands::usage::{UsageFormat, UsageGroup};
+use crate::storage::StorageBackend;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 /// stead - Operating environment for agent-driven development
 #[derive(Parser, Debug)]
@@ -12,12 +17,27 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub json: bool,
 
+    /// Project root (default: $STEAD_PROJECT, else nearest .stead or git root)
+    #[arg(long)]
+    pub project: Option<PathBuf>,
+
+    /// Contract storage backend: sqlite or jsonl (default: from config)
+    #[arg(long)]
+    pub storage: Option<StorageBackend>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
+    /// Initialize .stead/ with a config file and contract storage
+    Init {
+        /// Enable the global project registry and register this project
+        #[arg(long)]
+        register: bool,
+    },
+
     /// Create and execute a contract with verification
     Run {
         /// The task description for the agent
@@ -26,6 +46,10 @@ pub enum Commands {
         /// Shell command to verify task completion (exit 0 = pass)
         #[arg(long)]
         verify: String,
+
+        /// Require human approval before the contract counts as completed
+        #[arg(long)]
+        requires_approval: bool,
     },
 
     /// Create a contract without executing it
@@ -36,13 +60,71 @@ pub enum Commands {
         /// Shell command to verify task completion (exit 0 = pass)
         #[arg(long)]
         verify: String,
+
+        /// Spawn as a sub-contract of this contract ID
+        #[arg(long)]
+        parent: Option<String>,
+
+        /// Only allow verification once all sub-contracts are completed
+        #[arg(long)]
+        await_children: bool,
+
+        /// Require human approval before the contract counts as completed
+        #[arg(long)]
+        requires_approval: bool,
+
+        /// Tag the contract (repeatable; added to the config's default tags)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
     },
 
     /// List contracts with optional status filter
     List {
-        /// Filter by status: pending, ready, claimed, executing, verifying, completed, failed, cancelled
+        /// Filter by status: pending, ready, claimed, executing, verifying, awaitingapproval, completed, failed, cancelled
         #[arg(long)]
         status: Option<String>,
+
+        /// Only contracts claimed by this owner
+        #[arg(long)]
+        owner: Option<String>,
+
+        /// Only contracts with this tag (repeatable; all must match)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Maximum number of contracts to show
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// Skip this many contracts (for paging with --limit)
+        #[arg(long, default_value = "0")]
+        offset: usize,
+
+        /// List contracts from every project in the global registry
+        #[arg(long)]
+        all_projects: bool,
+    },
+
+    /// Full-text search over contract tasks, verification commands and output
+    Search {
+        /// Words to search for (all must match)
+        query: String,
+
+        /// Only show contracts with this status
+        #[arg(long)]
+        status: Option<String>,
+
+        /// Only show contracts with this tag (repeatable; all must match)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Maximum number of results
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Search archived contracts instead of live ones
+        #[arg(long)]
+        archived: bool,
     },
 
     /// Show details of a specific contract
@@ -62,9 +144,9 @@ pub enum Commands {
         /// Contract ID
         id: String,
 
-        /// Owner name (agent or user)
-        #[arg(long, default_value = "stead-cli")]
-        owner: String,
+        /// Owner name (agent or user; defaults to executor.owner in config)
+        #[arg(long)]
+        owner: Option<String>,
     },
 
     /// Cancel a contract
@@ -73,18 +155,156 @@ pub enum Commands {
         id: String,
     },
 
-    /// Browse AI CLI sessions (Claude Code, Codex CLI, OpenCode)
+    /// Approve a contract that is awaiting human sign-off
+    Approve {
+        /// Contract ID
+        id: String,
+
+        /// Who is approving (defaults to the current user)
+        #[arg(long)]
+        approver: Option<String>,
+    },
+
+    /// Reject a contract that is awaiting human sign-off
+    Reject {
+        /// Contract ID
+        id: String,
+
+        /// Why the contract was rejected
+        #[arg(long)]
+        reason: String,
+
+        /// Who is rejecting (defaults to the current user)
+        #[arg(long)]
+        approver: Option<String>,
+    },
+
+    /// Write contracts to stdout for moving between machines or attaching to PRs
+    Export {
+        /// Output format: jsonl, json or csv (csv is a summary and can't be re-imported)
+        #[arg(long, default_value = "jsonl")]
+        format: ExportFormat,
+
+        /// Only export contracts with this status
+        #[arg(long)]
+        status: Option<String>,
+    },
+
+    /// Import contracts from a JSONL or JSON export (or a contracts.jsonl)
+    Import {
+        /// File to import
+        file: PathBuf,
+
+        /// When an ID already exists: skip, overwrite or rename
+        #[arg(long, default_value = "skip")]
+        on_conflict: OnConflict,
+    },
+
+    /// Move old finished contracts to .stead/archive.jsonl
+    Archive {
+        /// Minimum time since finishing, e.g. 30d, 12h, 2w [default: retention config]
+        #[arg(long)]
+        older_than: Option<String>,
+
+        /// Statuses to archive, comma-separated [default: retention config]
+        #[arg(long = "status", value_delimiter = ',')]
+        statuses: Vec<String>,
+
+        /// Show what would be archived without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Stream contract changes until interrupted (NDJSON with --json)
+    Watch {
+        /// Milliseconds between checks for changes
+        #[arg(long, default_value = "500")]
+        interval_ms: u64,
+    },
+
+    /// What needs you: failures, pending approvals, agent questions, stale claims
+    Attention {
+        /// Skip scanning AI CLI sessions for open questions
+        #[arg(long)]
+        no_sessions: bool,
+    },
+
+    /// Interactive terminal dashboard: contracts by attention, updated live
+    Dashboard {
+        /// Milliseconds between checks for changes
+        #[arg(long, default_value = "500")]
+        interval_ms: u64,
+    },
+
+    /// Apply retention rules, remove leftover temp files and compact storage
+    Gc {
+        /// Show what would be done without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Database maintenance
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+
+    /// Browse AI CLI sessions (Claude Code, Codex CLI, OpenCode, Gemini CLI, Aider)
     Session {
         #[command(subcommand)]
         command: SessionCommands,
     },
+
+    /// Token and cost totals across AI CLI sessions
+    Usage {
+        /// Group by day, project, cli or model
+        #[arg(long, default_value = "project")]
+        by: UsageGroup,
+
+        /// Only sessions active since an age (e.g. 12h, 7d, 2w) or a date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Filter by CLI: claude, codex, opencode, gemini, aider, or an external adapter's name
+        #[arg(long)]
+        cli: Option<String>,
+
+        /// Filter by project path (substring match)
+        #[arg(long)]
+        project: Option<String>,
+
+        /// Output format: table, json or csv
+        #[arg(long, default_value = "table")]
+        format: UsageFormat,
+
+        /// Re-parse every session file instead of trusting the session index
+        #[arg(long)]
+        refresh: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DbCommands {
+    /// Apply pending schema migrations
+    Migrate {
+        /// List pending migrations without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Scan contracts for corrupt fields
+    Check {
+        /// Reset fields that can be repaired without inventing state
+        #[arg(long)]
+        repair: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum SessionCommands {
     /// List sessions from all installed AI CLIs
     List {
-        /// Filter by CLI: claude, codex, opencode
+        /// Filter by CLI: claude, codex, opencode, gemini, aider, or an external adapter's name
         #[arg(long)]
         cli: Option<String>,
 
@@ -95,6 +315,10 @@ pub enum SessionCommands {
         /// Maximum number of sessions to show
         #[arg(long, default_value = "20")]
         limit: usize,
+
+        /// Re-parse every session file instead of trusting the session index
+        #[arg(long)]
+        refresh: bool,
     },
 
     /// Show details of a specific session
@@ -105,6 +329,38 @@ pub enum SessionCommands {
         /// Show full timeline (default: summary only)
         #[arg(long)]
         full: bool,
+
+        /// Show the full timeline including the agent's reasoning
+        #[arg(long)]
+        thinking: bool,
+    },
+
+    /// Stream new timeline entries of a Claude Code or Codex session as
+    /// they're written (NDJSON with --json)
+    Follow {
+        /// Session ID (e.g., claude-abc123, codex-def456)
+        #[arg(required_unless_present = "latest")]
+        id: Option<String>,
+
+        /// Follow the most recently active session instead
+        #[arg(long, conflicts_with = "id")]
+        latest: bool,
+
+        /// With --latest, only consider sessions whose project path contains this
+        #[arg(long, conflicts_with = "id")]
+        project: Option<String>,
+
+        /// Print the existing timeline before new entries
+        #[arg(long)]
+        from_start: bool,
+
+        /// Include the agent's reasoning
+        #[arg(long)]
+        thinking: bool,
+
+        /// Milliseconds between checks for new entries
+        #[arg(long, default_value = "500")]
+        interval_ms: u64,
     },
 }
 
@@ -122,19 +378,102 @@ mod tests {
     fn test_run_command_parsing() {
         let cli = Cli::parse_from(["stead", "run", "fix the bug", "--verify", "cargo test"]);
         match cli.command {
-            Commands::Run { task, verify } => {
+            Commands::Run {
+                task,
+                verify,
+                requires_approval,
+            } => {
                 assert_eq!(task, "fix the bug");
                 assert_eq!(verify, "cargo test");
+                assert!(!requires_approval);
             }
             _ => panic!("Expected Run command"),
         }
     }
 
+    #[test]
+    fn test_create_with_parent() {
+        let cli = Cli::parse_from([
+            "stead",
+            "create",
+            "sub task",
+            "--verify",
+            "cargo test",
+            "--parent",
+            "abc123",
+        ]);
+        match cli.command {
+            Commands::Create {
+                parent,
+                await_children,
+                ..
+            } => {
+                assert_eq!(parent, Some("abc123".to_string()));
+                assert!(!await_children);
+            }
+            _ => panic!("Expected Create command"),
+        }
+    }
+
+    #[test]
+    fn test_create_await_children() {
+        let cli = Cli::parse_from([
+            "stead",
+            "create",
+            "big task",
+            "--verify",
+            "cargo test",
+            "--await-children",
+        ]);
+        match cli.command {
+            Commands::Create {
+                parent,
+                await_children,
+                ..
+            } => {
+                assert_eq!(parent, None);
+                assert!(await_children);
+            }
+            _ => panic!("Expected Create command"),
+        }
+    }
+
+    #[test]
+    fn test_approve_command() {
+        let cli = Cli::parse_from(["stead", "approve", "abc123", "--approver", "alice"]);
+        match cli.command {
+            Commands::Approve { id, approver } => {
+                assert_eq!(id, "abc123");
+                assert_eq!(approver, Some("alice".to_string()));
+            }
+            _ => panic!("Expected Approve command"),
+        }
+    }
+
+    #[test]
+    fn test_reject_requires_reason() {
+        assert!(Cli::try_parse_from(["stead", "reject", "abc123"]).is_err());
+
+        let cli = Cli::parse_from(["stead", "reject", "abc123", "--reason", "unsafe"]);
+        match cli.command {
+            Commands::Reject {
+                id,
+                reason,
+                approver,
+            } => {
+                assert_eq!(id, "abc123");
+                assert_eq!(reason, "unsafe");
+                assert_eq!(approver, None);
+            }
+            _ => panic!("Expected Reject command"),
+        }
+    }
+
     #[test]
     fn test_list_with_status() {
         let cli = Cli::parse_from(["stead", "list", "--status", "passed"]);
         match cli.command {
-            Commands::List { status } => {
+            Commands::List { status, .. } => {
                 assert_eq!(status, Some("passed".to_string()));
             }
             _ => panic!("Expected List command"),
@@ -145,13 +484,208 @@ mod tests {
     fn test_list_without_status() {
         let cli = Cli::parse_from(["stead", "list"]);
         match cli.command {
-            Commands::List { status } => {
+            Commands::List {
+                status,
+                all_projects,
+                ..
+            } => {
                 assert_eq!(status, None);
+                assert!(!all_projects);
             }
             _ => panic!("Expected List command"),
         }
     }
 
+    #[test]
+    fn test_db_migrate_dry_run() {
+        let cli = Cli::parse_from(["stead", "db", "migrate", "--dry-run"]);
+        match cli.command {
+            Commands::Db {
+                command: DbCommands::Migrate { dry_run },
+            } => assert!(dry_run),
+            _ => panic!("Expected Db Migrate command"),
+        }
+    }
+
+    #[test]
+    fn test_db_check_repair() {
+        let cli = Cli::parse_from(["stead", "db", "check", "--repair"]);
+        match cli.command {
+            Commands::Db {
+                command: DbCommands::Check { repair },
+            } => assert!(repair),
+            _ => panic!("Expected Db Check command"),
+        }
+    }
+
+    #[test]
+    fn test_init_command() {
+        let cli = Cli::parse_from(["stead", "init"]);
+        assert!(matches!(cli.command, Commands::Init { register: false }));
+
+        let cli = Cli::parse_from(["stead", "init", "--register"]);
+        assert!(matches!(cli.command, Commands::Init { register: true }));
+    }
+
+    #[test]
+    fn test_project_flag() {
+        let cli = Cli::parse_from(["stead", "--project", "/tmp/app", "list"]);
+        assert_eq!(cli.project, Some(PathBuf::from("/tmp/app")));
+
+        let cli = Cli::parse_from(["stead", "list"]);
+        assert_eq!(cli.project, None);
+    }
+
+    #[test]
+    fn test_storage_flag() {
+        let cli = Cli::parse_from(["stead", "--storage", "jsonl", "list"]);
+        assert_eq!(cli.storage, Some(StorageBackend::Jsonl));
+
+        let cli = Cli::parse_from(["stead", "list"]);
+        assert_eq!(cli.storage, None);
+
+        assert!(Cli::try_parse_from(["stead", "--storage", "csv", "list"]).is_err());
+    }
+
+    #[test]
+    fn test_export_command() {
+        let cli = Cli::parse_from(["stead", "export"]);
+        match cli.command {
+            Commands::Export { format, status } => {
+                assert_eq!(format, ExportFormat::Jsonl);
+                assert_eq!(status, None);
+            }
+            _ => panic!("Expected Export command"),
+        }
+
+        let cli = Cli::parse_from(["stead", "export", "--format", "csv", "--status", "failed"]);
+        match cli.command {
+            Commands::Export { format, status } => {
+                assert_eq!(format, ExportFormat::Csv);
+                assert_eq!(status, Some("failed".to_string()));
+            }
+            _ => panic!("Expected Export command"),
+        }
+    }
+
+    #[test]
+    fn test_import_command() {
+        let cli = Cli::parse_from(["stead", "import", "c.jsonl", "--on-conflict", "rename"]);
+        match cli.command {
+            Commands::Import { file, on_conflict } => {
+                assert_eq!(file, PathBuf::from("c.jsonl"));
+                assert_eq!(on_conflict, OnConflict::Rename);
+            }
+            _ => panic!("Expected Import command"),
+        }
+        assert!(
+            Cli::try_parse_from(["stead", "import", "c.jsonl", "--on-conflict", "merge"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_search_command() {
+        let cli = Cli::parse_from([
+            "stead",
+            "search",
+            "rate limiting",
+            "--status",
+            "failed",
+            "--tag",
+            "api",
+        ]);
+        match cli.command {
+            Commands::Search {
+                query,
+                status,
+                tags,
+                limit,
+                archived,
+            } => {
+                assert_eq!(query, "rate limiting");
+                assert_eq!(status, Some("failed".to_string()));
+                assert_eq!(tags, vec!["api"]);
+                assert_eq!(limit, 20);
+                assert!(!archived);
+            }
+            _ => panic!("Expected Search command"),
+        }
+    }
+
+    #[test]
+    fn test_archive_watch_dashboard_and_gc_commands() {
+        let cli = Cli::parse_from([
+            "stead",
+            "archive",
+            "--older-than",
+            "30d",
+            "--status",
+            "completed,cancelled",
+        ]);
+        match cli.command {
+            Commands::Archive {
+                older_than,
+                statuses,
+                dry_run,
+            } => {
+                assert_eq!(older_than, Some("30d".to_string()));
+                assert_eq!(statuses, vec!["completed", "cancelled"]);
+                assert!(!dry_run);
+            }
+            _ => panic!("Expected Archive command"),
+        }
+
+        let cli = Cli::parse_from(["stead", "watch", "--json", "--interval-ms", "100"]);
+        assert!(cli.json);
+        assert!(matches!(cli.command, Commands::Watch { interval_ms: 100 }));
+
+        let cli = Cli::parse_from(["stead", "attention", "--no-sessions"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Attention { no_sessions: true }
+        ));
+
+        let cli = Cli::parse_from(["stead", "dashboard"]);
+        assert!(matches!(
+            cli.command,
+            Commands::Dashboard { interval_ms: 500 }
+        ));
+
+        let cli = Cli::parse_from(["stead", "gc", "--dry-run"]);
+        assert!(matches!(cli.command, Commands::Gc { dry_run: true }));
+    }
+
+    #[test]
+    fn test_list_filters() {
+        let cli = Cli::parse_from([
+            "stead", "list", "--owner", "bot", "--tag", "api", "--limit", "10", "--offset", "20",
+        ]);
+        match cli.command {
+            Commands::List {
+                owner,
+                tags,
+                limit,
+                offset,
+                ..
+            } => {
+                assert_eq!(owner, Some("bot".to_string()));
+                assert_eq!(tags, vec!["api"]);
+                assert_eq!(limit, Some(10));
+                assert_eq!(offset, 20);
+            }
+            _ => panic!("Expected List command"),
+        }
+    }
+
+    #[test]
+    fn test_list_all_projects() {
+        let cli = Cli::parse_from(["stead", "list", "--all-projects"]);
+        match cli.command {
+            Commands::List { all_projects, .. } => assert!(all_projects),
+            _ => panic!("Expected List command"),
+        }
+    }
+
     #[test]
     fn test_show_command() {
         let cli = Cli::parse_from(["stead", "show", "abc123"]);
@@ -189,10 +723,12 @@ mod tests {
                     cli,
                     project,
                     limit,
+                    refresh,
                 } => {
                     assert_eq!(cli, None);
                     assert_eq!(project, None);
                     assert_eq!(limit, 20);
+                    assert!(!refresh);
                 }
                 _ => panic!("Expected List subcommand"),
             },
@@ -212,6 +748,7 @@ mod tests {
             "stead",
             "--limit",
             "10",
+            "--refresh",
         ]);
         match cli.command {
             Commands::Session { command } => match command {
@@ -219,10 +756,12 @@ mod tests {
                     cli,
                     project,
                     limit,
+                    refresh,
                 } => {
                     assert_eq!(cli, Some("claude".to_string()));
                     assert_eq!(project, Some("stead".to_string()));
                     assert_eq!(limit, 10);
+                    assert!(refresh);
                 }
                 _ => panic!("Expected List subcommand"),
             },
@@ -235,9 +774,10 @@ mod tests {
         let cli = Cli::parse_from(["stead", "session", "show", "claude-abc123"]);
         match cli.command {
             Commands::Session { command } => match command {
-                SessionCommands::Show { id, full } => {
+                SessionCommands::Show { id, full, thinking } => {
                     assert_eq!(id, "claude-abc123");
                     assert!(!full);
+                    assert!(!thinking);
                 }
                 _ => panic!("Expected Show subcommand"),
             },
@@ -247,16 +787,123 @@ mod tests {
 
     #[test]
     fn test_session_show_full() {
-        let cli = Cli::parse_from(["stead", "session", "show", "--full", "codex-def456"]);
+        let cli = Cli::parse_from([
+            "stead",
+            "session",
+            "show",
+            "--full",
+            "--thinking",
+            "codex-def456",
+        ]);
         match cli.command {
             Commands::Session { command } => match command {
-                SessionCommands::Show { id, full } => {
+                SessionCommands::Show { id, full, thinking } => {
                     assert_eq!(id, "codex-def456");
                     assert!(full);
+                    assert!(thinking);
                 }
                 _ => panic!("Expected Show subcommand"),
             },
             _ => panic!("Expected Session command"),
         }
     }
+
+    #[test]
+    fn test_session_follow_command() {
+        let cli = Cli::parse_from(["stead", "session", "follow", "claude-abc123"]);
+        match cli.command {
+            Commands::Session {
+                command:
+                    SessionCommands::Follow {
+                        id,
+                        latest,
+                        interval_ms,
+                        ..
+                    },
+            } => {
+                assert_eq!(id.as_deref(), Some("claude-abc123"));
+                assert!(!latest);
+                assert_eq!(interval_ms, 500);
+            }
+            _ => panic!("Expected Session Follow command"),
+        }
+
+        let cli = Cli::parse_from([
+            "stead",
+            "--json",
+            "session",
+            "follow",
+            "--latest",
+            "--project",
+            "stead",
+        ]);
+        assert!(cli.json);
+        match cli.command {
+            Commands::Session {
+                command:
+                    SessionCommands::Follow {
+                        id,
+                        latest,
+                        project,
+                        ..
+                    },
+            } => {
+                assert!(id.is_none() && latest);
+                assert_eq!(project.as_deref(), Some("stead"));
+            }
+            _ => panic!("Expected Session Follow command"),
+        }
+
+        // Needs exactly one of an ID or --latest; --project only with --latest
+        assert!(Cli::try_parse_from(["stead", "session", "follow"]).is_err());
+        assert!(Cli::try_parse_from(["stead", "session", "follow", "x", "--latest"]).is_err());
+        assert!(
+            Cli::try_parse_from(["stead", "session", "follow", "x", "--project", "p"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_usage_command() {
+        let cli = Cli::parse_from(["stead", "usage"]);
+        match cli.command {
+            Commands::Usage {
+                by, since, format, ..
+            } => {
+                assert_eq!(by, UsageGroup::Project);
+                assert!(since.is_none());
+                assert_eq!(format, UsageFormat::Table);
+            }
+            _ => panic!("Expected Usage command"),
+        }
+
+        let cli = Cli::parse_from([
+            "stead",
+            "usage",
+            "--by",
+            "day",
+            "--since",
+            "7d",
+            "--project",
+            "app",
+            "--format",
+            "csv",
+        ]);
+        match cli.command {
+            Commands::Usage {
+                by,
+                since,
+                project,
+                format,
+                ..
+            } => {
+                assert_eq!(by, UsageGroup::Day);
+                assert_eq!(since.as_deref(), Some("7d"));
+                assert_eq!(project.as_deref(), Some("app"));
+                assert_eq!(format, UsageFormat::Csv);
+            }
+            _ => panic!("Expected Usage command"),
+        }
+
+        assert!(Cli::try_parse_from(["stead", "usage", "--by", "week"]).is_err());
+    }
 }
@@ -0,0 +1,163 @@
+//! Approve command - sign off on a contract awaiting approval
+
+use super::process::{self, HookEvent};
+use crate::config::Config;
+use crate::schema::{Contract, TransitionError};
+use crate::storage::{Storage, StorageError};
+use anyhow::{bail, Result};
+use std::path::Path;
+use thiserror::Error;
+
+/// Why a sign-off (approval or rejection) couldn't be recorded
+#[derive(Error, Debug)]
+pub enum SignOffError {
+    #[error("Contract not found: {0}")]
+    NotFound(String),
+
+    #[error("A rejection reason is required")]
+    MissingReason,
+
+    #[error("{0}")]
+    Transition(#[from] TransitionError),
+
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
+
+/// Execute the approve command
+pub fn execute(id: &str, approver: Option<&str>, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(id, approver, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(
+    id: &str,
+    approver: Option<&str>,
+    json_output: bool,
+    cwd: &Path,
+) -> Result<()> {
+    let (config, db) = super::open_project(cwd, None)?;
+    execute_with_storage(id, approver, json_output, &config, &*db)
+}
+
+/// Execute with a specific storage backend
+pub fn execute_with_storage(
+    id: &str,
+    approver: Option<&str>,
+    json_output: bool,
+    config: &Config,
+    storage: &dyn Storage,
+) -> Result<()> {
+    let approver = resolve_approver(approver)?;
+    let contract = approve(storage, config, id, &approver)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string(&contract)?);
+    } else {
+        println!("Contract {} approved by {}", contract.id, approver);
+    }
+
+    Ok(())
+}
+
+/// Approve a contract, persist the decision and run the `on_complete`
+/// hook. The CLI and the FFI both sign off through here.
+pub fn approve(
+    storage: &dyn Storage,
+    config: &Config,
+    id: &str,
+    approver: &str,
+) -> Result<Contract, SignOffError> {
+    let mut contract = storage
+        .load_contract(id)?
+        .ok_or_else(|| SignOffError::NotFound(id.to_string()))?;
+
+    contract.approve(approver)?;
+    storage.update_contract(&contract)?;
+    process::run_hook(HookEvent::Complete, &contract, config);
+
+    Ok(contract)
+}
+
+/// Use the explicit approver, falling back to the current OS user
+pub(crate) fn resolve_approver(approver: Option<&str>) -> Result<String> {
+    if let Some(name) = approver {
+        return Ok(name.to_string());
+    }
+    match std::env::var("USER").or_else(|_| std::env::var("USERNAME")) {
+        Ok(name) if !name.is_empty() => Ok(name),
+        _ => bail!("Cannot determine approver; pass --approver <name>"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::ContractStatus;
+    use crate::storage::sqlite::SqliteStorage;
+
+    fn test_db() -> SqliteStorage {
+        SqliteStorage::open_in_memory().unwrap()
+    }
+
+    #[test]
+    fn test_approve_awaiting_contract() {
+        let db = test_db();
+        let mut contract = Contract::new("release", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(true, None);
+        db.save_contract(&contract).unwrap();
+
+        execute_with_storage(&contract.id, Some("alice"), false, &Config::default(), &db).unwrap();
+
+        let loaded = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(loaded.status, ContractStatus::Completed);
+        assert_eq!(loaded.approval.unwrap().approver, "alice");
+    }
+
+    #[test]
+    fn test_approve_not_awaiting_fails() {
+        let db = test_db();
+        let contract = Contract::new("task", "verify");
+        db.save_contract(&contract).unwrap();
+
+        let result =
+            execute_with_storage(&contract.id, Some("alice"), false, &Config::default(), &db);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_approve_not_found() {
+        let db = test_db();
+        let result =
+            execute_with_storage("nonexistent", Some("alice"), false, &Config::default(), &db);
+        assert!(result.is_err());
+        assert!(matches!(
+            approve(&db, &Config::default(), "nonexistent", "alice"),
+            Err(SignOffError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_approve_runs_complete_hook() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let out = tmp.path().join("hook.txt");
+        let mut config = Config::default();
+        config.hooks.on_complete = Some(format!(
+            "echo \"$STEAD_CONTRACT_STATUS\" > {}",
+            out.display()
+        ));
+
+        let db = test_db();
+        let mut contract = Contract::new("release", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(true, None);
+        db.save_contract(&contract).unwrap();
+
+        approve(&db, &config, &contract.id, "alice").unwrap();
+
+        let written = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(written.trim(), "completed");
+    }
+}
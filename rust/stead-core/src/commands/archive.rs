@@ -0,0 +1,239 @@
+//! Archive command - move old finished contracts out of live storage
+//!
+//! Archived contracts go to `.stead/archive.jsonl` (see `storage::archive`)
+//! and stop showing up in `list`, `show` and the control room.
+
+use crate::config::Config;
+use crate::schema::{Contract, ContractStatus};
+use crate::storage::archive::{finished_at, Archive};
+use crate::storage::{ContractQuery, Storage};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::path::Path;
+
+/// Filters for the archive command; unset fields come from `[retention]`
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveOptions {
+    /// Minimum age since finishing, e.g. `30d`
+    pub older_than: Option<String>,
+    pub statuses: Vec<String>,
+    /// Report what would be archived without changing anything
+    pub dry_run: bool,
+}
+
+/// Execute the archive command
+pub fn execute(options: &ArchiveOptions, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(options, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(options: &ArchiveOptions, json_output: bool, cwd: &Path) -> Result<()> {
+    let (config, db) = super::open_project(cwd, None)?;
+    execute_with_storage(options, json_output, &config, &*db, &Archive::new(cwd))
+}
+
+/// Execute with a specific storage backend and archive
+pub fn execute_with_storage(
+    options: &ArchiveOptions,
+    json_output: bool,
+    config: &Config,
+    storage: &dyn Storage,
+    archive: &Archive,
+) -> Result<()> {
+    let age = match &options.older_than {
+        Some(spec) => parse_age(spec)?,
+        None => Duration::days(config.retention.archive_after_days as i64),
+    };
+    let statuses = if options.statuses.is_empty() {
+        config.retention.archive_statuses.clone()
+    } else {
+        options
+            .statuses
+            .iter()
+            .map(|s| super::list::parse_status(s))
+            .collect::<Result<_>>()?
+    };
+
+    let archived = archive_contracts(
+        storage,
+        archive,
+        &statuses,
+        Utc::now() - age,
+        options.dry_run,
+    )?;
+
+    if json_output {
+        println!("{}", serde_json::to_string(&archived)?);
+        return Ok(());
+    }
+
+    let verb = if options.dry_run {
+        "Would archive"
+    } else {
+        "Archived"
+    };
+    println!("{} {} contract(s)", verb, archived.len());
+    for c in &archived {
+        println!(
+            "  {:15} {:w$} {}",
+            c.id,
+            c.status,
+            super::list::truncate(&c.task, 40),
+            w = ContractStatus::MAX_NAME_LEN
+        );
+    }
+
+    Ok(())
+}
+
+/// Move contracts with one of `statuses` that finished before `cutoff` into
+/// the archive; returns them.
+///
+/// The archive is appended (and synced) before the contracts are deleted in
+/// one transaction, so an interruption can leave a contract in both places
+/// but never in neither.
+pub fn archive_contracts(
+    storage: &dyn Storage,
+    archive: &Archive,
+    statuses: &[ContractStatus],
+    cutoff: DateTime<Utc>,
+    dry_run: bool,
+) -> Result<Vec<Contract>> {
+    if statuses.is_empty() {
+        bail!("No statuses to archive");
+    }
+    if let Some(s) = statuses.iter().find(|s| !is_archivable(**s)) {
+        bail!(
+            "Cannot archive {} contracts; only finished ones (completed, failed, rolledback, cancelled)",
+            s
+        );
+    }
+
+    // Nothing can finish before it was created, so created_before narrows
+    // the scan; the finish time decides
+    let candidates = storage.query(
+        &ContractQuery::new()
+            .statuses(statuses.iter().copied())
+            .created_before(cutoff),
+    )?;
+    let selected: Vec<Contract> = candidates
+        .into_iter()
+        .filter(|c| finished_at(c) < cutoff)
+        .collect();
+
+    if dry_run || selected.is_empty() {
+        return Ok(selected);
+    }
+
+    archive.append(&selected)?;
+    storage.transaction(&mut |tx| {
+        for c in &selected {
+            tx.delete_contract(&c.id)?;
+        }
+        Ok(())
+    })?;
+
+    Ok(selected)
+}
+
+/// Statuses a contract no longer leaves on its own
+fn is_archivable(status: ContractStatus) -> bool {
+    status.is_terminal() || status == ContractStatus::Failed
+}
+
+/// Parse an age such as `90m`, `12h`, `30d` or `2w`
+pub(crate) fn parse_age(spec: &str) -> Result<Duration> {
+    let spec = spec.trim();
+    let invalid = || anyhow::anyhow!("Invalid age '{}'. Expected e.g. 90m, 12h, 30d, 2w", spec);
+    let unit = spec.chars().last().ok_or_else(invalid)?;
+    let n = spec[..spec.len() - unit.len_utf8()]
+        .parse::<u32>()
+        .map(i64::from)
+        .map_err(|_| invalid())?;
+    match unit {
+        'm' => Ok(Duration::minutes(n)),
+        'h' => Ok(Duration::hours(n)),
+        'd' => Ok(Duration::days(n)),
+        'w' => Ok(Duration::weeks(n)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::archive::finished;
+    use crate::storage::sqlite::SqliteStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_age() {
+        assert_eq!(parse_age("30d").unwrap(), Duration::days(30));
+        assert_eq!(parse_age("2w").unwrap(), Duration::weeks(2));
+        assert_eq!(parse_age("90m").unwrap(), Duration::minutes(90));
+        assert!(parse_age("30").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("-5d").is_err());
+        assert!(parse_age("").is_err());
+    }
+
+    #[test]
+    fn test_archive_moves_old_finished_contracts() {
+        let tmp = TempDir::new().unwrap();
+        let db = SqliteStorage::open_in_memory().unwrap();
+        let archive = Archive::new(tmp.path());
+
+        let old = finished("old", 40);
+        let recent = finished("recent", 5);
+        let mut active = Contract::new("active", "true");
+        active.created_at = Utc::now() - Duration::days(60);
+        for c in [&old, &recent, &active] {
+            db.save_contract(c).unwrap();
+        }
+
+        let cutoff = Utc::now() - Duration::days(30);
+        let statuses = [ContractStatus::Completed];
+
+        let planned = archive_contracts(&db, &archive, &statuses, cutoff, true).unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(db.load_all_contracts().unwrap().len(), 3);
+        assert!(archive.load_all().unwrap().is_empty());
+
+        let moved = archive_contracts(&db, &archive, &statuses, cutoff, false).unwrap();
+        assert_eq!(moved[0].id, old.id);
+        assert!(db.load_contract(&old.id).unwrap().is_none());
+        assert_eq!(db.load_all_contracts().unwrap().len(), 2);
+        assert_eq!(archive.load_all().unwrap()[0].id, old.id);
+    }
+
+    #[test]
+    fn test_archive_rejects_active_statuses() {
+        let tmp = TempDir::new().unwrap();
+        let db = SqliteStorage::open_in_memory().unwrap();
+        let result = archive_contracts(
+            &db,
+            &Archive::new(tmp.path()),
+            &[ContractStatus::Ready],
+            Utc::now(),
+            false,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_execute_uses_retention_config() {
+        let tmp = TempDir::new().unwrap();
+        let db = SqliteStorage::open_in_memory().unwrap();
+        db.save_contract(&finished("old", 10)).unwrap();
+
+        let mut config = Config::default();
+        let archive = Archive::new(tmp.path());
+        execute_with_storage(&ArchiveOptions::default(), true, &config, &db, &archive).unwrap();
+        assert_eq!(db.load_all_contracts().unwrap().len(), 1);
+
+        config.retention.archive_after_days = 7;
+        execute_with_storage(&ArchiveOptions::default(), true, &config, &db, &archive).unwrap();
+        assert!(db.load_all_contracts().unwrap().is_empty());
+    }
+}
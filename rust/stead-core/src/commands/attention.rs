@@ -0,0 +1,125 @@
+//! Attention command - what needs a human right now
+//!
+//! Prints the ranked list from `crate::attention::assess` and the
+//! aggregate state. `--json` emits the same structure the FFI exposes to
+//! the macOS app.
+
+use crate::attention::{self, Attention};
+use crate::config::Config;
+use crate::storage::Storage;
+use crate::usf::UniversalSession;
+use anyhow::Result;
+use chrono::Utc;
+use std::path::Path;
+
+/// Execute the attention command
+pub fn execute(include_sessions: bool, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(include_sessions, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(include_sessions: bool, json_output: bool, cwd: &Path) -> Result<()> {
+    let (config, db) = super::open_project(cwd, None)?;
+    let sessions = if include_sessions {
+        attention::recent_sessions(cwd, &config.attention)
+    } else {
+        Vec::new()
+    };
+    execute_with_storage(json_output, &config, &*db, &sessions)
+}
+
+/// Execute with a specific storage backend and already-loaded sessions
+pub fn execute_with_storage(
+    json_output: bool,
+    config: &Config,
+    storage: &dyn Storage,
+    sessions: &[UniversalSession],
+) -> Result<()> {
+    let contracts = storage.load_all_contracts()?;
+    let report = attention::assess(&contracts, sessions, &config.attention, Utc::now());
+
+    if json_output {
+        println!("{}", serde_json::to_string(&report)?);
+    } else {
+        print!("{}", format_report(&report));
+    }
+    Ok(())
+}
+
+fn format_report(report: &Attention) -> String {
+    let mut out = format!(
+        "State: {} ({} active contract(s))\n",
+        report.state, report.active
+    );
+    if report.items.is_empty() {
+        out.push_str("Nothing needs you.\n");
+        return out;
+    }
+
+    out.push_str(&format!("\nNeeds you ({}):\n", report.items.len()));
+    for item in &report.items {
+        let id = item
+            .contract_id
+            .as_deref()
+            .or(item.session_id.as_deref())
+            .unwrap_or("-");
+        out.push_str(&format!(
+            "  {:22} {:15} {}  ({})\n",
+            item.reason.to_string(),
+            id,
+            super::list::truncate(&item.title, 40),
+            super::session::format_relative_time(item.since)
+        ));
+        if let Some(detail) = &item.detail {
+            out.push_str(&format!(
+                "  {:22} {}\n",
+                "",
+                super::list::truncate(detail, 70)
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attention::AttentionState;
+    use crate::schema::{Contract, ContractStatus};
+    use crate::storage::sqlite::SqliteStorage;
+
+    #[test]
+    fn test_execute_with_storage() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        let mut c = Contract::new("task", "false");
+        c.status = ContractStatus::Failed;
+        storage.save_contract(&c).unwrap();
+
+        execute_with_storage(true, &Config::default(), &storage, &[]).unwrap();
+        execute_with_storage(false, &Config::default(), &storage, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_format_report() {
+        let mut c = Contract::new("Fix the login flow", "cargo test");
+        c.status = ContractStatus::Failed;
+        c.output = Some("1 test failed".to_string());
+        let report = attention::assess(
+            std::slice::from_ref(&c),
+            &[],
+            &Default::default(),
+            Utc::now(),
+        );
+        assert_eq!(report.state, AttentionState::Failing);
+
+        let text = format_report(&report);
+        assert!(text.starts_with("State: failing"));
+        assert!(text.contains("failed verification"));
+        assert!(text.contains(&c.id));
+        assert!(text.contains("1 test failed"));
+
+        let idle = attention::assess(&[], &[], &Default::default(), Utc::now());
+        assert!(format_report(&idle).contains("Nothing needs you."));
+    }
+}
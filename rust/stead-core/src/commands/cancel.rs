@@ -1,24 +1,25 @@
 //! Cancel command - cancel a contract
 
-use crate::storage::{self, Storage};
+use crate::schema::Contract;
+use crate::storage::Storage;
 use anyhow::{bail, Result};
+use std::path::Path;
 
 /// Execute the cancel command
 pub fn execute(id: &str, json_output: bool) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-    let db = storage::sqlite::open_default(&cwd)?;
-    execute_with_storage(id, json_output, &db)
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(id, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(id: &str, json_output: bool, cwd: &Path) -> Result<()> {
+    let (_, db) = super::open_project(cwd, None)?;
+    execute_with_storage(id, json_output, &*db)
 }
 
 /// Execute with a specific storage backend
 pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage) -> Result<()> {
-    let mut contract = match storage.load_contract(id)? {
-        Some(c) => c,
-        None => bail!("Contract not found: {}", id),
-    };
-
-    contract.cancel().map_err(|e| anyhow::anyhow!("{}", e))?;
-    storage.update_contract(&contract)?;
+    let contract = cancel(id, storage)?;
 
     if json_output {
         println!("{}", serde_json::to_string(&contract)?);
@@ -29,6 +30,18 @@ pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage)
     Ok(())
 }
 
+/// Cancel a contract and store it
+pub fn cancel(id: &str, storage: &dyn Storage) -> Result<Contract> {
+    let mut contract = match storage.load_contract(id)? {
+        Some(c) => c,
+        None => bail!("Contract not found: {}", id),
+    };
+
+    contract.cancel().map_err(|e| anyhow::anyhow!("{}", e))?;
+    storage.update_contract(&contract)?;
+    Ok(contract)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,7 +68,7 @@ mod tests {
     fn test_cancel_completed_fails() {
         let db = test_db();
         let mut contract = Contract::new("task", "verify");
-        contract.complete(true, None);
+        contract.finish_verification(true, None);
         db.save_contract(&contract).unwrap();
 
         let result = execute_with_storage(&contract.id, false, &db);
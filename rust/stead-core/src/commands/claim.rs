@@ -1,13 +1,28 @@
 //! Claim command - claim a contract for execution
 
-use crate::storage::{self, Storage};
+use crate::schema::Contract;
+use crate::storage::Storage;
 use anyhow::{bail, Result};
+use std::path::Path;
 
 /// Execute the claim command
-pub fn execute(id: &str, owner: &str, json_output: bool) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-    let db = storage::sqlite::open_default(&cwd)?;
-    execute_with_storage(id, owner, json_output, &db)
+///
+/// Without an explicit owner, the project's `executor.owner` is used.
+pub fn execute(id: &str, owner: Option<&str>, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(id, owner, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(
+    id: &str,
+    owner: Option<&str>,
+    json_output: bool,
+    cwd: &Path,
+) -> Result<()> {
+    let (config, db) = super::open_project(cwd, None)?;
+    let owner = owner.unwrap_or(&config.executor.owner);
+    execute_with_storage(id, owner, json_output, &*db)
 }
 
 /// Execute with a specific storage backend
@@ -17,6 +32,19 @@ pub fn execute_with_storage(
     json_output: bool,
     storage: &dyn Storage,
 ) -> Result<()> {
+    let contract = claim(id, owner, storage)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string(&contract)?);
+    } else {
+        println!("Contract {} claimed by {}", contract.id, owner);
+    }
+
+    Ok(())
+}
+
+/// Claim a contract for `owner` and store it (Pending moves through Ready)
+pub fn claim(id: &str, owner: &str, storage: &dyn Storage) -> Result<Contract> {
     let mut contract = match storage.load_contract(id)? {
         Some(c) => c,
         None => bail!("Contract not found: {}", id),
@@ -34,14 +62,7 @@ pub fn execute_with_storage(
         .claim(owner)
         .map_err(|e| anyhow::anyhow!("{}", e))?;
     storage.update_contract(&contract)?;
-
-    if json_output {
-        println!("{}", serde_json::to_string(&contract)?);
-    } else {
-        println!("Contract {} claimed by {}", contract.id, owner);
-    }
-
-    Ok(())
+    Ok(contract)
 }
 
 #[cfg(test)]
@@ -1,28 +1,88 @@
 //! Create command - create a contract without executing it
 
+use super::process::{self, HookEvent};
+use crate::config::Config;
 use crate::schema::Contract;
-use crate::storage::{self, Storage};
-use anyhow::Result;
+use crate::storage::Storage;
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// Optional settings for a new contract
+#[derive(Debug, Clone, Default)]
+pub struct CreateOptions {
+    /// Spawn as a sub-contract of this contract ID
+    pub parent: Option<String>,
+    /// Only allow verification once all children are Completed
+    pub await_children: bool,
+    /// Require human sign-off after verification passes
+    pub requires_approval: bool,
+    /// Tags in addition to the project's configured defaults
+    pub tags: Vec<String>,
+}
 
 /// Execute the create command
-pub fn execute(task: &str, verify_cmd: &str, json_output: bool) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-    let db = storage::sqlite::open_default(&cwd)?;
-    execute_with_storage(task, verify_cmd, json_output, &db)
+pub fn execute(
+    task: &str,
+    verify_cmd: &str,
+    options: &CreateOptions,
+    json_output: bool,
+) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(task, verify_cmd, options, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(
+    task: &str,
+    verify_cmd: &str,
+    options: &CreateOptions,
+    json_output: bool,
+    cwd: &Path,
+) -> Result<()> {
+    let (config, db) = super::open_project(cwd, None)?;
+    execute_with_storage(task, verify_cmd, options, json_output, &config, &*db)
 }
 
 /// Execute with a specific storage backend
 pub fn execute_with_storage(
     task: &str,
     verify_cmd: &str,
+    options: &CreateOptions,
     json_output: bool,
+    config: &Config,
     storage: &dyn Storage,
 ) -> Result<()> {
-    let contract = Contract::new(task, verify_cmd);
+    let mut contract = match &options.parent {
+        Some(parent_id) => {
+            let parent = match storage.load_contract(parent_id)? {
+                Some(p) => p,
+                None => bail!("Parent contract not found: {}", parent_id),
+            };
+            if parent.status.is_terminal() {
+                bail!(
+                    "Cannot add a sub-contract to {} contract {}",
+                    parent.status,
+                    parent.id
+                );
+            }
+            parent.spawn_child(task, verify_cmd)
+        }
+        None => Contract::new(task, verify_cmd),
+    };
+    contract.await_children = options.await_children;
+    contract.requires_approval = options.requires_approval;
+    for tag in config.tags.iter().chain(&options.tags) {
+        if !contract.tags.contains(tag) {
+            contract.tags.push(tag.clone());
+        }
+    }
     storage.save_contract(&contract)?;
+    process::run_hook(HookEvent::Create, &contract, config);
 
     if json_output {
         println!("{}", serde_json::to_string(&contract)?);
+    } else if let Some(ref parent_id) = contract.parent_id {
+        println!("Contract created: {} (child of {})", contract.id, parent_id);
     } else {
         println!("Contract created: {}", contract.id);
     }
@@ -43,7 +103,15 @@ mod tests {
     #[test]
     fn test_create_contract() {
         let db = test_db();
-        execute_with_storage("my task", "echo ok", false, &db).unwrap();
+        execute_with_storage(
+            "my task",
+            "echo ok",
+            &CreateOptions::default(),
+            false,
+            &Config::default(),
+            &db,
+        )
+        .unwrap();
 
         let contracts = db.load_all_contracts().unwrap();
         assert_eq!(contracts.len(), 1);
@@ -51,4 +119,68 @@ mod tests {
         assert_eq!(contracts[0].verification, "echo ok");
         assert_eq!(contracts[0].status, ContractStatus::Pending);
     }
+
+    #[test]
+    fn test_create_merges_config_tags() {
+        let db = test_db();
+        let config = Config {
+            tags: vec!["backend".to_string()],
+            ..Default::default()
+        };
+        let options = CreateOptions {
+            tags: vec!["auth".to_string(), "backend".to_string()],
+            ..Default::default()
+        };
+        execute_with_storage("task", "verify", &options, false, &config, &db).unwrap();
+
+        let contracts = db.load_all_contracts().unwrap();
+        assert_eq!(contracts[0].tags, vec!["backend", "auth"]);
+    }
+
+    #[test]
+    fn test_create_child_contract() {
+        let db = test_db();
+        let parent = Contract::new("parent", "verify");
+        db.save_contract(&parent).unwrap();
+
+        let options = CreateOptions {
+            parent: Some(parent.id.clone()),
+            ..Default::default()
+        };
+        execute_with_storage("child", "verify", &options, false, &Config::default(), &db).unwrap();
+
+        let children = db.load_children(&parent.id).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].task, "child");
+    }
+
+    #[test]
+    fn test_create_child_of_missing_parent() {
+        let db = test_db();
+        let options = CreateOptions {
+            parent: Some("nonexistent".to_string()),
+            ..Default::default()
+        };
+        let result =
+            execute_with_storage("child", "verify", &options, false, &Config::default(), &db);
+        assert!(result.is_err());
+        assert!(db.load_all_contracts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_create_child_of_terminal_parent() {
+        let db = test_db();
+        let mut parent = Contract::new("parent", "verify");
+        parent.cancel().unwrap();
+        db.save_contract(&parent).unwrap();
+
+        let options = CreateOptions {
+            parent: Some(parent.id.clone()),
+            ..Default::default()
+        };
+        assert!(
+            execute_with_storage("child", "verify", &options, false, &Config::default(), &db)
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,215 @@
+//! Database maintenance commands
+//!
+//! `db migrate` only applies to the SQLite backend; JSONL files need no
+//! migration. `db check` covers both.
+
+use crate::config::Config;
+use crate::storage::sqlite::SqliteStorage;
+use crate::storage::{self, migrations, StorageBackend};
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// Execute `db migrate`
+pub fn migrate(dry_run: bool, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    migrate_with_cwd(dry_run, json_output, &cwd)
+}
+
+/// Execute `db migrate` with explicit project root
+pub fn migrate_with_cwd(dry_run: bool, json_output: bool, cwd: &Path) -> Result<()> {
+    ensure_sqlite(cwd)?;
+    let db_exists = SqliteStorage::db_path(cwd).exists();
+
+    let (from_version, migrations) = if dry_run && !db_exists {
+        (0, migrations::all())
+    } else {
+        let storage = SqliteStorage::open_unmigrated(cwd)?;
+        let from = storage.schema_version()?;
+        let list = if dry_run {
+            storage.pending_migrations()?
+        } else {
+            storage.migrate()?
+        };
+        (from, list)
+    };
+
+    let to_version = migrations.last().map(|m| m.version).unwrap_or(from_version);
+
+    if json_output {
+        let result = serde_json::json!({
+            "dry_run": dry_run,
+            "from_version": from_version,
+            "to_version": to_version,
+            "migrations": migrations.iter().map(|m| serde_json::json!({
+                "version": m.version,
+                "description": m.description,
+            })).collect::<Vec<_>>(),
+        });
+        println!("{}", result);
+        return Ok(());
+    }
+
+    if migrations.is_empty() {
+        println!("Database is up to date (schema version {})", from_version);
+        return Ok(());
+    }
+
+    let verb = if dry_run { "Would apply" } else { "Applied" };
+    println!(
+        "{} {} migration(s): version {} -> {}",
+        verb,
+        migrations.len(),
+        from_version,
+        to_version
+    );
+    for m in &migrations {
+        println!("  {:3}  {}", m.version, m.description);
+    }
+
+    Ok(())
+}
+
+/// Execute `db check`
+pub fn check(repair: bool, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    check_with_cwd(repair, json_output, &cwd)
+}
+
+/// Execute `db check` with explicit project root
+///
+/// Fails when corruption remains after the (optional) repair, so scripts
+/// can gate on it. JSONL lines are reported whole and never repaired.
+pub fn check_with_cwd(repair: bool, json_output: bool, cwd: &Path) -> Result<()> {
+    let (problems, repaired) = match Config::load(cwd)?.storage.backend {
+        StorageBackend::Sqlite => {
+            let storage = SqliteStorage::open(cwd)?;
+            let problems = storage.check_integrity()?;
+            let repaired = if repair {
+                storage.repair(&problems)?
+            } else {
+                0
+            };
+            (problems, repaired)
+        }
+        StorageBackend::Jsonl => (storage::check_integrity(cwd)?, 0),
+    };
+    let remaining = problems.len() - repaired;
+
+    if json_output {
+        let result = serde_json::json!({
+            "problems": problems,
+            "repaired": repaired,
+            "remaining": remaining,
+        });
+        println!("{}", result);
+    } else if problems.is_empty() {
+        println!("No corrupt contracts found");
+    } else {
+        println!("{:15} {:14} {:10} VALUE", "ID", "FIELD", "REPAIR");
+        println!("{}", "-".repeat(60));
+        for p in &problems {
+            let action = match (p.repairable, repair) {
+                (true, true) => "repaired",
+                (true, false) => "available",
+                (false, _) => "manual",
+            };
+            println!(
+                "{:15} {:14} {:10} {}",
+                p.id,
+                p.field,
+                action,
+                p.value.as_deref().unwrap_or("NULL")
+            );
+        }
+        if repaired > 0 {
+            println!("\nRepaired {} field(s)", repaired);
+        }
+    }
+
+    if remaining > 0 {
+        let hint = if problems.iter().any(|p| p.repairable) && !repair {
+            " (run with --repair to fix the safe ones)"
+        } else {
+            ""
+        };
+        bail!("{} corrupt field(s) found{}", remaining, hint);
+    }
+
+    Ok(())
+}
+
+/// Refuse to touch a project configured for another backend
+fn ensure_sqlite(cwd: &Path) -> Result<()> {
+    let backend = Config::load(cwd)?.storage.backend;
+    if backend != StorageBackend::Sqlite {
+        bail!(
+            "This project uses the {} storage backend; db commands only apply to sqlite",
+            backend
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_migrate_dry_run_does_not_create_db() {
+        let tmp = TempDir::new().unwrap();
+        migrate_with_cwd(true, false, tmp.path()).unwrap();
+        assert!(!SqliteStorage::db_path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn test_check_clean_database() {
+        let tmp = TempDir::new().unwrap();
+        check_with_cwd(false, false, tmp.path()).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_rejects_jsonl_project() {
+        let tmp = TempDir::new().unwrap();
+        crate::storage::ensure_stead_dir(tmp.path()).unwrap();
+        std::fs::write(Config::path(tmp.path()), "[storage]\nbackend = \"jsonl\"").unwrap();
+
+        assert!(migrate_with_cwd(false, false, tmp.path()).is_err());
+        assert!(!SqliteStorage::db_path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn test_check_jsonl_project() {
+        let tmp = TempDir::new().unwrap();
+        crate::storage::ensure_stead_dir(tmp.path()).unwrap();
+        std::fs::write(Config::path(tmp.path()), "[storage]\nbackend = \"jsonl\"").unwrap();
+
+        let contract = crate::schema::Contract::new("task", "verify");
+        crate::storage::write_contract(&contract, tmp.path()).unwrap();
+        check_with_cwd(false, false, tmp.path()).unwrap();
+
+        let path = crate::storage::get_contracts_path(tmp.path());
+        let mut content = std::fs::read_to_string(&path).unwrap();
+        content.push_str("{invalid json\n");
+        std::fs::write(&path, content).unwrap();
+
+        assert!(check_with_cwd(true, false, tmp.path()).is_err());
+        assert!(!SqliteStorage::db_path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn test_migrate_dry_run_leaves_version() {
+        let tmp = TempDir::new().unwrap();
+        let storage = SqliteStorage::open_unmigrated(tmp.path()).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), 0);
+
+        migrate_with_cwd(true, true, tmp.path()).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), 0);
+
+        migrate_with_cwd(false, false, tmp.path()).unwrap();
+        assert_eq!(
+            storage.schema_version().unwrap(),
+            migrations::latest_version()
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Export command - write contracts as JSONL, JSON or CSV
+
+use crate::schema::Contract;
+use crate::storage::{ContractQuery, SortOrder, Storage};
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+
+/// Output format for `stead export`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One contract per line; the same layout as `.stead/contracts.jsonl`
+    #[default]
+    Jsonl,
+    /// A single JSON array
+    Json,
+    /// Flat summary table (lossy; for PRs and spreadsheets, not re-import)
+    Csv,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            _ => Err(format!(
+                "Invalid export format '{}'. Valid values: jsonl, json, csv",
+                s
+            )),
+        }
+    }
+}
+
+/// Execute the export command
+pub fn execute(format: ExportFormat, status_filter: Option<&str>) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(format, status_filter, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(
+    format: ExportFormat,
+    status_filter: Option<&str>,
+    cwd: &Path,
+) -> Result<()> {
+    let (_, db) = super::open_project(cwd, None)?;
+    execute_with_storage(format, status_filter, &*db)
+}
+
+/// Execute with a specific storage backend, writing to stdout
+pub fn execute_with_storage(
+    format: ExportFormat,
+    status_filter: Option<&str>,
+    storage: &dyn Storage,
+) -> Result<()> {
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    export(storage, format, status_filter, &mut out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Write contracts oldest first, so re-exports append rather than reshuffle
+pub fn export(
+    storage: &dyn Storage,
+    format: ExportFormat,
+    status_filter: Option<&str>,
+    out: &mut dyn Write,
+) -> Result<()> {
+    let mut query = ContractQuery::new().sort(SortOrder::OldestFirst);
+    if let Some(s) = status_filter {
+        query = query.status(super::list::parse_status(s)?);
+    }
+    let contracts = storage.query(&query)?;
+
+    match format {
+        ExportFormat::Jsonl => {
+            for c in &contracts {
+                writeln!(out, "{}", serde_json::to_string(c)?)?;
+            }
+        }
+        ExportFormat::Json => {
+            writeln!(out, "{}", serde_json::to_string_pretty(&contracts)?)?;
+        }
+        ExportFormat::Csv => write_csv(&contracts, out)?,
+    }
+
+    Ok(())
+}
+
+const CSV_HEADER: &[&str] = &[
+    "id",
+    "status",
+    "task",
+    "verification",
+    "owner",
+    "parent_id",
+    "blocked_by",
+    "tags",
+    "created_at",
+    "completed_at",
+];
+
+fn write_csv(contracts: &[Contract], out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "{}", CSV_HEADER.join(","))?;
+    for c in contracts {
+        let fields = [
+            c.id.clone(),
+            c.status.to_string(),
+            c.task.clone(),
+            c.verification.clone(),
+            c.owner.clone().unwrap_or_default(),
+            c.parent_id.clone().unwrap_or_default(),
+            c.blocked_by.join(";"),
+            c.tags.join(";"),
+            c.created_at.to_rfc3339(),
+            c.completed_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        writeln!(out, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+/// Quote a CSV field (RFC 4180) when it contains a delimiter, quote or newline
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::sqlite::SqliteStorage;
+
+    fn test_db() -> SqliteStorage {
+        SqliteStorage::open_in_memory().unwrap()
+    }
+
+    fn export_string(db: &SqliteStorage, format: ExportFormat, status: Option<&str>) -> String {
+        let mut buf = Vec::new();
+        export(db, format, status, &mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(
+            "JSONL".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Jsonl
+        );
+        assert_eq!("csv".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn test_export_jsonl_oldest_first() {
+        let db = test_db();
+        let mut old = Contract::new("old", "true");
+        old.created_at -= chrono::Duration::hours(1);
+        let new = Contract::new("new", "true");
+        db.save_contract(&new).unwrap();
+        db.save_contract(&old).unwrap();
+
+        let out = export_string(&db, ExportFormat::Jsonl, None);
+        let lines: Vec<Contract> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].task, "old");
+        assert_eq!(lines[1].task, "new");
+    }
+
+    #[test]
+    fn test_export_json_with_status_filter() {
+        let db = test_db();
+        let mut ready = Contract::new("ready", "true");
+        ready.mark_ready().unwrap();
+        db.save_contract(&ready).unwrap();
+        db.save_contract(&Contract::new("pending", "true")).unwrap();
+
+        let out = export_string(&db, ExportFormat::Json, Some("ready"));
+        let contracts: Vec<Contract> = serde_json::from_str(&out).unwrap();
+        assert_eq!(contracts.len(), 1);
+        assert_eq!(contracts[0].id, ready.id);
+
+        let mut buf = Vec::new();
+        assert!(export(&db, ExportFormat::Json, Some("bogus"), &mut buf).is_err());
+    }
+
+    #[test]
+    fn test_export_csv_escapes_fields() {
+        let db = test_db();
+        let mut c = Contract::new("fix \"rate\", limits", "cargo test");
+        c.tags = vec!["a".to_string(), "b".to_string()];
+        db.save_contract(&c).unwrap();
+
+        let out = export_string(&db, ExportFormat::Csv, None);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER.join(","));
+        assert!(lines[1].starts_with(&format!("{},pending,", c.id)));
+        assert!(lines[1].contains("\"fix \"\"rate\"\", limits\""));
+        assert!(lines[1].contains(",a;b,"));
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+}
@@ -0,0 +1,247 @@
+//! Gc command - apply retention rules and reclaim space in `.stead/`
+//!
+//! In order: archive finished contracts per `[retention]`, purge archived
+//! contracts past `delete_archived_after_days`, delete temp files left by
+//! interrupted rewrites of `contracts.jsonl` or `archive.jsonl`, then
+//! compact the storage backend (`VACUUM` for SQLite).
+//!
+//! stead doesn't store artifacts yet, so there is nothing else to collect;
+//! `[artifacts] ignore` is reserved for when it does.
+
+use crate::config::Config;
+use crate::storage::archive::Archive;
+use crate::storage::{get_contracts_temp_path, get_stead_dir, Storage};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Temp files younger than this may belong to a rewrite still in progress
+const TEMP_FILE_MIN_AGE: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// What a gc run did (or, with `--dry-run`, would do)
+#[derive(Debug, Default, Serialize)]
+pub struct GcReport {
+    /// IDs moved to the archive
+    pub archived: Vec<String>,
+    /// Archived contracts deleted for good
+    pub purged: usize,
+    /// Leftover temp files removed from `.stead/`
+    pub removed_files: Vec<PathBuf>,
+    /// Size of `.stead/` before and after, in bytes
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
+/// Execute the gc command
+pub fn execute(dry_run: bool, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(dry_run, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(dry_run: bool, json_output: bool, cwd: &Path) -> Result<()> {
+    let (config, db) = super::open_project(cwd, None)?;
+    execute_with_storage(dry_run, json_output, &config, &*db, cwd)
+}
+
+/// Execute with a specific storage backend for the project at `cwd`
+pub fn execute_with_storage(
+    dry_run: bool,
+    json_output: bool,
+    config: &Config,
+    storage: &dyn Storage,
+    cwd: &Path,
+) -> Result<()> {
+    let report = gc(config, storage, cwd, dry_run)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string(&report)?);
+        return Ok(());
+    }
+
+    let prefix = if dry_run { "Would have " } else { "" };
+    println!(
+        "{}archived {} contract(s), purged {} archived contract(s), removed {} temp file(s)",
+        prefix,
+        report.archived.len(),
+        report.purged,
+        report.removed_files.len()
+    );
+    for path in &report.removed_files {
+        println!("  {}", path.display());
+    }
+    if !dry_run {
+        println!(
+            ".stead/ size: {} -> {}",
+            format_bytes(report.bytes_before),
+            format_bytes(report.bytes_after)
+        );
+    }
+
+    Ok(())
+}
+
+/// Run every gc step for the project at `cwd`
+pub fn gc(config: &Config, storage: &dyn Storage, cwd: &Path, dry_run: bool) -> Result<GcReport> {
+    let retention = &config.retention;
+    let archive = Archive::new(cwd);
+    let mut report = GcReport {
+        bytes_before: dir_size(&get_stead_dir(cwd)),
+        ..Default::default()
+    };
+
+    let cutoff = Utc::now() - Duration::days(retention.archive_after_days as i64);
+    report.archived = super::archive::archive_contracts(
+        storage,
+        &archive,
+        &retention.archive_statuses,
+        cutoff,
+        dry_run,
+    )?
+    .into_iter()
+    .map(|c| c.id)
+    .collect();
+
+    if let Some(days) = retention.delete_archived_after_days {
+        let cutoff = Utc::now() - Duration::days(days as i64);
+        report.purged = if dry_run {
+            archive
+                .load_all()?
+                .iter()
+                .filter(|c| crate::storage::archive::finished_at(c) < cutoff)
+                .count()
+        } else {
+            archive.prune(cutoff)?
+        };
+    }
+
+    report.removed_files = leftover_temp_files(cwd, SystemTime::now())?;
+    if !dry_run {
+        for path in &report.removed_files {
+            std::fs::remove_file(path)?;
+        }
+        storage.compact()?;
+    }
+
+    report.bytes_after = dir_size(&get_stead_dir(cwd));
+    Ok(report)
+}
+
+/// Temp files left in `.stead/` by rewrites that never reached their rename.
+///
+/// Only stead's own temp files count, and only once they are older than
+/// `TEMP_FILE_MIN_AGE`, so a rewrite running in another process keeps its file.
+fn leftover_temp_files(cwd: &Path, now: SystemTime) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for path in [get_contracts_temp_path(cwd), Archive::temp_path(cwd)] {
+        let modified = match std::fs::metadata(&path) {
+            Ok(meta) if meta.is_file() => meta.modified()?,
+            Ok(_) => continue,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let age = now.duration_since(modified).unwrap_or_default();
+        if age >= TEMP_FILE_MIN_AGE {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Total size of the files directly in `dir`
+fn dir_size(dir: &Path) -> u64 {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok()?.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::archive::finished;
+    use crate::storage::sqlite::SqliteStorage;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_gc_applies_retention_and_removes_temp_files() {
+        let tmp = TempDir::new().unwrap();
+        let db = SqliteStorage::open(tmp.path()).unwrap();
+        let old = finished("old", 60);
+        db.save_contract(&old).unwrap();
+        db.save_contract(&finished("recent", 1)).unwrap();
+
+        let archive = Archive::new(tmp.path());
+        archive.append(&[finished("ancient", 500)]).unwrap();
+        let leftover = get_contracts_temp_path(tmp.path());
+        std::fs::write(&leftover, "partial").unwrap();
+        let two_hours_ago = SystemTime::now() - std::time::Duration::from_secs(2 * 60 * 60);
+        std::fs::File::options()
+            .write(true)
+            .open(&leftover)
+            .unwrap()
+            .set_modified(two_hours_ago)
+            .unwrap();
+        // A file stead doesn't own is left alone
+        let foreign = get_stead_dir(tmp.path()).join("notes.tmp");
+        std::fs::write(&foreign, "mine").unwrap();
+
+        let mut config = Config::default();
+        config.retention.delete_archived_after_days = Some(365);
+
+        let planned = gc(&config, &db, tmp.path(), true).unwrap();
+        assert_eq!(planned.archived, vec![old.id.clone()]);
+        assert_eq!(planned.purged, 1);
+        assert_eq!(planned.removed_files, vec![leftover.clone()]);
+        assert!(leftover.exists());
+        assert_eq!(db.load_all_contracts().unwrap().len(), 2);
+
+        let report = gc(&config, &db, tmp.path(), false).unwrap();
+        assert_eq!(report.archived, vec![old.id.clone()]);
+        assert_eq!(report.purged, 1);
+        assert!(!leftover.exists());
+        assert!(foreign.exists());
+        assert_eq!(db.load_all_contracts().unwrap().len(), 1);
+        let archived = archive.load_all().unwrap();
+        assert_eq!(archived.len(), 1);
+        assert_eq!(archived[0].id, old.id);
+    }
+
+    #[test]
+    fn test_recent_temp_files_are_kept() {
+        let tmp = TempDir::new().unwrap();
+        crate::storage::ensure_stead_dir(tmp.path()).unwrap();
+        let in_progress = Archive::temp_path(tmp.path());
+        std::fs::write(&in_progress, "partial").unwrap();
+
+        let now = SystemTime::now();
+        assert!(leftover_temp_files(tmp.path(), now).unwrap().is_empty());
+        let later = now + TEMP_FILE_MIN_AGE;
+        assert_eq!(
+            leftover_temp_files(tmp.path(), later).unwrap(),
+            vec![in_progress]
+        );
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+}
@@ -0,0 +1,290 @@
+//! Import command - load contracts exported by `stead export`
+//!
+//! Accepts JSONL (including a legacy `.stead/contracts.jsonl`) or a JSON
+//! array. Contracts are stored as-is, so `created_at`, `owner` and the
+//! dependency links survive the round trip.
+
+use crate::schema::{generate_id, Contract};
+use crate::storage::Storage;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// What to do when an imported contract's ID already exists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnConflict {
+    /// Keep the existing contract
+    #[default]
+    Skip,
+    /// Replace the existing contract with the imported one
+    Overwrite,
+    /// Import under a fresh ID, rewriting links within the imported set
+    Rename,
+}
+
+impl std::str::FromStr for OnConflict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "skip" => Ok(OnConflict::Skip),
+            "overwrite" => Ok(OnConflict::Overwrite),
+            "rename" => Ok(OnConflict::Rename),
+            _ => Err(format!(
+                "Invalid conflict mode '{}'. Valid values: skip, overwrite, rename",
+                s
+            )),
+        }
+    }
+}
+
+/// Outcome of an import
+#[derive(Debug, Default, Serialize)]
+pub struct ImportReport {
+    /// IDs stored without a conflict
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+    pub overwritten: Vec<String>,
+    /// Original ID -> newly assigned ID
+    pub renamed: HashMap<String, String>,
+}
+
+/// Execute the import command
+pub fn execute(file: &Path, on_conflict: OnConflict, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(file, on_conflict, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(
+    file: &Path,
+    on_conflict: OnConflict,
+    json_output: bool,
+    cwd: &Path,
+) -> Result<()> {
+    let (_, db) = super::open_project(cwd, None)?;
+    execute_with_storage(file, on_conflict, json_output, &*db)
+}
+
+/// Execute with a specific storage backend
+pub fn execute_with_storage(
+    file: &Path,
+    on_conflict: OnConflict,
+    json_output: bool,
+    storage: &dyn Storage,
+) -> Result<()> {
+    let content =
+        std::fs::read_to_string(file).with_context(|| format!("Cannot read {}", file.display()))?;
+    let contracts = parse(&content).with_context(|| format!("Invalid {}", file.display()))?;
+    let report = import(storage, contracts, on_conflict)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string(&report)?);
+        return Ok(());
+    }
+
+    println!(
+        "Imported {} contract(s): {} new, {} overwritten, {} renamed, {} skipped",
+        report.imported.len() + report.overwritten.len() + report.renamed.len(),
+        report.imported.len(),
+        report.overwritten.len(),
+        report.renamed.len(),
+        report.skipped.len()
+    );
+    let mut renamed: Vec<_> = report.renamed.iter().collect();
+    renamed.sort();
+    for (old, new) in renamed {
+        println!("  {} -> {}", old, new);
+    }
+
+    Ok(())
+}
+
+/// Parse a JSON array or JSONL document into contracts
+pub fn parse(content: &str) -> Result<Vec<Contract>> {
+    if content.trim_start().starts_with('[') {
+        return Ok(serde_json::from_str(content)?);
+    }
+
+    let mut contracts = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let contract = serde_json::from_str(line).with_context(|| format!("line {}", i + 1))?;
+        contracts.push(contract);
+    }
+    Ok(contracts)
+}
+
+/// Store `contracts`, resolving ID conflicts per `on_conflict`
+pub fn import(
+    storage: &dyn Storage,
+    mut contracts: Vec<Contract>,
+    on_conflict: OnConflict,
+) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+
+    let mut seen = HashSet::new();
+    for c in &contracts {
+        if !seen.insert(c.id.as_str()) {
+            bail!("Duplicate contract ID in import: {}", c.id);
+        }
+    }
+
+    if on_conflict == OnConflict::Rename {
+        for c in &contracts {
+            if storage.load_contract(&c.id)?.is_some() {
+                report.renamed.insert(c.id.clone(), generate_id());
+            }
+        }
+        for c in &mut contracts {
+            relink(c, &report.renamed);
+        }
+    }
+
+    // All-or-nothing: a failure part-way leaves the project untouched
+    let fresh_ids: HashSet<String> = report.renamed.values().cloned().collect();
+    storage.transaction(&mut |tx| {
+        for contract in &contracts {
+            if fresh_ids.contains(&contract.id) {
+                tx.save_contract(contract)?;
+                continue;
+            }
+
+            if tx.load_contract(&contract.id)?.is_none() {
+                tx.save_contract(contract)?;
+                report.imported.push(contract.id.clone());
+                continue;
+            }
+
+            match on_conflict {
+                OnConflict::Skip | OnConflict::Rename => report.skipped.push(contract.id.clone()),
+                OnConflict::Overwrite => {
+                    tx.update_contract(contract)?;
+                    report.overwritten.push(contract.id.clone());
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(report)
+}
+
+/// Point a contract's own ID and its links at renamed IDs
+fn relink(contract: &mut Contract, renamed: &HashMap<String, String>) {
+    let map = |id: &mut String| {
+        if let Some(new) = renamed.get(id) {
+            *id = new.clone();
+        }
+    };
+    map(&mut contract.id);
+    if let Some(parent) = contract.parent_id.as_mut() {
+        map(parent);
+    }
+    contract.blocked_by.iter_mut().for_each(map);
+    contract.blocks.iter_mut().for_each(map);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::sqlite::SqliteStorage;
+
+    fn test_db() -> SqliteStorage {
+        SqliteStorage::open_in_memory().unwrap()
+    }
+
+    #[test]
+    fn test_parse_jsonl_and_json() {
+        let a = Contract::new("a", "true");
+        let b = Contract::new("b", "true");
+        let jsonl = format!(
+            "{}\n\n{}\n",
+            serde_json::to_string(&a).unwrap(),
+            serde_json::to_string(&b).unwrap()
+        );
+        assert_eq!(parse(&jsonl).unwrap().len(), 2);
+
+        let json = serde_json::to_string_pretty(&vec![a, b]).unwrap();
+        assert_eq!(parse(&json).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_reports_line() {
+        let a = serde_json::to_string(&Contract::new("a", "true")).unwrap();
+        let err = parse(&format!("{}\n{{oops\n", a)).unwrap_err();
+        assert!(format!("{:#}", err).contains("line 2"));
+    }
+
+    #[test]
+    fn test_import_preserves_fields() {
+        let db = test_db();
+        let mut c = Contract::new("task", "true");
+        c.created_at -= chrono::Duration::days(3);
+        c.owner = Some("alice".to_string());
+        c.blocked_by = vec!["dep".to_string()];
+
+        let report = import(&db, vec![c.clone()], OnConflict::Skip).unwrap();
+        assert_eq!(report.imported, vec![c.id.clone()]);
+
+        let loaded = db.load_contract(&c.id).unwrap().unwrap();
+        assert_eq!(loaded.created_at, c.created_at);
+        assert_eq!(loaded.owner.as_deref(), Some("alice"));
+        assert_eq!(loaded.blocked_by, vec!["dep"]);
+    }
+
+    #[test]
+    fn test_conflict_skip_and_overwrite() {
+        let db = test_db();
+        let existing = Contract::new("existing", "true");
+        db.save_contract(&existing).unwrap();
+
+        let mut incoming = existing.clone();
+        incoming.task = "incoming".to_string();
+
+        let report = import(&db, vec![incoming.clone()], OnConflict::Skip).unwrap();
+        assert_eq!(report.skipped, vec![existing.id.clone()]);
+        assert_eq!(
+            db.load_contract(&existing.id).unwrap().unwrap().task,
+            "existing"
+        );
+
+        let report = import(&db, vec![incoming], OnConflict::Overwrite).unwrap();
+        assert_eq!(report.overwritten, vec![existing.id.clone()]);
+        assert_eq!(
+            db.load_contract(&existing.id).unwrap().unwrap().task,
+            "incoming"
+        );
+    }
+
+    #[test]
+    fn test_conflict_rename_relinks() {
+        let db = test_db();
+        let parent = Contract::new("parent", "true");
+        let child = parent.spawn_child("child", "true");
+        db.save_contract(&parent).unwrap();
+
+        let report = import(&db, vec![parent.clone(), child.clone()], OnConflict::Rename).unwrap();
+        let new_parent = report.renamed.get(&parent.id).unwrap();
+        assert_eq!(report.imported, vec![child.id.clone()]);
+
+        assert_eq!(db.load_all_contracts().unwrap().len(), 3);
+        let child = db.load_contract(&child.id).unwrap().unwrap();
+        assert_eq!(child.parent_id.as_ref(), Some(new_parent));
+        assert_eq!(
+            db.load_contract(new_parent).unwrap().unwrap().task,
+            "parent"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_ids_in_file_rejected() {
+        let db = test_db();
+        let c = Contract::new("task", "true");
+        assert!(import(&db, vec![c.clone(), c], OnConflict::Skip).is_err());
+        assert!(db.load_all_contracts().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+//! Init command - create .stead/config.toml and contract storage
+
+use crate::config::{Config, DEFAULT_CONFIG_TOML};
+use crate::storage::registry::Registry;
+use crate::storage::{self, StorageBackend};
+use anyhow::Result;
+use std::path::Path;
+
+/// Execute the init command
+pub fn execute(backend: Option<StorageBackend>, register: bool, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(backend, register, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+///
+/// Idempotent: an existing config is validated and left untouched. A new
+/// config records `backend` (default SQLite) as the project's storage.
+/// `register` creates the global registry, which is the only way to turn
+/// it on.
+pub fn execute_with_cwd(
+    backend: Option<StorageBackend>,
+    register: bool,
+    json_output: bool,
+    cwd: &Path,
+) -> Result<()> {
+    let config_path = Config::path(cwd);
+    let created_config = !config_path.exists();
+
+    if created_config {
+        storage::ensure_stead_dir(cwd)?;
+        std::fs::write(&config_path, config_template(backend.unwrap_or_default()))?;
+    }
+
+    let (config, _) = super::open_project(cwd, backend)?;
+    let storage_path = config.storage.backend.path(cwd);
+    if register {
+        Registry::open_default()?.register(cwd)?;
+    }
+
+    if json_output {
+        let result = serde_json::json!({
+            "root": cwd.display().to_string(),
+            "config": config_path.display().to_string(),
+            "backend": config.storage.backend,
+            "storage": storage_path.display().to_string(),
+            "created_config": created_config,
+            "registered": register,
+        });
+        println!("{}", result);
+    } else {
+        println!("Initialized stead in {}", cwd.display());
+        let note = if created_config {
+            "created"
+        } else {
+            "already exists"
+        };
+        println!("  Config:   {} ({})", config_path.display(), note);
+        println!(
+            "  Storage:  {} ({})",
+            storage_path.display(),
+            config.storage.backend
+        );
+        if register {
+            println!("  Registry: registered");
+        }
+    }
+
+    Ok(())
+}
+
+/// Default config with `backend` filled in
+fn config_template(backend: StorageBackend) -> String {
+    DEFAULT_CONFIG_TOML.replace(
+        "backend = \"sqlite\"",
+        &format!("backend = \"{}\"", backend),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_init_creates_config_and_db() {
+        let tmp = TempDir::new().unwrap();
+        execute_with_cwd(None, false, false, tmp.path()).unwrap();
+
+        assert!(Config::path(tmp.path()).exists());
+        assert!(StorageBackend::Sqlite.path(tmp.path()).exists());
+        assert_eq!(Config::load(tmp.path()).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_init_with_jsonl_backend() {
+        let tmp = TempDir::new().unwrap();
+        execute_with_cwd(Some(StorageBackend::Jsonl), false, false, tmp.path()).unwrap();
+
+        let config = Config::load(tmp.path()).unwrap();
+        assert_eq!(config.storage.backend, StorageBackend::Jsonl);
+        assert!(StorageBackend::Jsonl.path(tmp.path()).exists());
+        assert!(!StorageBackend::Sqlite.path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn test_init_keeps_existing_config() {
+        let tmp = TempDir::new().unwrap();
+        execute_with_cwd(None, false, false, tmp.path()).unwrap();
+        std::fs::write(Config::path(tmp.path()), "tags = [\"keep\"]\n").unwrap();
+
+        execute_with_cwd(None, false, true, tmp.path()).unwrap();
+        assert_eq!(Config::load(tmp.path()).unwrap().tags, vec!["keep"]);
+    }
+
+    #[test]
+    fn test_init_rejects_invalid_config() {
+        let tmp = TempDir::new().unwrap();
+        storage::ensure_stead_dir(tmp.path()).unwrap();
+        std::fs::write(Config::path(tmp.path()), "retry = 3").unwrap();
+
+        assert!(execute_with_cwd(None, false, false, tmp.path()).is_err());
+    }
+}
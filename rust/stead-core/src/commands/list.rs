@@ -1,36 +1,61 @@
 //! List command - display contracts with optional filtering
 
 use crate::schema::ContractStatus;
-use crate::storage::{self, Storage};
+use crate::storage::registry::Registry;
+use crate::storage::{self, ContractQuery, Storage, StorageError};
 use anyhow::Result;
 use std::path::Path;
 
+/// Filters for the list command
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    pub status: Option<String>,
+    pub owner: Option<String>,
+    /// Contract must carry all of these tags
+    pub tags: Vec<String>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl ListOptions {
+    /// Validate the filters and turn them into a storage query
+    pub fn to_query(&self) -> Result<ContractQuery> {
+        let mut query = ContractQuery::new().offset(self.offset);
+        if let Some(status) = &self.status {
+            query = query.status(parse_status(status)?);
+        }
+        if let Some(owner) = &self.owner {
+            query = query.owner(owner.clone());
+        }
+        for tag in &self.tags {
+            query = query.tag(tag.clone());
+        }
+        if let Some(limit) = self.limit {
+            query = query.limit(limit);
+        }
+        Ok(query)
+    }
+}
+
 /// Execute the list command
-pub fn execute(status_filter: Option<&str>, json_output: bool) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-    let db = storage::sqlite::open_default(&cwd)?;
-    execute_with_storage(status_filter, json_output, &db)
+pub fn execute(options: &ListOptions, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(options, json_output, &cwd)
 }
 
 /// Execute with explicit working directory (for testing)
-pub fn execute_with_cwd(status_filter: Option<&str>, json_output: bool, cwd: &Path) -> Result<()> {
-    let db = storage::sqlite::open_default(cwd)?;
-    execute_with_storage(status_filter, json_output, &db)
+pub fn execute_with_cwd(options: &ListOptions, json_output: bool, cwd: &Path) -> Result<()> {
+    let (_, db) = super::open_project(cwd, None)?;
+    execute_with_storage(options, json_output, &*db)
 }
 
 /// Execute with a specific storage backend
 pub fn execute_with_storage(
-    status_filter: Option<&str>,
+    options: &ListOptions,
     json_output: bool,
     storage: &dyn Storage,
 ) -> Result<()> {
-    let mut contracts = storage.load_all_contracts()?;
-
-    // Filter by status if provided
-    if let Some(status_str) = status_filter {
-        let status = parse_status(status_str)?;
-        contracts.retain(|c| c.status == status);
-    }
+    let contracts = storage.query(&options.to_query()?)?;
 
     if json_output {
         println!("{}", serde_json::to_string(&contracts)?);
@@ -43,13 +68,14 @@ pub fn execute_with_storage(
     }
 
     // Print table header
-    println!("{:15} {:9} {:30} {:16}", "ID", "STATUS", "TASK", "CREATED");
-    println!("{}", "-".repeat(72));
+    let w = ContractStatus::MAX_NAME_LEN;
+    println!("{:15} {:w$} {:30} {:16}", "ID", "STATUS", "TASK", "CREATED");
+    println!("{}", "-".repeat(64 + w));
 
     // Print each contract
     for contract in contracts {
         println!(
-            "{:15} {:9} {:30} {:16}",
+            "{:15} {:w$} {:30} {:16}",
             truncate(&contract.id, 15),
             contract.status,
             truncate(&contract.task, 30),
@@ -60,18 +86,77 @@ pub fn execute_with_storage(
     Ok(())
 }
 
+/// Execute `list --all-projects`: contracts from every registered project.
+///
+/// Fails when the registry is off rather than turning it on; the current
+/// project is registered first so it always shows up.
+pub fn execute_all_projects(options: &ListOptions, json_output: bool, cwd: &Path) -> Result<()> {
+    let registry = Registry::open_if_enabled()?.ok_or(StorageError::RegistryDisabled)?;
+    if storage::is_initialized(cwd) {
+        registry.register(cwd)?;
+    }
+    execute_with_registry(options, json_output, &registry)
+}
+
+/// Execute `list --all-projects` against a specific registry
+pub fn execute_with_registry(
+    options: &ListOptions,
+    json_output: bool,
+    registry: &Registry,
+) -> Result<()> {
+    let all = registry.load_all_contracts(&options.to_query()?)?;
+    for failure in &all.errors {
+        eprintln!("Warning: skipping {}: {}", failure.project, failure.message);
+    }
+    let contracts = all.contracts;
+
+    if json_output {
+        println!("{}", serde_json::to_string(&contracts)?);
+        return Ok(());
+    }
+
+    if contracts.is_empty() {
+        println!("No contracts found");
+        return Ok(());
+    }
+
+    let w = ContractStatus::MAX_NAME_LEN;
+    println!(
+        "{:20} {:15} {:w$} {:30} {:16}",
+        "PROJECT", "ID", "STATUS", "TASK", "CREATED"
+    );
+    println!("{}", "-".repeat(85 + w));
+
+    for pc in contracts {
+        let name = Path::new(&pc.project)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| pc.project.clone());
+        println!(
+            "{:20} {:15} {:w$} {:30} {:16}",
+            truncate(&name, 20),
+            truncate(&pc.contract.id, 15),
+            pc.contract.status,
+            truncate(&pc.contract.task, 30),
+            format_date(&pc.contract.created_at)
+        );
+    }
+
+    Ok(())
+}
+
 /// Parse status string to enum
-fn parse_status(s: &str) -> Result<ContractStatus> {
+pub(crate) fn parse_status(s: &str) -> Result<ContractStatus> {
     s.to_lowercase()
         .parse::<ContractStatus>()
         .map_err(|_| anyhow::anyhow!(
-            "Invalid status '{}'. Valid values: pending, ready, claimed, executing, verifying, completed, failed, rollingback, rolledback, cancelled",
+            "Invalid status '{}'. Valid values: pending, ready, claimed, executing, verifying, awaitingapproval, completed, failed, rollingback, rolledback, cancelled",
             s
         ))
 }
 
 /// Truncate string with ellipsis (UTF-8 safe)
-fn truncate(s: &str, max_len: usize) -> String {
+pub fn truncate(s: &str, max_len: usize) -> String {
     let char_count = s.chars().count();
     if char_count <= max_len {
         s.to_string()
@@ -96,6 +181,13 @@ mod tests {
         SqliteStorage::open_in_memory().unwrap()
     }
 
+    fn with_status(status: &str) -> ListOptions {
+        ListOptions {
+            status: Some(status.to_string()),
+            ..Default::default()
+        }
+    }
+
     #[test]
     fn test_parse_status() {
         assert_eq!(parse_status("pending").unwrap(), ContractStatus::Pending);
@@ -124,7 +216,7 @@ mod tests {
     #[test]
     fn test_list_empty() {
         let db = test_db();
-        execute_with_storage(None, false, &db).unwrap();
+        execute_with_storage(&ListOptions::default(), false, &db).unwrap();
     }
 
     #[test]
@@ -137,7 +229,7 @@ mod tests {
         let c2 = Contract::new("task 2", "verify 2");
         db.save_contract(&c2).unwrap();
 
-        execute_with_storage(None, false, &db).unwrap();
+        execute_with_storage(&ListOptions::default(), false, &db).unwrap();
     }
 
     #[test]
@@ -147,7 +239,7 @@ mod tests {
         let c = Contract::new("task", "verify");
         db.save_contract(&c).unwrap();
 
-        execute_with_storage(None, true, &db).unwrap();
+        execute_with_storage(&ListOptions::default(), true, &db).unwrap();
     }
 
     #[test]
@@ -158,13 +250,48 @@ mod tests {
         db.save_contract(&c).unwrap();
 
         // Should work with valid status
-        execute_with_storage(Some("pending"), false, &db).unwrap();
+        execute_with_storage(&with_status("pending"), false, &db).unwrap();
+    }
+
+    #[test]
+    fn test_list_all_projects() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = SqliteStorage::open(dir.path()).unwrap();
+        db.save_contract(&Contract::new("task", "verify")).unwrap();
+
+        let registry = Registry::open(&dir.path().join("registry.db")).unwrap();
+        registry.register(dir.path()).unwrap();
+
+        execute_with_registry(&ListOptions::default(), false, &registry).unwrap();
+        execute_with_registry(&with_status("pending"), true, &registry).unwrap();
+        assert!(execute_with_registry(&with_status("invalid"), false, &registry).is_err());
+    }
+
+    #[test]
+    fn test_options_to_query() {
+        let options = ListOptions {
+            status: Some("READY".to_string()),
+            owner: Some("bot".to_string()),
+            tags: vec!["api".to_string()],
+            limit: Some(10),
+            offset: 20,
+        };
+        let query = options.to_query().unwrap();
+        assert_eq!(
+            query,
+            ContractQuery::new()
+                .status(ContractStatus::Ready)
+                .owner("bot")
+                .tag("api")
+                .limit(10)
+                .offset(20)
+        );
     }
 
     #[test]
     fn test_list_invalid_status() {
         let db = test_db();
-        let result = execute_with_storage(Some("invalid"), false, &db);
+        let result = execute_with_storage(&with_status("invalid"), false, &db);
         assert!(result.is_err());
     }
 }
@@ -1,10 +1,43 @@
 //! Command implementations
 
+use crate::config::Config;
+use crate::storage::{self, Storage, StorageBackend};
+use std::path::Path;
+
+pub mod approve;
+pub mod archive;
+pub mod attention;
 pub mod cancel;
 pub mod claim;
 pub mod create;
+pub mod db;
+pub mod export;
+pub mod gc;
+pub mod import;
+pub mod init;
 pub mod list;
+pub(crate) mod process;
+pub mod reject;
 pub mod run;
+pub mod search;
 pub mod session;
 pub mod show;
+pub mod usage;
 pub mod verify;
+pub mod watch;
+
+/// Load a project's config and open its contract storage.
+///
+/// `backend` overrides the configured `[storage] backend` (the `--storage`
+/// flag); the returned config reflects the backend actually used.
+pub fn open_project(
+    root: &Path,
+    backend: Option<StorageBackend>,
+) -> anyhow::Result<(Config, Box<dyn Storage>)> {
+    let mut config = Config::load(root)?;
+    if let Some(backend) = backend {
+        config.storage.backend = backend;
+    }
+    let storage = storage::open(root, config.storage.backend)?;
+    Ok((config, storage))
+}
@@ -0,0 +1,227 @@
+//! Child process helpers shared by commands: executor, verification, hooks
+
+use crate::config::Config;
+use crate::process::run_with_timeout;
+use crate::schema::{Contract, ContractStatus};
+use anyhow::{Context, Result};
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+/// Build a command that runs `script` through the configured shell
+fn shell_command(script: &str, config: &Config) -> Command {
+    let (program, flags) = match config.verification.shell.split_first() {
+        Some((program, flags)) => (program.as_str(), flags),
+        None => ("sh", &[][..]),
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(flags).arg(script);
+    cmd
+}
+
+/// Run the configured executor (agent CLI) with the task
+pub(crate) fn spawn_executor(task: &str, config: &Config) -> Result<()> {
+    let mut cmd = Command::new(&config.executor.command);
+    cmd.args(&config.executor.args).arg(task);
+
+    let output = run_with_timeout(cmd, config.executor_timeout())
+        .with_context(|| format!("Failed to execute {}", config.executor.command))?;
+
+    if output.timed_out {
+        anyhow::bail!(
+            "{} timed out after {}s",
+            config.executor.command,
+            config.executor.timeout_secs.unwrap_or_default()
+        );
+    }
+    if !output.success {
+        anyhow::bail!(
+            "{} exited with error: {}",
+            config.executor.command,
+            output.stderr
+        );
+    }
+
+    Ok(())
+}
+
+/// Run a verification command, retrying per the config's retry policy
+pub(crate) fn run_verification(cmd: &str, config: &Config) -> Result<(bool, Option<String>)> {
+    let attempts = config.retry.max_attempts.max(1);
+    let mut attempt = 1;
+
+    loop {
+        let output = run_with_timeout(shell_command(cmd, config), config.verification_timeout())
+            .context("Failed to run verification command")?;
+
+        if output.success || attempt >= attempts {
+            let mut text = output.combined();
+            if output.timed_out {
+                let note = format!(
+                    "[verification timed out after {}s]",
+                    config.verification.timeout_secs.unwrap_or_default()
+                );
+                text = Some(match text {
+                    Some(t) => format!("{}\n{}", t, note),
+                    None => note,
+                });
+            }
+            return Ok((output.success, text));
+        }
+
+        attempt += 1;
+        thread::sleep(Duration::from_secs(config.retry.delay_secs));
+    }
+}
+
+/// Contract lifecycle events that can trigger a hook
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum HookEvent {
+    Create,
+    Complete,
+    Fail,
+}
+
+impl HookEvent {
+    /// Event implied by a contract's status after verification or sign-off
+    pub fn for_status(status: ContractStatus) -> Option<Self> {
+        match status {
+            ContractStatus::Completed => Some(Self::Complete),
+            ContractStatus::Failed => Some(Self::Fail),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Create => "on_create",
+            Self::Complete => "on_complete",
+            Self::Fail => "on_fail",
+        }
+    }
+}
+
+/// Run the configured hook for `event`, if any.
+///
+/// Best-effort: a failing hook prints a warning but never fails the command.
+pub(crate) fn run_hook(event: HookEvent, contract: &Contract, config: &Config) {
+    let hook = match event {
+        HookEvent::Create => &config.hooks.on_create,
+        HookEvent::Complete => &config.hooks.on_complete,
+        HookEvent::Fail => &config.hooks.on_fail,
+    };
+    let Some(script) = hook.as_deref().filter(|s| !s.trim().is_empty()) else {
+        return;
+    };
+
+    let mut cmd = shell_command(script, config);
+    cmd.env("STEAD_CONTRACT_ID", &contract.id)
+        .env("STEAD_CONTRACT_STATUS", contract.status.to_string())
+        .env("STEAD_CONTRACT_TASK", &contract.task);
+
+    match run_with_timeout(cmd, config.verification_timeout()) {
+        Ok(output) if output.success => {}
+        Ok(output) => eprintln!(
+            "Warning: {} hook failed: {}",
+            event.name(),
+            output.combined().unwrap_or_default()
+        ),
+        Err(e) => eprintln!("Warning: {} hook failed: {}", event.name(), e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verification_pass() {
+        let (passed, output) = run_verification("echo hello", &Config::default()).unwrap();
+        assert!(passed);
+        assert_eq!(output, Some("hello".to_string()));
+    }
+
+    #[test]
+    fn test_verification_fail() {
+        let cmd = if cfg!(target_os = "windows") {
+            "exit 1"
+        } else {
+            "false"
+        };
+        let (passed, _) = run_verification(cmd, &Config::default()).unwrap();
+        assert!(!passed);
+    }
+
+    #[test]
+    fn test_verification_captures_stderr() {
+        let cmd = if cfg!(target_os = "windows") {
+            "echo error 1>&2"
+        } else {
+            "echo error >&2"
+        };
+        let (passed, output) = run_verification(cmd, &Config::default()).unwrap();
+        assert!(passed);
+        assert!(output.unwrap().contains("error"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_verification_timeout() {
+        let mut config = Config::default();
+        config.verification.timeout_secs = Some(0);
+        let (passed, output) = run_verification("exec sleep 5", &config).unwrap();
+        assert!(!passed);
+        assert!(output.unwrap().contains("timed out"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_verification_retries() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let marker = tmp.path().join("attempted");
+        // Fails on the first attempt, passes on the second
+        let cmd = format!(
+            "if [ -f {0} ]; then echo ok; else touch {0}; exit 1; fi",
+            marker.display()
+        );
+
+        let (passed, _) = run_verification(&cmd, &Config::default()).unwrap();
+        assert!(!passed);
+
+        std::fs::remove_file(&marker).unwrap();
+        let mut config = Config::default();
+        config.retry.max_attempts = 2;
+        let (passed, output) = run_verification(&cmd, &config).unwrap();
+        assert!(passed);
+        assert_eq!(output, Some("ok".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_custom_shell() {
+        let mut config = Config::default();
+        config.verification.shell = vec!["bash".to_string(), "-c".to_string()];
+        let (passed, output) = run_verification("echo $0", &config).unwrap();
+        assert!(passed);
+        assert_eq!(output, Some("bash".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hook_receives_contract_env() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let out = tmp.path().join("hook.txt");
+        let mut config = Config::default();
+        config.hooks.on_complete = Some(format!(
+            "echo \"$STEAD_CONTRACT_ID $STEAD_CONTRACT_STATUS\" > {}",
+            out.display()
+        ));
+
+        let mut contract = Contract::new("task", "true");
+        contract.finish_verification(true, None);
+        run_hook(HookEvent::Complete, &contract, &config);
+
+        let written = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(written.trim(), format!("{} completed", contract.id));
+    }
+}
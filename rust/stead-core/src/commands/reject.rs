@@ -0,0 +1,159 @@
+//! Reject command - refuse sign-off on a contract awaiting approval
+
+use super::approve::SignOffError;
+use super::process::{self, HookEvent};
+use crate::config::Config;
+use crate::schema::Contract;
+use crate::storage::Storage;
+use anyhow::Result;
+use std::path::Path;
+
+/// Execute the reject command
+pub fn execute(id: &str, reason: &str, approver: Option<&str>, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(id, reason, approver, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(
+    id: &str,
+    reason: &str,
+    approver: Option<&str>,
+    json_output: bool,
+    cwd: &Path,
+) -> Result<()> {
+    let (config, db) = super::open_project(cwd, None)?;
+    execute_with_storage(id, reason, approver, json_output, &config, &*db)
+}
+
+/// Execute with a specific storage backend
+pub fn execute_with_storage(
+    id: &str,
+    reason: &str,
+    approver: Option<&str>,
+    json_output: bool,
+    config: &Config,
+    storage: &dyn Storage,
+) -> Result<()> {
+    let approver = super::approve::resolve_approver(approver)?;
+    let contract = reject(storage, config, id, &approver, reason)?;
+
+    if json_output {
+        println!("{}", serde_json::to_string(&contract)?);
+    } else {
+        println!(
+            "Contract {} rejected by {}: {}",
+            contract.id, approver, reason
+        );
+    }
+
+    Ok(())
+}
+
+/// Reject a contract, persist the decision and run the `on_fail` hook.
+/// The CLI and the FFI both sign off through here.
+pub fn reject(
+    storage: &dyn Storage,
+    config: &Config,
+    id: &str,
+    approver: &str,
+    reason: &str,
+) -> Result<Contract, SignOffError> {
+    if reason.trim().is_empty() {
+        return Err(SignOffError::MissingReason);
+    }
+
+    let mut contract = storage
+        .load_contract(id)?
+        .ok_or_else(|| SignOffError::NotFound(id.to_string()))?;
+
+    contract.reject(approver, reason)?;
+    storage.update_contract(&contract)?;
+    process::run_hook(HookEvent::Fail, &contract, config);
+
+    Ok(contract)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::ContractStatus;
+    use crate::storage::sqlite::SqliteStorage;
+
+    fn test_db() -> SqliteStorage {
+        SqliteStorage::open_in_memory().unwrap()
+    }
+
+    #[test]
+    fn test_reject_awaiting_contract() {
+        let db = test_db();
+        let mut contract = Contract::new("migration", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(true, None);
+        db.save_contract(&contract).unwrap();
+
+        execute_with_storage(
+            &contract.id,
+            "drops a column",
+            Some("bob"),
+            false,
+            &Config::default(),
+            &db,
+        )
+        .unwrap();
+
+        let loaded = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(loaded.status, ContractStatus::Failed);
+        let approval = loaded.approval.unwrap();
+        assert_eq!(approval.approver, "bob");
+        assert!(!approval.approved);
+        assert_eq!(approval.reason, Some("drops a column".to_string()));
+    }
+
+    #[test]
+    fn test_reject_requires_reason() {
+        let db = test_db();
+        let mut contract = Contract::new("migration", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(true, None);
+        db.save_contract(&contract).unwrap();
+
+        assert!(execute_with_storage(
+            &contract.id,
+            "  ",
+            Some("bob"),
+            false,
+            &Config::default(),
+            &db
+        )
+        .is_err());
+        assert!(matches!(
+            reject(&db, &Config::default(), &contract.id, "bob", ""),
+            Err(SignOffError::MissingReason)
+        ));
+        let loaded = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(loaded.status, ContractStatus::AwaitingApproval);
+    }
+
+    #[test]
+    fn test_reject_runs_fail_hook() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let out = tmp.path().join("hook.txt");
+        let mut config = Config::default();
+        config.hooks.on_fail = Some(format!(
+            "echo \"$STEAD_CONTRACT_STATUS\" > {}",
+            out.display()
+        ));
+
+        let db = test_db();
+        let mut contract = Contract::new("migration", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(true, None);
+        db.save_contract(&contract).unwrap();
+
+        reject(&db, &config, &contract.id, "bob", "drops a column").unwrap();
+
+        let written = std::fs::read_to_string(&out).unwrap();
+        assert_eq!(written.trim(), "failed");
+    }
+}
@@ -0,0 +1,150 @@
+//! Search command - full-text search over contracts
+
+use crate::schema::ContractStatus;
+use crate::storage::archive::Archive;
+use crate::storage::search::{SearchHit, SearchQuery};
+use crate::storage::Storage;
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// Filters for the search command
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub status: Option<String>,
+    pub tags: Vec<String>,
+    pub limit: usize,
+}
+
+/// Execute the search command
+pub fn execute(query: &str, options: &SearchOptions, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(query, options, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(
+    query: &str,
+    options: &SearchOptions,
+    json_output: bool,
+    cwd: &Path,
+) -> Result<()> {
+    let (_, db) = super::open_project(cwd, None)?;
+    execute_with_storage(query, options, json_output, &*db)
+}
+
+/// Execute with a specific storage backend
+pub fn execute_with_storage(
+    query: &str,
+    options: &SearchOptions,
+    json_output: bool,
+    storage: &dyn Storage,
+) -> Result<()> {
+    let hits = storage.search(&search_query(query, options)?)?;
+    print_hits(query, &hits, json_output)
+}
+
+/// Execute `search --archived` against a project's archive
+pub fn execute_with_archive(
+    query: &str,
+    options: &SearchOptions,
+    json_output: bool,
+    archive: &Archive,
+) -> Result<()> {
+    let hits = archive.search(&search_query(query, options)?)?;
+    print_hits(query, &hits, json_output)
+}
+
+fn search_query(query: &str, options: &SearchOptions) -> Result<SearchQuery> {
+    if query.trim().is_empty() {
+        bail!("Search query must not be empty");
+    }
+
+    Ok(SearchQuery {
+        text: query.to_string(),
+        status: options
+            .status
+            .as_deref()
+            .map(super::list::parse_status)
+            .transpose()?,
+        tags: options.tags.clone(),
+        limit: Some(options.limit),
+    })
+}
+
+fn print_hits(query: &str, hits: &[SearchHit], json_output: bool) -> Result<()> {
+    if json_output {
+        println!("{}", serde_json::to_string(hits)?);
+        return Ok(());
+    }
+
+    if hits.is_empty() {
+        println!("No contracts match '{}'", query);
+        return Ok(());
+    }
+
+    let w = ContractStatus::MAX_NAME_LEN;
+    println!("{:15} {:w$} {:6} TASK", "ID", "STATUS", "SCORE");
+    println!("{}", "-".repeat(63 + w));
+    for hit in hits {
+        println!(
+            "{:15} {:w$} {:6.2} {}",
+            super::list::truncate(&hit.contract.id, 15),
+            hit.contract.status,
+            hit.score,
+            super::list::truncate(&hit.contract.task, 40)
+        );
+        println!("    {}", hit.snippet);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Contract;
+    use crate::storage::sqlite::SqliteStorage;
+
+    fn options() -> SearchOptions {
+        SearchOptions {
+            limit: 20,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search_empty_query() {
+        let db = SqliteStorage::open_in_memory().unwrap();
+        assert!(execute_with_storage("  ", &options(), false, &db).is_err());
+    }
+
+    #[test]
+    fn test_search_invalid_status() {
+        let db = SqliteStorage::open_in_memory().unwrap();
+        let opts = SearchOptions {
+            status: Some("bogus".to_string()),
+            ..options()
+        };
+        assert!(execute_with_storage("rate", &opts, false, &db).is_err());
+    }
+
+    #[test]
+    fn test_search_with_results() {
+        let db = SqliteStorage::open_in_memory().unwrap();
+        db.save_contract(&Contract::new("Add rate limiting", "cargo test"))
+            .unwrap();
+        execute_with_storage("rate", &options(), false, &db).unwrap();
+        execute_with_storage("rate", &options(), true, &db).unwrap();
+    }
+
+    #[test]
+    fn test_search_archive() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let archive = Archive::new(tmp.path());
+        archive
+            .append(&[Contract::new("Add rate limiting", "cargo test")])
+            .unwrap();
+        execute_with_archive("rate", &options(), false, &archive).unwrap();
+        assert!(execute_with_archive("", &options(), false, &archive).is_err());
+    }
+}
@@ -1,20 +1,23 @@
 //! Show command - display contract details
 
-use crate::storage::{self, Storage};
+use crate::schema::Contract;
+use crate::storage::{Storage, StorageError};
 use anyhow::{bail, Result};
 use std::path::Path;
 
+/// Maximum sub-contract depth rendered by `show` (guards against cycles)
+const MAX_TREE_DEPTH: usize = 16;
+
 /// Execute the show command
 pub fn execute(id: &str, json_output: bool) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-    let db = storage::sqlite::open_default(&cwd)?;
-    execute_with_storage(id, json_output, &db)
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(id, json_output, &cwd)
 }
 
 /// Execute with explicit working directory (for testing)
 pub fn execute_with_cwd(id: &str, json_output: bool, cwd: &Path) -> Result<()> {
-    let db = storage::sqlite::open_default(cwd)?;
-    execute_with_storage(id, json_output, &db)
+    let (_, db) = super::open_project(cwd, None)?;
+    execute_with_storage(id, json_output, &*db)
 }
 
 /// Execute with a specific storage backend
@@ -36,6 +39,30 @@ pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage)
                     println!("Owner: {}", owner);
                 }
 
+                if c.requires_approval {
+                    println!("Requires approval: yes");
+                }
+                if let Some(ref approval) = c.approval {
+                    let decision = if approval.approved {
+                        "Approved"
+                    } else {
+                        "Rejected"
+                    };
+                    println!(
+                        "{} by: {} ({})",
+                        decision,
+                        approval.approver,
+                        approval.decided_at.format("%Y-%m-%d %H:%M:%S")
+                    );
+                    if let Some(ref reason) = approval.reason {
+                        println!("Reason: {}", reason);
+                    }
+                }
+
+                if let Some(ref parent_id) = c.parent_id {
+                    println!("Parent: {}", parent_id);
+                }
+
                 if let Some(completed) = c.completed_at {
                     println!("Completed: {}", completed.format("%Y-%m-%d %H:%M:%S"));
                 }
@@ -47,6 +74,18 @@ pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage)
                     println!("Blocks: {}", c.blocks.join(", "));
                 }
 
+                let tree = render_children(&c, storage)?;
+                if !tree.is_empty() {
+                    if c.await_children {
+                        println!("\nChildren (must complete before verification):");
+                    } else {
+                        println!("\nChildren:");
+                    }
+                    for line in tree {
+                        println!("{}", line);
+                    }
+                }
+
                 if let Some(ref output) = c.output {
                     println!("\nOutput:");
                     println!("{}", output);
@@ -66,6 +105,44 @@ pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage)
     Ok(())
 }
 
+/// Render the sub-contract tree below `contract`, one line per descendant
+fn render_children(
+    contract: &Contract,
+    storage: &dyn Storage,
+) -> Result<Vec<String>, StorageError> {
+    let mut lines = Vec::new();
+    render_level(&contract.id, "  ", 0, storage, &mut lines)?;
+    Ok(lines)
+}
+
+fn render_level(
+    parent_id: &str,
+    prefix: &str,
+    depth: usize,
+    storage: &dyn Storage,
+    lines: &mut Vec<String>,
+) -> Result<(), StorageError> {
+    if depth >= MAX_TREE_DEPTH {
+        return Ok(());
+    }
+
+    let children = storage.load_children(parent_id)?;
+    let count = children.len();
+    for (i, child) in children.iter().enumerate() {
+        let last = i + 1 == count;
+        let branch = if last { "└── " } else { "├── " };
+        lines.push(format!(
+            "{}{}{} [{}] {}",
+            prefix, branch, child.id, child.status, child.task
+        ));
+
+        let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        render_level(&child.id, &child_prefix, depth + 1, storage, lines)?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +179,38 @@ mod tests {
 
         execute_with_storage(&contract.id, true, &db).unwrap();
     }
+
+    #[test]
+    fn test_render_children_tree() {
+        let db = test_db();
+
+        let parent = Contract::new("parent", "verify");
+        db.save_contract(&parent).unwrap();
+        let c1 = parent.spawn_child("child 1", "verify");
+        db.save_contract(&c1).unwrap();
+        let grandchild = c1.spawn_child("grandchild", "verify");
+        db.save_contract(&grandchild).unwrap();
+        let c2 = parent.spawn_child("child 2", "verify");
+        db.save_contract(&c2).unwrap();
+
+        let lines = render_children(&parent, &db).unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], format!("  ├── {} [pending] child 1", c1.id));
+        assert_eq!(
+            lines[1],
+            format!("  │   └── {} [pending] grandchild", grandchild.id)
+        );
+        assert_eq!(lines[2], format!("  └── {} [pending] child 2", c2.id));
+
+        execute_with_storage(&parent.id, false, &db).unwrap();
+    }
+
+    #[test]
+    fn test_render_children_empty() {
+        let db = test_db();
+        let contract = Contract::new("task", "verify");
+        db.save_contract(&contract).unwrap();
+
+        assert!(render_children(&contract, &db).unwrap().is_empty());
+    }
 }
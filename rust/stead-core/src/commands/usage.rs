@@ -0,0 +1,642 @@
+//! Usage command - token and cost totals across AI CLI sessions
+//!
+//! Totals come from session summaries (see `usf::index`). Sessions whose
+//! CLI records the time of each request are split by model and hour, so
+//! `--since` and `--by day` count tokens when they were used; other
+//! sessions count towards the day they were last active. Cost is what the
+//! CLI reported (spread over a split session by token count), else
+//! estimated from `usf::pricing`.
+
+use super::export::csv_field;
+use super::session::{format_tokens, load_pricing};
+use crate::usf::{
+    adapters::AdapterRegistry,
+    pricing::{Cost, Pricing},
+    CliType, SessionSummary, TokenUsage,
+};
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, TimeZone, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
+
+/// What `stead usage` groups sessions by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageGroup {
+    /// Local date the tokens were used
+    Day,
+    /// Project path
+    #[default]
+    Project,
+    /// CLI, or external adapter name
+    Cli,
+    /// Model that used the tokens
+    Model,
+}
+
+impl std::str::FromStr for UsageGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" => Ok(UsageGroup::Day),
+            "project" => Ok(UsageGroup::Project),
+            "cli" => Ok(UsageGroup::Cli),
+            "model" => Ok(UsageGroup::Model),
+            _ => Err(format!(
+                "Invalid grouping '{}'. Valid values: day, project, cli, model",
+                s
+            )),
+        }
+    }
+}
+
+/// Output format for `stead usage`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UsageFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+impl std::str::FromStr for UsageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(UsageFormat::Table),
+            "json" => Ok(UsageFormat::Json),
+            "csv" => Ok(UsageFormat::Csv),
+            _ => Err(format!(
+                "Invalid usage format '{}'. Valid values: table, json, csv",
+                s
+            )),
+        }
+    }
+}
+
+/// Options for the usage command
+#[derive(Debug, Clone, Default)]
+pub struct UsageOptions {
+    pub group: UsageGroup,
+    /// Only sessions active since this age (`7d`) or local date (`2026-01-31`)
+    pub since: Option<String>,
+    /// Only this CLI or external adapter
+    pub cli: Option<String>,
+    /// Only projects whose path contains this (case-insensitive)
+    pub project: Option<String>,
+    pub format: UsageFormat,
+    /// Re-parse every session file instead of trusting the session index
+    pub refresh: bool,
+}
+
+/// Totals for one group
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageRow {
+    pub key: String,
+    pub sessions: usize,
+    pub tokens: TokenUsage,
+    /// USD, reported plus estimated
+    pub cost: f64,
+    /// Some of `cost` is estimated
+    pub estimated: bool,
+    /// Sessions with tokens but no reported cost or known price
+    pub unpriced: usize,
+}
+
+/// A row being added up; a session split over several shares counts once
+#[derive(Default)]
+struct RowTotals<'a> {
+    row: UsageRow,
+    sessions: HashSet<&'a str>,
+    unpriced: HashSet<&'a str>,
+}
+
+impl<'a> RowTotals<'a> {
+    fn add(&mut self, share: &Share<'a>) {
+        let id = share.session.id.as_str();
+        self.sessions.insert(id);
+        if let Some(tokens) = share.tokens {
+            self.row.tokens += tokens;
+        }
+        match share.cost {
+            Some(cost) => {
+                self.row.cost += cost.usd;
+                self.row.estimated |= cost.estimated;
+            }
+            None if share.tokens.is_some() => {
+                self.unpriced.insert(id);
+            }
+            None => {}
+        }
+    }
+
+    fn finish(self) -> UsageRow {
+        UsageRow {
+            sessions: self.sessions.len(),
+            unpriced: self.unpriced.len(),
+            ..self.row
+        }
+    }
+}
+
+/// Part of a session's usage: all of it, or what one model used in one hour
+struct Share<'a> {
+    session: &'a SessionSummary,
+    /// When the tokens were used: the start of the hour, or the session's
+    /// last activity
+    at: DateTime<Utc>,
+    /// Latest time the tokens may have been used, for `--since`
+    until: DateTime<Utc>,
+    model: Option<&'a str>,
+    tokens: Option<TokenUsage>,
+    cost: Option<Cost>,
+}
+
+/// Split a session by model and hour when every part of its usage has a
+/// time, else keep it whole
+fn shares<'a>(session: &'a SessionSummary, pricing: &Pricing) -> Vec<Share<'a>> {
+    let total: u64 = session.model_usage.iter().map(|u| u.tokens.total()).sum();
+    let hourly = session.model_usage.iter().all(|u| u.hour.is_some());
+    if session.model_usage.is_empty() || !hourly || total == 0 {
+        return vec![Share {
+            session,
+            at: session.last_modified,
+            until: session.last_modified,
+            model: session.model.as_deref(),
+            tokens: session.tokens,
+            cost: pricing.summary_cost(session),
+        }];
+    }
+
+    session
+        .model_usage
+        .iter()
+        .filter_map(|usage| {
+            let hour = usage.hour?;
+            let cost = match session.cost {
+                Some(usd) => Some(Cost {
+                    usd: usd * usage.tokens.total() as f64 / total as f64,
+                    estimated: false,
+                }),
+                None => pricing
+                    .estimate_by_model(std::slice::from_ref(usage))
+                    .map(|usd| Cost {
+                        usd,
+                        estimated: true,
+                    }),
+            };
+            Some(Share {
+                session,
+                at: hour,
+                until: hour + TimeDelta::hours(1),
+                model: Some(usage.model.as_str()),
+                tokens: Some(usage.tokens),
+                cost,
+            })
+        })
+        .collect()
+}
+
+/// A usage report: one row per group, plus the total
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageReport {
+    pub group: UsageGroup,
+    pub rows: Vec<UsageRow>,
+    pub total: UsageRow,
+}
+
+/// Execute the usage command, writing to stdout
+pub fn execute(options: &UsageOptions, json_output: bool) -> Result<()> {
+    let since = options.since.as_deref().map(parse_since).transpose()?;
+
+    let mut registry = AdapterRegistry::builder();
+    if let Some(cli) = &options.cli {
+        registry = registry.only(cli);
+    }
+    let discovery = registry.build().discover(options.refresh);
+    for failure in &discovery.errors {
+        eprintln!("Warning: {}", failure);
+    }
+
+    let project = options.project.as_deref().map(str::to_lowercase);
+    let sessions: Vec<SessionSummary> = discovery
+        .sessions
+        .into_iter()
+        .filter(|s| {
+            project
+                .as_deref()
+                .is_none_or(|p| s.project_path.to_lowercase().contains(p))
+        })
+        .collect();
+
+    let report = aggregate(&sessions, options.group, since, &load_pricing());
+    let format = if json_output {
+        UsageFormat::Json
+    } else {
+        options.format
+    };
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    match format {
+        UsageFormat::Table => write_table(&report, &mut out)?,
+        UsageFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?,
+        UsageFormat::Csv => write_csv(&report, &mut out)?,
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Total usage per group, counting only usage since `since`. Days are
+/// listed in order; other groups most expensive first.
+pub fn aggregate(
+    sessions: &[SessionSummary],
+    group: UsageGroup,
+    since: Option<DateTime<Utc>>,
+    pricing: &Pricing,
+) -> UsageReport {
+    let row = |key: String| RowTotals {
+        row: UsageRow {
+            key,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut groups: BTreeMap<String, RowTotals> = BTreeMap::new();
+    let mut total = row("total".to_string());
+    for session in sessions {
+        for share in shares(session, pricing) {
+            if since.is_some_and(|since| share.until < since) {
+                continue;
+            }
+            let key = group_key(&share, group);
+            groups
+                .entry(key.clone())
+                .or_insert_with(|| row(key))
+                .add(&share);
+            total.add(&share);
+        }
+    }
+
+    let total = total.finish();
+    let mut rows: Vec<UsageRow> = groups.into_values().map(RowTotals::finish).collect();
+    if group != UsageGroup::Day {
+        rows.sort_by(|a, b| {
+            b.cost
+                .total_cmp(&a.cost)
+                .then(b.tokens.total().cmp(&a.tokens.total()))
+        });
+    }
+    UsageReport { group, rows, total }
+}
+
+fn group_key(share: &Share, group: UsageGroup) -> String {
+    let session = share.session;
+    match group {
+        UsageGroup::Day => share
+            .at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d")
+            .to_string(),
+        UsageGroup::Project => session.project_path.clone(),
+        // External adapters are told apart by their ID prefix
+        UsageGroup::Cli if session.cli == CliType::Universal => session
+            .id
+            .split_once('-')
+            .map_or("universal", |(name, _)| name)
+            .to_string(),
+        UsageGroup::Cli => session.cli.as_str().to_string(),
+        UsageGroup::Model => share.model.unwrap_or("unknown").to_string(),
+    }
+}
+
+/// Parse `--since`: an age such as `7d`, or a local date such as `2026-01-31`
+fn parse_since(spec: &str) -> Result<DateTime<Utc>> {
+    if let Ok(date) = NaiveDate::parse_from_str(spec.trim(), "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).expect("midnight is valid");
+        if let Some(start) = Local.from_local_datetime(&midnight).earliest() {
+            return Ok(start.with_timezone(&Utc));
+        }
+    }
+    let age = super::archive::parse_age(spec).map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid --since '{}'. Expected an age (e.g. 12h, 7d, 2w) or a date (YYYY-MM-DD)",
+            spec
+        )
+    })?;
+    Ok(Utc::now() - age)
+}
+
+fn format_usd(row: &UsageRow) -> String {
+    format!("{}${:.2}", if row.estimated { "~" } else { "" }, row.cost)
+}
+
+fn write_table(report: &UsageReport, out: &mut dyn Write) -> Result<()> {
+    if report.total.sessions == 0 {
+        writeln!(out, "No sessions found.")?;
+        return Ok(());
+    }
+
+    let heading = match report.group {
+        UsageGroup::Day => "DAY",
+        UsageGroup::Project => "PROJECT",
+        UsageGroup::Cli => "CLI",
+        UsageGroup::Model => "MODEL",
+    };
+    let width = report
+        .rows
+        .iter()
+        .map(|r| r.key.chars().count())
+        .chain([heading.len(), "TOTAL".len()])
+        .max()
+        .unwrap_or(0);
+    let line = |out: &mut dyn Write, key: &str, row: &UsageRow| {
+        writeln!(
+            out,
+            "{:width$}  {:>8}  {:>8}  {:>8}  {:>10}  {:>11}  {:>10}",
+            key,
+            row.sessions,
+            format_tokens(row.tokens.input),
+            format_tokens(row.tokens.output),
+            format_tokens(row.tokens.cache_read),
+            format_tokens(row.tokens.cache_write),
+            format_usd(row),
+            width = width
+        )
+    };
+
+    writeln!(
+        out,
+        "{:width$}  {:>8}  {:>8}  {:>8}  {:>10}  {:>11}  {:>10}",
+        heading,
+        "SESSIONS",
+        "INPUT",
+        "OUTPUT",
+        "CACHE READ",
+        "CACHE WRITE",
+        "COST",
+        width = width
+    )?;
+    writeln!(out, "{}", "-".repeat(width + 67))?;
+    for row in &report.rows {
+        line(out, &row.key, row)?;
+    }
+    writeln!(out, "{}", "-".repeat(width + 67))?;
+    line(out, "TOTAL", &report.total)?;
+
+    if report.total.estimated {
+        writeln!(out, "\n~ includes estimates from model prices")?;
+    }
+    if report.total.unpriced > 0 {
+        writeln!(
+            out,
+            "{} session(s) have no price for their model; add it to ~/.config/stead/pricing.toml",
+            report.total.unpriced
+        )?;
+    }
+    Ok(())
+}
+
+const CSV_HEADER: &[&str] = &[
+    "group",
+    "sessions",
+    "input_tokens",
+    "output_tokens",
+    "cache_read_tokens",
+    "cache_write_tokens",
+    "cost_usd",
+    "estimated",
+    "unpriced_sessions",
+];
+
+fn write_csv(report: &UsageReport, out: &mut dyn Write) -> Result<()> {
+    writeln!(out, "{}", CSV_HEADER.join(","))?;
+    for row in report.rows.iter().chain([&report.total]) {
+        let fields = [
+            csv_field(&row.key),
+            row.sessions.to_string(),
+            row.tokens.input.to_string(),
+            row.tokens.output.to_string(),
+            row.tokens.cache_read.to_string(),
+            row.tokens.cache_write.to_string(),
+            format!("{:.4}", row.cost),
+            row.estimated.to_string(),
+            row.unpriced.to_string(),
+        ];
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, cli: CliType, project: &str, model: &str, input: u64) -> SessionSummary {
+        let at = Utc::now();
+        SessionSummary {
+            id: id.to_string(),
+            cli,
+            project_path: project.to_string(),
+            title: String::new(),
+            created: at,
+            last_modified: at,
+            message_count: 1,
+            git_branch: None,
+            model: Some(model.to_string()),
+            tokens: Some(TokenUsage {
+                input,
+                ..Default::default()
+            }),
+            cost: None,
+            model_usage: Vec::new(),
+            project_hash: None,
+        }
+    }
+
+    fn sessions() -> Vec<SessionSummary> {
+        let mut reported = session("opencode-1", CliType::OpenCode, "/work/app", "gpt-4o", 0);
+        reported.cost = Some(0.25);
+        vec![
+            session(
+                "claude-1",
+                CliType::Claude,
+                "/work/app",
+                "claude-sonnet-4",
+                1_000_000,
+            ),
+            session(
+                "claude-2",
+                CliType::Claude,
+                "/work/api",
+                "claude-sonnet-4",
+                2_000_000,
+            ),
+            session("acme-7", CliType::Universal, "/work/api", "acme-1", 500),
+            reported,
+        ]
+    }
+
+    #[test]
+    fn test_aggregate_by_project() {
+        let report = aggregate(&sessions(), UsageGroup::Project, None, &Pricing::builtin());
+
+        // Most expensive first
+        let keys: Vec<&str> = report.rows.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["/work/api", "/work/app"]);
+
+        let api = &report.rows[0];
+        assert_eq!(api.sessions, 2);
+        assert_eq!(api.tokens.input, 2_000_500);
+        assert_eq!(api.cost, 6.0);
+        assert!(api.estimated);
+        assert_eq!(api.unpriced, 1);
+
+        assert_eq!(report.rows[1].cost, 3.25);
+        assert_eq!(report.total.sessions, 4);
+        assert_eq!(report.total.cost, 9.25);
+    }
+
+    #[test]
+    fn test_aggregate_by_cli_and_model() {
+        let pricing = Pricing::builtin();
+
+        let report = aggregate(&sessions(), UsageGroup::Cli, None, &pricing);
+        let keys: Vec<&str> = report.rows.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["claude", "opencode", "acme"]);
+
+        let report = aggregate(&sessions(), UsageGroup::Model, None, &pricing);
+        let opencode = report.rows.iter().find(|r| r.key == "gpt-4o").unwrap();
+        assert_eq!(opencode.cost, 0.25);
+        assert!(!opencode.estimated);
+    }
+
+    #[test]
+    fn test_aggregate_by_day() {
+        let mut old = session(
+            "claude-3",
+            CliType::Claude,
+            "/work/app",
+            "claude-sonnet-4",
+            1,
+        );
+        old.last_modified = Utc::now() - chrono::Duration::days(3);
+        let mut all = sessions();
+        all.push(old);
+
+        let report = aggregate(&all, UsageGroup::Day, None, &Pricing::builtin());
+        assert_eq!(report.rows.len(), 2);
+        // Chronological
+        assert!(report.rows[0].key < report.rows[1].key);
+        assert_eq!(report.rows[0].sessions, 1);
+    }
+
+    #[test]
+    fn test_aggregate_splits_session_by_hour() {
+        // Two days apart at noon so both hours fall on different local dates
+        let today = Local::now()
+            .date_naive()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_local_timezone(Local)
+            .unwrap()
+            .with_timezone(&Utc);
+        let earlier = today - chrono::Duration::days(2);
+        let usage = |model: &str, input: u64, hour| crate::usf::ModelUsage {
+            model: model.to_string(),
+            tokens: TokenUsage {
+                input,
+                ..Default::default()
+            },
+            hour: Some(hour),
+        };
+        let mut long = session(
+            "claude-4",
+            CliType::Claude,
+            "/work/app",
+            "claude-sonnet-4",
+            3_000_000,
+        );
+        long.last_modified = today;
+        long.model_usage = vec![
+            usage("claude-sonnet-4", 1_000_000, earlier),
+            usage("claude-3-5-haiku", 2_000_000, today),
+        ];
+        let pricing = Pricing::builtin();
+
+        let report = aggregate(std::slice::from_ref(&long), UsageGroup::Day, None, &pricing);
+        assert_eq!(report.rows.len(), 2);
+        assert_eq!(report.rows[0].tokens.input, 1_000_000);
+        assert_eq!(report.rows[1].tokens.input, 2_000_000);
+        assert_eq!(report.total.sessions, 1);
+
+        let report = aggregate(
+            std::slice::from_ref(&long),
+            UsageGroup::Model,
+            None,
+            &pricing,
+        );
+        let keys: Vec<&str> = report.rows.iter().map(|r| r.key.as_str()).collect();
+        assert_eq!(keys, vec!["claude-sonnet-4", "claude-3-5-haiku"]);
+
+        // Only usage since yesterday counts
+        let since = Some(today - chrono::Duration::days(1));
+        let report = aggregate(
+            std::slice::from_ref(&long),
+            UsageGroup::Day,
+            since,
+            &pricing,
+        );
+        assert_eq!(report.rows.len(), 1);
+        assert_eq!(report.total.tokens.input, 2_000_000);
+        assert!((report.total.cost - 1.6).abs() < 1e-9);
+
+        // A reported cost is spread by tokens
+        long.cost = Some(3.0);
+        let report = aggregate(
+            std::slice::from_ref(&long),
+            UsageGroup::Day,
+            since,
+            &pricing,
+        );
+        assert!((report.total.cost - 2.0).abs() < 1e-9);
+        assert!(!report.total.estimated);
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let week = parse_since("7d").unwrap();
+        assert!(Utc::now() - week >= chrono::Duration::days(7));
+
+        let date = parse_since("2026-01-31").unwrap();
+        assert_eq!(
+            date.with_timezone(&Local).date_naive(),
+            NaiveDate::from_ymd_opt(2026, 1, 31).unwrap()
+        );
+
+        assert!(parse_since("last week").is_err());
+    }
+
+    #[test]
+    fn test_write_table_and_csv() {
+        let report = aggregate(&sessions(), UsageGroup::Project, None, &Pricing::builtin());
+
+        let mut table = Vec::new();
+        write_table(&report, &mut table).unwrap();
+        let table = String::from_utf8(table).unwrap();
+        assert!(table.starts_with("PROJECT"), "{}", table);
+        assert!(table.contains("~$9.25"), "{}", table);
+        assert!(table.contains("1 session(s) have no price"), "{}", table);
+
+        let mut csv = Vec::new();
+        write_csv(&report, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "/work/api,2,2000500,0,0,0,6.0000,true,1");
+        assert_eq!(lines[3], "total,4,3000500,0,0,0,9.2500,true,1");
+    }
+}
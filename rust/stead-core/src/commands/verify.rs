@@ -1,28 +1,34 @@
 //! Verify command - re-run verification for a contract
 
-use crate::storage::{self, Storage};
-use anyhow::{bail, Context, Result};
+use super::process::{self, HookEvent};
+use crate::config::Config;
+use crate::schema::{Contract, ContractStatus};
+use crate::storage::Storage;
+use anyhow::{bail, Result};
 use std::path::Path;
-use std::process::Command;
 
 /// Execute the verify command
 pub fn execute(id: &str, json_output: bool) -> Result<()> {
-    let cwd = std::env::current_dir()?;
-    let db = storage::sqlite::open_default(&cwd)?;
-    execute_with_storage(id, json_output, &db)
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(id, json_output, &cwd)
 }
 
 /// Execute with explicit working directory (for testing)
 pub fn execute_with_cwd(id: &str, json_output: bool, cwd: &Path) -> Result<()> {
-    let db = storage::sqlite::open_default(cwd)?;
-    execute_with_storage(id, json_output, &db)
+    let (config, db) = super::open_project(cwd, None)?;
+    execute_with_storage(id, json_output, &config, &*db)
 }
 
 /// Execute with a specific storage backend
-pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage) -> Result<()> {
+pub fn execute_with_storage(
+    id: &str,
+    json_output: bool,
+    config: &Config,
+    storage: &dyn Storage,
+) -> Result<()> {
     let contract = storage.load_contract(id)?;
 
-    let mut contract = match contract {
+    let contract = match contract {
         Some(c) => c,
         None => {
             if json_output {
@@ -39,21 +45,18 @@ pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage)
         println!("Running verification: {}", contract.verification);
     }
 
-    // Run verification
-    let (passed, output) = run_verification(&contract.verification)?;
-
-    // Update contract
-    contract.complete(passed, output);
-    storage.update_contract(&contract)?;
+    let contract = verify(contract, config, storage)?;
+    let passed = contract.status != ContractStatus::Failed;
 
     if json_output {
         println!("{}", serde_json::to_string(&contract)?);
     } else {
-        println!(
-            "Verification {}: {}",
-            if passed { "PASSED" } else { "FAILED" },
-            contract.id
-        );
+        let result = match (passed, contract.status) {
+            (true, ContractStatus::AwaitingApproval) => "PASSED (awaiting approval)",
+            (true, _) => "PASSED",
+            (false, _) => "FAILED",
+        };
+        println!("Verification {}: {}", result, contract.id);
         if let Some(ref out) = contract.output {
             if !out.is_empty() {
                 println!("\nOutput:\n{}", out);
@@ -64,42 +67,81 @@ pub fn execute_with_storage(id: &str, json_output: bool, storage: &dyn Storage)
     Ok(())
 }
 
-/// Run verification command and capture output
-fn run_verification(cmd: &str) -> Result<(bool, Option<String>)> {
-    let (shell, flag) = if cfg!(target_os = "windows") {
-        ("cmd", "/c")
-    } else {
-        ("sh", "-c")
-    };
-
-    let output = Command::new(shell)
-        .args([flag, cmd])
-        .output()
-        .context("Failed to run verification command")?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+/// Run a contract's verification, store the result and fire its hook.
+///
+/// Finished contracts and those awaiting sign-off are refused, so an
+/// earlier result or approval is never overwritten.
+pub fn verify(mut contract: Contract, config: &Config, storage: &dyn Storage) -> Result<Contract> {
+    if contract.status.is_terminal() || contract.status == ContractStatus::AwaitingApproval {
+        bail!(
+            "Contract {} is {} and cannot be verified again",
+            contract.id,
+            contract.status
+        );
+    }
+    ensure_children_complete(&contract, storage)?;
 
-    let combined = [stdout.trim(), stderr.trim()]
-        .iter()
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .collect::<Vec<_>>()
-        .join("\n");
+    contract.reverify()?;
+    storage.update_contract(&contract)?;
+    let result = process::run_verification(&contract.verification, config);
+    record_result(&mut contract, result, None, config, storage)?;
+    Ok(contract)
+}
 
-    let output_str = if combined.is_empty() {
-        None
-    } else {
-        Some(combined)
+/// Store a verification result, after `prefix` in the output, and fire the
+/// hook.
+///
+/// A command that couldn't be run fails the contract with the error as its
+/// output, so it doesn't stay Verifying, and the error is then returned.
+pub(crate) fn record_result(
+    contract: &mut Contract,
+    result: Result<(bool, Option<String>)>,
+    prefix: Option<String>,
+    config: &Config,
+    storage: &dyn Storage,
+) -> Result<()> {
+    let (passed, output, error) = match result {
+        Ok((passed, output)) => (passed, output, None),
+        Err(e) => (false, Some(format!("{:#}", e)), Some(e)),
+    };
+    let output = match (prefix, output) {
+        (Some(prefix), Some(out)) => Some(format!("{}\n{}", prefix, out)),
+        (Some(prefix), None) => Some(prefix),
+        (None, out) => out,
     };
 
-    Ok((output.status.success(), output_str))
+    contract.complete(passed, output)?;
+    storage.update_contract(contract)?;
+    if let Some(event) = HookEvent::for_status(contract.status) {
+        process::run_hook(event, contract, config);
+    }
+    error.map_or(Ok(()), Err)
+}
+
+/// Parents that await their children stay put until every child is Completed
+fn ensure_children_complete(contract: &Contract, storage: &dyn Storage) -> Result<()> {
+    let children = storage.load_children(&contract.id)?;
+    let incomplete = contract.incomplete_children(&children);
+    if !incomplete.is_empty() {
+        let pending = incomplete
+            .iter()
+            .map(|c| format!("{} ({})", c.id, c.status))
+            .collect::<Vec<_>>()
+            .join(", ");
+        bail!(
+            "Contract {} is waiting on {} incomplete child contract(s): {}",
+            contract.id,
+            incomplete.len(),
+            pending
+        );
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::schema::{Contract, ContractStatus};
+    use crate::schema::Contract;
     use crate::storage::sqlite::SqliteStorage;
 
     fn test_db() -> SqliteStorage {
@@ -113,7 +155,7 @@ mod tests {
         let contract = Contract::new("test", "echo verified");
         db.save_contract(&contract).unwrap();
 
-        execute_with_storage(&contract.id, false, &db).unwrap();
+        execute_with_storage(&contract.id, false, &Config::default(), &db).unwrap();
 
         let updated = db.load_contract(&contract.id).unwrap().unwrap();
         assert_eq!(updated.status, ContractStatus::Completed);
@@ -122,7 +164,7 @@ mod tests {
     #[test]
     fn test_verify_nonexistent() {
         let db = test_db();
-        let result = execute_with_storage("nonexistent", false, &db);
+        let result = execute_with_storage("nonexistent", false, &Config::default(), &db);
         assert!(result.is_err());
     }
 
@@ -139,9 +181,100 @@ mod tests {
         let contract = Contract::new("test", verify_cmd);
         db.save_contract(&contract).unwrap();
 
-        execute_with_storage(&contract.id, false, &db).unwrap();
+        execute_with_storage(&contract.id, false, &Config::default(), &db).unwrap();
 
         let updated = db.load_contract(&contract.id).unwrap().unwrap();
         assert_eq!(updated.status, ContractStatus::Failed);
     }
+
+    #[test]
+    fn test_verify_waits_for_children() {
+        let db = test_db();
+
+        let mut parent = Contract::new("parent", "echo ok");
+        parent.await_children = true;
+        db.save_contract(&parent).unwrap();
+
+        let mut child = parent.spawn_child("child", "echo ok");
+        db.save_contract(&child).unwrap();
+
+        // Child still pending: parent cannot be verified
+        assert!(execute_with_storage(&parent.id, false, &Config::default(), &db).is_err());
+        let unchanged = db.load_contract(&parent.id).unwrap().unwrap();
+        assert_eq!(unchanged.status, ContractStatus::Pending);
+
+        // Once the child completes, the parent verifies normally
+        child.finish_verification(true, None);
+        db.update_contract(&child).unwrap();
+        execute_with_storage(&parent.id, false, &Config::default(), &db).unwrap();
+        let updated = db.load_contract(&parent.id).unwrap().unwrap();
+        assert_eq!(updated.status, ContractStatus::Completed);
+    }
+
+    #[test]
+    fn test_verify_command_that_cannot_run() {
+        let mut config = Config::default();
+        config.verification.shell = vec!["/nonexistent/shell".to_string()];
+
+        let db = test_db();
+        let contract = Contract::new("test", "echo ok");
+        db.save_contract(&contract).unwrap();
+
+        assert!(execute_with_storage(&contract.id, false, &config, &db).is_err());
+
+        let updated = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(updated.status, ContractStatus::Failed);
+        assert!(updated
+            .output
+            .unwrap()
+            .contains("Failed to run verification command"));
+    }
+
+    #[test]
+    fn test_verify_keeps_approval() {
+        let db = test_db();
+        let config = Config::default();
+
+        let mut contract = Contract::new("release", "echo ok");
+        contract.requires_approval = true;
+        db.save_contract(&contract).unwrap();
+
+        execute_with_storage(&contract.id, false, &config, &db).unwrap();
+        // Awaiting sign-off: verifying again is refused
+        assert!(execute_with_storage(&contract.id, false, &config, &db).is_err());
+
+        crate::commands::approve::approve(&db, &config, &contract.id, "alice").unwrap();
+        assert!(execute_with_storage(&contract.id, false, &config, &db).is_err());
+
+        let loaded = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(loaded.status, ContractStatus::Completed);
+        assert_eq!(loaded.approval.unwrap().approver, "alice");
+    }
+
+    #[test]
+    fn test_verify_refuses_finished_contracts() {
+        let db = test_db();
+        let mut contract = Contract::new("test", "false");
+        contract.cancel().unwrap();
+        db.save_contract(&contract).unwrap();
+
+        assert!(execute_with_storage(&contract.id, false, &Config::default(), &db).is_err());
+        let loaded = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(loaded.status, ContractStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_verify_requires_approval() {
+        let db = test_db();
+
+        let mut contract = Contract::new("release", "echo ok");
+        contract.requires_approval = true;
+        db.save_contract(&contract).unwrap();
+
+        execute_with_storage(&contract.id, false, &Config::default(), &db).unwrap();
+
+        let updated = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(updated.status, ContractStatus::AwaitingApproval);
+        assert!(updated.completed_at.is_none());
+    }
 }
@@ -0,0 +1,135 @@
+//! Watch command - stream contract changes as they happen
+//!
+//! With `--json`, each event is one line of NDJSON (see
+//! `storage::watch::ContractEvent`), so scripts can consume the stream
+//! without polling `stead list`.
+
+use crate::schema::ContractStatus;
+use crate::storage::watch::{self, ContractEvent};
+use crate::storage::Storage;
+use anyhow::Result;
+use std::io::Write;
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::time::Duration;
+
+/// Execute the watch command
+pub fn execute(interval: Duration, json_output: bool) -> Result<()> {
+    let cwd = crate::project::resolve(None)?;
+    execute_with_cwd(interval, json_output, &cwd)
+}
+
+/// Execute with explicit project root
+pub fn execute_with_cwd(interval: Duration, json_output: bool, cwd: &Path) -> Result<()> {
+    let (_, db) = super::open_project(cwd, None)?;
+    execute_with_storage(interval, json_output, &*db)
+}
+
+/// Execute with a specific storage backend; runs until stdout closes
+pub fn execute_with_storage(
+    interval: Duration,
+    json_output: bool,
+    storage: &dyn Storage,
+) -> Result<()> {
+    if !json_output {
+        eprintln!("Watching for contract changes (Ctrl-C to stop)");
+    }
+
+    let stdout = std::io::stdout();
+    watch::watch(storage, interval, &mut |events| {
+        let mut out = stdout.lock();
+        // A closed pipe (e.g. `stead watch --json | head`) ends the watch
+        let written = write_events(events, json_output, &mut out).and_then(|()| Ok(out.flush()?));
+        match written {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(_) => ControlFlow::Break(()),
+        }
+    })?;
+    Ok(())
+}
+
+fn write_events(events: &[ContractEvent], json_output: bool, out: &mut dyn Write) -> Result<()> {
+    for event in events {
+        if json_output {
+            writeln!(out, "{}", serde_json::to_string(event)?)?;
+        } else {
+            writeln!(out, "{}", format_event(event))?;
+        }
+    }
+    Ok(())
+}
+
+/// One human-readable line per event
+fn format_event(event: &ContractEvent) -> String {
+    let now = chrono::Local::now().format("%H:%M:%S");
+    match event {
+        ContractEvent::Created { contract } => format!(
+            "{} created  {:15} {:w$} {}",
+            now,
+            contract.id,
+            contract.status,
+            super::list::truncate(&contract.task, 40),
+            w = ContractStatus::MAX_NAME_LEN
+        ),
+        ContractEvent::Updated {
+            contract,
+            previous_status,
+        } => {
+            let status = if *previous_status == contract.status {
+                contract.status.to_string()
+            } else {
+                format!("{} -> {}", previous_status, contract.status)
+            };
+            format!(
+                "{} updated  {:15} {} {}",
+                now,
+                contract.id,
+                status,
+                super::list::truncate(&contract.task, 40)
+            )
+        }
+        ContractEvent::Deleted { id } => format!("{} deleted  {}", now, id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{Contract, ContractStatus};
+
+    #[test]
+    fn test_write_events_ndjson() {
+        let contract = Contract::new("task", "true");
+        let events = vec![
+            ContractEvent::Created {
+                contract: contract.clone(),
+            },
+            ContractEvent::Deleted {
+                id: contract.id.clone(),
+            },
+        ];
+
+        let mut buf = Vec::new();
+        write_events(&events, true, &mut buf).unwrap();
+        let lines: Vec<serde_json::Value> = String::from_utf8(buf)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "created");
+        assert_eq!(lines[0]["contract"]["id"], contract.id.as_str());
+        assert_eq!(lines[1]["event"], "deleted");
+    }
+
+    #[test]
+    fn test_format_transition() {
+        let mut contract = Contract::new("task", "true");
+        contract.mark_ready().unwrap();
+        let line = format_event(&ContractEvent::Updated {
+            contract,
+            previous_status: ContractStatus::Pending,
+        });
+        assert!(line.contains("pending -> ready"));
+    }
+}
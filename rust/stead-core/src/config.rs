@@ -0,0 +1,361 @@
+//! Project configuration
+//!
+//! Loaded from `.stead/config.toml` (created by `stead init`). Every field
+//! has a default, so a missing file or a partial config is fine.
+
+use crate::schema::ContractStatus;
+use crate::storage::StorageBackend;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// Template written by `stead init`; parses to `Config::default()`
+pub const DEFAULT_CONFIG_TOML: &str = r#"# stead project configuration
+
+# Tags applied to every new contract
+tags = []
+
+[storage]
+# Contract storage: "sqlite" (.stead/stead.db) or "jsonl"
+# (.stead/contracts.jsonl, one contract per line, suited to checking into git)
+backend = "sqlite"
+
+[executor]
+# Agent CLI invoked by `stead run`; the task is appended as the last argument
+command = "claude"
+args = ["-p"]
+# Owner recorded when stead claims a contract
+owner = "stead-cli"
+# timeout_secs = 1800
+
+[verification]
+# Shell used to run verification commands and hooks
+# shell = ["sh", "-c"]
+# timeout_secs = 300
+
+[retry]
+# Verification attempts before a contract is marked failed
+max_attempts = 1
+delay_secs = 0
+
+[artifacts]
+# Paths to exclude from artifact capture (reserved: stead doesn't store
+# artifacts yet, so this has no effect)
+ignore = [".git/", "target/", "node_modules/"]
+
+[retention]
+# Finished contracts older than this (days since they finished) are moved to
+# .stead/archive.jsonl by `stead archive` and `stead gc`
+archive_after_days = 30
+# Statuses eligible for archiving (finished states only)
+archive_statuses = ["completed", "cancelled", "rolledback"]
+# Permanently drop archived contracts this many days after they finished
+# delete_archived_after_days = 365
+
+[attention]
+# A contract claimed or executing for longer than this needs a look
+stale_claim_after_mins = 60
+# Sessions modified within this many hours are checked for open questions
+session_window_hours = 24
+
+[hooks]
+# Shell commands run on contract events. The contract is exposed via
+# STEAD_CONTRACT_ID, STEAD_CONTRACT_STATUS and STEAD_CONTRACT_TASK.
+# on_create = ""
+# on_complete = ""
+# on_fail = ""
+"#;
+
+/// Errors loading project configuration
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid config {path}: {message}")]
+    Parse { path: PathBuf, message: String },
+}
+
+/// Project-level settings from `.stead/config.toml`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Tags applied to every new contract
+    pub tags: Vec<String>,
+    pub storage: StorageConfig,
+    pub executor: ExecutorConfig,
+    pub verification: VerificationConfig,
+    pub retry: RetryConfig,
+    pub artifacts: ArtifactsConfig,
+    pub retention: RetentionConfig,
+    pub attention: AttentionConfig,
+    pub hooks: HooksConfig,
+}
+
+/// Where contracts are stored
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+}
+
+/// Agent CLI used by `stead run`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutorConfig {
+    pub command: String,
+    pub args: Vec<String>,
+    /// Owner recorded when stead claims a contract
+    pub owner: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl Default for ExecutorConfig {
+    fn default() -> Self {
+        Self {
+            command: "claude".to_string(),
+            args: vec!["-p".to_string()],
+            owner: "stead-cli".to_string(),
+            timeout_secs: None,
+        }
+    }
+}
+
+/// How verification commands are run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerificationConfig {
+    /// Program and flag the command string is passed to
+    pub shell: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl Default for VerificationConfig {
+    fn default() -> Self {
+        let shell = if cfg!(target_os = "windows") {
+            ["cmd", "/c"]
+        } else {
+            ["sh", "-c"]
+        };
+        Self {
+            shell: shell.iter().map(|s| s.to_string()).collect(),
+            timeout_secs: None,
+        }
+    }
+}
+
+/// Verification retry policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryConfig {
+    /// Total attempts (1 = no retry)
+    pub max_attempts: u32,
+    /// Pause between attempts
+    pub delay_secs: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            delay_secs: 0,
+        }
+    }
+}
+
+/// Artifact capture settings.
+///
+/// Reserved: there is no artifact store yet, so nothing reads these.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArtifactsConfig {
+    /// Path patterns excluded from artifact capture
+    pub ignore: Vec<String>,
+}
+
+impl Default for ArtifactsConfig {
+    fn default() -> Self {
+        Self {
+            ignore: vec![
+                ".git/".to_string(),
+                "target/".to_string(),
+                "node_modules/".to_string(),
+            ],
+        }
+    }
+}
+
+/// When finished contracts are archived and purged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Days after finishing before a contract is archived
+    pub archive_after_days: u64,
+    pub archive_statuses: Vec<ContractStatus>,
+    /// Days after finishing before an archived contract is deleted (None = keep)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_archived_after_days: Option<u64>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            archive_after_days: 30,
+            archive_statuses: vec![
+                ContractStatus::Completed,
+                ContractStatus::Cancelled,
+                ContractStatus::RolledBack,
+            ],
+            delete_archived_after_days: None,
+        }
+    }
+}
+
+/// What `stead attention` flags
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttentionConfig {
+    /// Minutes a contract may stay claimed or executing before it is stale
+    pub stale_claim_after_mins: u64,
+    /// Hours back to look for sessions waiting on a question
+    pub session_window_hours: u64,
+}
+
+impl Default for AttentionConfig {
+    fn default() -> Self {
+        Self {
+            stale_claim_after_mins: 60,
+            session_window_hours: 24,
+        }
+    }
+}
+
+/// Shell commands run on contract events
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_create: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_complete: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_fail: Option<String>,
+}
+
+impl Config {
+    /// Path of the config file for a project root
+    pub fn path(root: &Path) -> PathBuf {
+        root.join(crate::storage::STEAD_DIR).join(CONFIG_FILE)
+    }
+
+    /// Load the project config, falling back to defaults when absent
+    pub fn load(root: &Path) -> Result<Self, ConfigError> {
+        let path = Self::path(root);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|message| ConfigError::Parse {
+                path: path.clone(),
+                message,
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Parse config from TOML text
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| e.message().to_string())
+    }
+
+    pub fn executor_timeout(&self) -> Option<Duration> {
+        self.executor.timeout_secs.map(Duration::from_secs)
+    }
+
+    pub fn verification_timeout(&self) -> Option<Duration> {
+        self.verification.timeout_secs.map(Duration::from_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_template_matches_defaults() {
+        let config = Config::parse(DEFAULT_CONFIG_TOML).unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn test_partial_config() {
+        let config = Config::parse(
+            r#"
+            tags = ["backend"]
+
+            [executor]
+            owner = "ci"
+
+            [retry]
+            max_attempts = 3
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.tags, vec!["backend"]);
+        assert_eq!(config.executor.owner, "ci");
+        assert_eq!(config.executor.command, "claude");
+        assert_eq!(config.retry.max_attempts, 3);
+        assert_eq!(config.retry.delay_secs, 0);
+        assert_eq!(config.storage.backend, StorageBackend::Sqlite);
+    }
+
+    #[test]
+    fn test_storage_backend() {
+        let config = Config::parse("[storage]\nbackend = \"jsonl\"").unwrap();
+        assert_eq!(config.storage.backend, StorageBackend::Jsonl);
+        assert!(Config::parse("[storage]\nbackend = \"postgres\"").is_err());
+    }
+
+    #[test]
+    fn test_retention() {
+        let config = Config::parse(
+            "[retention]\narchive_after_days = 7\narchive_statuses = [\"failed\"]\ndelete_archived_after_days = 90",
+        )
+        .unwrap();
+        assert_eq!(config.retention.archive_after_days, 7);
+        assert_eq!(
+            config.retention.archive_statuses,
+            vec![ContractStatus::Failed]
+        );
+        assert_eq!(config.retention.delete_archived_after_days, Some(90));
+        assert!(Config::parse("[retention]\narchive_statuses = [\"done\"]").is_err());
+    }
+
+    #[test]
+    fn test_attention() {
+        let config = Config::parse("[attention]\nstale_claim_after_mins = 15").unwrap();
+        assert_eq!(config.attention.stale_claim_after_mins, 15);
+        assert_eq!(config.attention.session_window_hours, 24);
+    }
+
+    #[test]
+    fn test_load_missing_is_default() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(Config::load(tmp.path()).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_load_invalid() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".stead")).unwrap();
+        std::fs::write(Config::path(tmp.path()), "retry = 3").unwrap();
+
+        let err = Config::load(tmp.path()).unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { .. }));
+    }
+}
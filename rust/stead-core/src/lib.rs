@@ -5,10 +5,15 @@
 //! - JSONL-based persistent storage
 //! - CLI argument parsing
 //! - Command implementations
+//! - Attention priority shared by every frontend
 //! - Universal Session Format (USF) for cross-CLI session visibility
 
+pub mod attention;
 pub mod cli;
 pub mod commands;
+pub mod config;
+pub(crate) mod process;
+pub mod project;
 pub mod schema;
 pub mod storage;
 pub mod usf;
@@ -0,0 +1,87 @@
+//! Child processes with a timeout, shared by commands and session adapters
+
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Result of a finished (or killed) child process
+pub(crate) struct ProcessOutput {
+    pub success: bool,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ProcessOutput {
+    /// Trimmed stdout and stderr joined, None when both are empty
+    pub fn combined(&self) -> Option<String> {
+        let combined = [self.stdout.trim(), self.stderr.trim()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if combined.is_empty() {
+            None
+        } else {
+            Some(combined)
+        }
+    }
+}
+
+/// Run `cmd` to completion, killing it once `timeout` elapses
+pub(crate) fn run_with_timeout(
+    mut cmd: Command,
+    timeout: Option<Duration>,
+) -> std::io::Result<ProcessOutput> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if timeout.is_some_and(|limit| started.elapsed() >= limit) {
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    // A killed shell may leave grandchildren holding the pipes open, so
+    // only collect output from processes that exited on their own
+    let (stdout, stderr) = match status {
+        Some(_) => (
+            stdout.map(|h| h.join().unwrap_or_default()),
+            stderr.map(|h| h.join().unwrap_or_default()),
+        ),
+        None => (None, None),
+    };
+
+    Ok(ProcessOutput {
+        success: status.is_some_and(|s| s.success()),
+        timed_out: status.is_none(),
+        stdout: stdout.unwrap_or_default(),
+        stderr: stderr.unwrap_or_default(),
+    })
+}
+
+fn read_in_background<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
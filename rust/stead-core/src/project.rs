@@ -0,0 +1,113 @@
+//! Project root discovery
+//!
+//! Commands operate on the project root rather than the raw working
+//! directory, so `stead list` from `src/auth/` uses the same
+//! `.stead/stead.db` as running it from the repository root.
+//!
+//! Resolution order:
+//! 1. An explicit `--project <path>`
+//! 2. The `STEAD_PROJECT` environment variable
+//! 3. The nearest ancestor containing `.stead` or `.git`
+//! 4. The working directory itself
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the project root
+pub const PROJECT_ENV: &str = "STEAD_PROJECT";
+
+/// Walk up from `start` to the nearest directory containing `.stead` or `.git`.
+///
+/// Falls back to `start` when neither marker is found.
+pub fn find_root(start: &Path) -> PathBuf {
+    start
+        .ancestors()
+        .find(|dir| dir.join(crate::storage::STEAD_DIR).is_dir() || dir.join(".git").exists())
+        .unwrap_or(start)
+        .to_path_buf()
+}
+
+/// Resolve the project root for a process started in `start`
+pub fn resolve_from(start: &Path, explicit: Option<&Path>) -> io::Result<PathBuf> {
+    let env_override = std::env::var_os(PROJECT_ENV).filter(|v| !v.is_empty());
+    let override_path = explicit
+        .map(Path::to_path_buf)
+        .or_else(|| env_override.map(PathBuf::from));
+
+    match override_path {
+        Some(path) => {
+            let path = start.join(path);
+            if !path.is_dir() {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Project directory not found: {}", path.display()),
+                ));
+            }
+            Ok(path)
+        }
+        None => Ok(find_root(start)),
+    }
+}
+
+/// Resolve the project root from the current working directory
+pub fn resolve(explicit: Option<&Path>) -> io::Result<PathBuf> {
+    resolve_from(&std::env::current_dir()?, explicit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_root_stead_dir() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".stead")).unwrap();
+        let nested = tmp.path().join("src/auth");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_root(&nested), tmp.path());
+    }
+
+    #[test]
+    fn test_find_root_git_dir() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        let nested = tmp.path().join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_root(&nested), tmp.path());
+    }
+
+    #[test]
+    fn test_find_root_nearest_wins() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join(".git")).unwrap();
+        let sub = tmp.path().join("packages/app");
+        std::fs::create_dir_all(sub.join(".stead")).unwrap();
+        let nested = sub.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(find_root(&nested), sub);
+    }
+
+    #[test]
+    fn test_find_root_fallback() {
+        let tmp = TempDir::new().unwrap();
+        // TempDir may live under a git checkout on some machines; only
+        // assert that we never walk *below* the start directory
+        let root = find_root(tmp.path());
+        assert!(tmp.path().starts_with(&root));
+    }
+
+    #[test]
+    fn test_resolve_explicit() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir(tmp.path().join("other")).unwrap();
+
+        let root = resolve_from(tmp.path(), Some(Path::new("other"))).unwrap();
+        assert_eq!(root, tmp.path().join("other"));
+
+        assert!(resolve_from(tmp.path(), Some(Path::new("missing"))).is_err());
+    }
+}
@@ -6,7 +6,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-/// Contract execution status (10-state lifecycle)
+/// Contract execution status (11-state lifecycle)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ContractStatus {
@@ -20,6 +20,8 @@ pub enum ContractStatus {
     Executing,
     /// Running verification command
     Verifying,
+    /// Verification passed, waiting for a human to sign off
+    AwaitingApproval,
     /// Verification passed
     Completed,
     /// Verification failed
@@ -33,6 +35,52 @@ pub enum ContractStatus {
 }
 
 impl ContractStatus {
+    /// Every status, in lifecycle order
+    pub const ALL: [ContractStatus; 11] = [
+        ContractStatus::Pending,
+        ContractStatus::Ready,
+        ContractStatus::Claimed,
+        ContractStatus::Executing,
+        ContractStatus::Verifying,
+        ContractStatus::AwaitingApproval,
+        ContractStatus::Completed,
+        ContractStatus::Failed,
+        ContractStatus::RollingBack,
+        ContractStatus::RolledBack,
+        ContractStatus::Cancelled,
+    ];
+
+    /// Length of the longest status name, for sizing table columns
+    pub const MAX_NAME_LEN: usize = {
+        let mut max = 0;
+        let mut i = 0;
+        while i < Self::ALL.len() {
+            let len = Self::ALL[i].as_str().len();
+            if len > max {
+                max = len;
+            }
+            i += 1;
+        }
+        max
+    };
+
+    /// Name as stored and displayed
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ContractStatus::Pending => "pending",
+            ContractStatus::Ready => "ready",
+            ContractStatus::Claimed => "claimed",
+            ContractStatus::Executing => "executing",
+            ContractStatus::Verifying => "verifying",
+            ContractStatus::AwaitingApproval => "awaitingapproval",
+            ContractStatus::Completed => "completed",
+            ContractStatus::Failed => "failed",
+            ContractStatus::RollingBack => "rollingback",
+            ContractStatus::RolledBack => "rolledback",
+            ContractStatus::Cancelled => "cancelled",
+        }
+    }
+
     /// Valid next states from the current state
     pub fn valid_transitions(&self) -> &[ContractStatus] {
         use ContractStatus::*;
@@ -41,9 +89,10 @@ impl ContractStatus {
             Ready => &[Claimed, Cancelled],
             Claimed => &[Executing, Ready, Cancelled], // unclaim goes back to Ready
             Executing => &[Verifying, Failed, Cancelled],
-            Verifying => &[Completed, Failed],
-            Completed => &[],                           // terminal
-            Failed => &[Ready, RollingBack, Cancelled], // retry or rollback
+            Verifying => &[Completed, Failed, AwaitingApproval],
+            AwaitingApproval => &[Completed, Failed, Cancelled], // approve or reject
+            Completed => &[],                                    // terminal
+            Failed => &[Ready, RollingBack, Cancelled],          // retry or rollback
             RollingBack => &[RolledBack, Failed],
             RolledBack => &[], // terminal
             Cancelled => &[],  // terminal
@@ -62,19 +111,9 @@ impl ContractStatus {
 }
 
 impl std::fmt::Display for ContractStatus {
+    /// Honours width and alignment, so statuses line up in tables
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ContractStatus::Pending => write!(f, "pending"),
-            ContractStatus::Ready => write!(f, "ready"),
-            ContractStatus::Claimed => write!(f, "claimed"),
-            ContractStatus::Executing => write!(f, "executing"),
-            ContractStatus::Verifying => write!(f, "verifying"),
-            ContractStatus::Completed => write!(f, "completed"),
-            ContractStatus::Failed => write!(f, "failed"),
-            ContractStatus::RollingBack => write!(f, "rollingback"),
-            ContractStatus::RolledBack => write!(f, "rolledback"),
-            ContractStatus::Cancelled => write!(f, "cancelled"),
-        }
+        f.pad(self.as_str())
     }
 }
 
@@ -88,6 +127,7 @@ impl std::str::FromStr for ContractStatus {
             "claimed" => Ok(Self::Claimed),
             "executing" | "running" => Ok(Self::Executing),
             "verifying" => Ok(Self::Verifying),
+            "awaitingapproval" | "awaiting_approval" => Ok(Self::AwaitingApproval),
             "completed" | "passed" => Ok(Self::Completed),
             "failed" => Ok(Self::Failed),
             "rollingback" => Ok(Self::RollingBack),
@@ -106,8 +146,25 @@ pub struct TransitionError {
     pub to: ContractStatus,
 }
 
+/// A human sign-off decision on a contract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Approval {
+    /// Who made the decision
+    pub approver: String,
+
+    /// Whether the contract was approved (false = rejected)
+    pub approved: bool,
+
+    /// Reason given with the decision (required for rejections)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// When the decision was recorded
+    pub decided_at: DateTime<Utc>,
+}
+
 /// A contract for agent task execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Contract {
     /// Unique identifier (timestamp-random in base36)
     pub id: String,
@@ -136,6 +193,10 @@ pub struct Contract {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub owner: Option<String>,
 
+    /// When the current owner claimed it (None when unclaimed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_at: Option<DateTime<Utc>>,
+
     /// Contract IDs that must complete before this one can start
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub blocked_by: Vec<String>,
@@ -143,6 +204,26 @@ pub struct Contract {
     /// Contract IDs that are waiting on this one
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub blocks: Vec<String>,
+
+    /// Parent contract ID (set when spawned as a sub-contract)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+
+    /// Only verifiable once every child contract is Completed
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub await_children: bool,
+
+    /// Passing verification leaves the contract AwaitingApproval until a human signs off
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub requires_approval: bool,
+
+    /// Most recent approval decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<Approval>,
+
+    /// Free-form labels (project defaults come from `.stead/config.toml`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
 }
 
 impl Contract {
@@ -157,9 +238,37 @@ impl Contract {
             completed_at: None,
             output: None,
             owner: None,
+            claimed_at: None,
             blocked_by: Vec::new(),
             blocks: Vec::new(),
+            parent_id: None,
+            await_children: false,
+            requires_approval: false,
+            approval: None,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Create a sub-contract of this contract
+    pub fn spawn_child(&self, task: impl Into<String>, verification: impl Into<String>) -> Self {
+        let mut child = Self::new(task, verification);
+        child.parent_id = Some(self.id.clone());
+        child
+    }
+
+    /// Children that still block verification of this contract.
+    ///
+    /// Empty unless `await_children` is set; otherwise every child that
+    /// is not Completed.
+    pub fn incomplete_children<'a>(&self, children: &'a [Contract]) -> Vec<&'a Contract> {
+        if !self.await_children {
+            return Vec::new();
         }
+        children
+            .iter()
+            .filter(|c| c.parent_id.as_deref() == Some(self.id.as_str()))
+            .filter(|c| c.status != ContractStatus::Completed)
+            .collect()
     }
 
     /// Transition to a new status, enforcing valid transitions
@@ -186,6 +295,7 @@ impl Contract {
     pub fn claim(&mut self, owner: impl Into<String>) -> Result<(), TransitionError> {
         self.transition_to(ContractStatus::Claimed)?;
         self.owner = Some(owner.into());
+        self.claimed_at = Some(Utc::now());
         Ok(())
     }
 
@@ -193,6 +303,22 @@ impl Contract {
     pub fn unclaim(&mut self) -> Result<(), TransitionError> {
         self.transition_to(ContractStatus::Ready)?;
         self.owner = None;
+        self.claimed_at = None;
+        Ok(())
+    }
+
+    /// Re-queue a failed contract (→ Ready), releasing its owner
+    pub fn retry(&mut self) -> Result<(), TransitionError> {
+        if self.status != ContractStatus::Failed {
+            return Err(TransitionError {
+                from: self.status,
+                to: ContractStatus::Ready,
+            });
+        }
+        self.transition_to(ContractStatus::Ready)?;
+        self.owner = None;
+        self.claimed_at = None;
+        self.completed_at = None;
         Ok(())
     }
 
@@ -206,15 +332,81 @@ impl Contract {
         self.transition_to(ContractStatus::Verifying)
     }
 
-    /// Complete the contract with verification result
-    pub fn complete(&mut self, passed: bool, output: Option<String>) {
-        self.status = if passed {
-            ContractStatus::Completed
-        } else {
-            ContractStatus::Failed
+    /// Re-enter verification outside the run flow (`stead verify`).
+    ///
+    /// Allowed from any state that is neither terminal nor awaiting
+    /// sign-off, so a finished or decided contract is never re-judged.
+    pub fn reverify(&mut self) -> Result<(), TransitionError> {
+        if self.status.is_terminal() || self.status == ContractStatus::AwaitingApproval {
+            return Err(TransitionError {
+                from: self.status,
+                to: ContractStatus::Verifying,
+            });
+        }
+        self.status = ContractStatus::Verifying;
+        self.completed_at = None;
+        Ok(())
+    }
+
+    /// Complete the contract with verification result.
+    ///
+    /// A passing contract that requires approval moves to AwaitingApproval
+    /// instead of Completed; any earlier decision is cleared.
+    pub fn complete(
+        &mut self,
+        passed: bool,
+        output: Option<String>,
+    ) -> Result<(), TransitionError> {
+        let target = match (passed, self.requires_approval) {
+            (true, true) => ContractStatus::AwaitingApproval,
+            (true, false) => ContractStatus::Completed,
+            (false, _) => ContractStatus::Failed,
         };
-        self.completed_at = Some(Utc::now());
+        self.transition_to(target)?;
         self.output = output;
+        if target == ContractStatus::AwaitingApproval {
+            self.completed_at = None;
+            self.approval = None;
+        }
+        Ok(())
+    }
+
+    /// Sign off on a contract awaiting approval (→ Completed)
+    pub fn approve(&mut self, approver: impl Into<String>) -> Result<(), TransitionError> {
+        self.decide(ContractStatus::Completed, approver.into(), None)
+    }
+
+    /// Reject a contract awaiting approval (→ Failed)
+    pub fn reject(
+        &mut self,
+        approver: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Result<(), TransitionError> {
+        self.decide(ContractStatus::Failed, approver.into(), Some(reason.into()))
+    }
+
+    fn decide(
+        &mut self,
+        target: ContractStatus,
+        approver: String,
+        reason: Option<String>,
+    ) -> Result<(), TransitionError> {
+        if self.status != ContractStatus::AwaitingApproval {
+            return Err(TransitionError {
+                from: self.status,
+                to: target,
+            });
+        }
+        self.transition_to(target)?;
+        let now = Utc::now();
+        self.completed_at = Some(now);
+        self.approval = Some(Approval {
+            approver,
+            approved: target == ContractStatus::Completed,
+            reason,
+            decided_at: now,
+        });
+        Ok(())
     }
 
     /// Cancel the contract
@@ -223,6 +415,19 @@ impl Contract {
     }
 }
 
+#[cfg(test)]
+impl Contract {
+    /// Take a fixture through verification to the given result
+    pub(crate) fn finish_verification(&mut self, passed: bool, output: Option<String>) {
+        self.reverify().expect("fixture can be verified");
+        self.complete(passed, output).expect("verifying -> result");
+    }
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
 /// Generate a unique contract ID (base36 timestamp + random)
 pub fn generate_id() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -291,6 +496,126 @@ mod tests {
         assert!(contract.owner.is_none());
         assert!(contract.blocked_by.is_empty());
         assert!(contract.blocks.is_empty());
+        assert!(contract.parent_id.is_none());
+        assert!(!contract.await_children);
+    }
+
+    #[test]
+    fn test_spawn_child() {
+        let parent = Contract::new("big task", "cargo test");
+        let child = parent.spawn_child("small task", "cargo test --lib");
+
+        assert_ne!(child.id, parent.id);
+        assert_eq!(child.parent_id, Some(parent.id.clone()));
+        assert_eq!(child.status, ContractStatus::Pending);
+    }
+
+    #[test]
+    fn test_incomplete_children() {
+        let mut parent = Contract::new("big task", "verify");
+        let mut done = parent.spawn_child("child 1", "verify");
+        done.finish_verification(true, None);
+        let open = parent.spawn_child("child 2", "verify");
+        let unrelated = Contract::new("other", "verify");
+        let children = vec![done, open.clone(), unrelated];
+
+        // Without the gate, children never block
+        assert!(parent.incomplete_children(&children).is_empty());
+
+        parent.await_children = true;
+        let incomplete = parent.incomplete_children(&children);
+        assert_eq!(incomplete.len(), 1);
+        assert_eq!(incomplete[0].id, open.id);
+    }
+
+    #[test]
+    fn test_approval_gate() {
+        let mut contract = Contract::new("migrate schema", "verify");
+        contract.requires_approval = true;
+        contract.mark_ready().unwrap();
+        contract.claim("agent").unwrap();
+        contract.start().unwrap();
+        contract.begin_verify().unwrap();
+
+        // Passing verification waits for a human
+        contract.complete(true, Some("ok".to_string())).unwrap();
+        assert_eq!(contract.status, ContractStatus::AwaitingApproval);
+        assert!(contract.completed_at.is_none());
+        assert!(!contract.status.is_terminal());
+
+        contract.approve("alice").unwrap();
+        assert_eq!(contract.status, ContractStatus::Completed);
+        assert!(contract.completed_at.is_some());
+        let approval = contract.approval.unwrap();
+        assert_eq!(approval.approver, "alice");
+        assert!(approval.approved);
+        assert!(approval.reason.is_none());
+    }
+
+    #[test]
+    fn test_reject() {
+        let mut contract = Contract::new("release", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(true, None);
+
+        contract.reject("bob", "changelog missing").unwrap();
+        assert_eq!(contract.status, ContractStatus::Failed);
+        let approval = contract.approval.clone().unwrap();
+        assert_eq!(approval.approver, "bob");
+        assert!(!approval.approved);
+        assert_eq!(approval.reason, Some("changelog missing".to_string()));
+
+        // Rejected contracts can be retried
+        contract.mark_ready().unwrap();
+    }
+
+    #[test]
+    fn test_failed_verification_skips_approval() {
+        let mut contract = Contract::new("task", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(false, None);
+        assert_eq!(contract.status, ContractStatus::Failed);
+        assert!(contract.approve("alice").is_err());
+    }
+
+    #[test]
+    fn test_complete_requires_verifying() {
+        let mut contract = Contract::new("task", "verify");
+        assert!(contract.complete(true, None).is_err());
+        assert_eq!(contract.status, ContractStatus::Pending);
+        assert!(contract.completed_at.is_none());
+    }
+
+    #[test]
+    fn test_reverify_keeps_decisions() {
+        let mut contract = Contract::new("task", "verify");
+        contract.requires_approval = true;
+        contract.finish_verification(true, None);
+
+        // Awaiting sign-off: verifying again would hide the pending decision
+        assert!(contract.reverify().is_err());
+        contract.approve("alice").unwrap();
+
+        // Completed is terminal, so the approval stays put
+        assert!(contract.reverify().is_err());
+        assert_eq!(contract.status, ContractStatus::Completed);
+        assert!(contract.approval.is_some());
+
+        // A failed contract can be verified again
+        let mut failed = Contract::new("task", "verify");
+        failed.finish_verification(false, None);
+        failed.reverify().unwrap();
+        assert_eq!(failed.status, ContractStatus::Verifying);
+        assert!(failed.completed_at.is_none());
+    }
+
+    #[test]
+    fn test_approve_requires_awaiting_state() {
+        let mut contract = Contract::new("task", "verify");
+        assert!(contract.approve("alice").is_err());
+        assert!(contract.reject("alice", "no").is_err());
+        assert_eq!(contract.status, ContractStatus::Pending);
+        assert!(contract.approval.is_none());
     }
 
     #[test]
@@ -315,7 +640,9 @@ mod tests {
         assert_eq!(contract.status, ContractStatus::Verifying);
 
         // Verifying -> Completed
-        contract.complete(true, Some("All tests passed".to_string()));
+        contract
+            .complete(true, Some("All tests passed".to_string()))
+            .unwrap();
         assert_eq!(contract.status, ContractStatus::Completed);
         assert!(contract.completed_at.is_some());
         assert_eq!(contract.output, Some("All tests passed".to_string()));
@@ -328,7 +655,9 @@ mod tests {
         contract.claim("agent-1").unwrap();
         contract.start().unwrap();
         contract.begin_verify().unwrap();
-        contract.complete(false, Some("Test failed".to_string()));
+        contract
+            .complete(false, Some("Test failed".to_string()))
+            .unwrap();
 
         assert_eq!(contract.status, ContractStatus::Failed);
     }
@@ -338,10 +667,12 @@ mod tests {
         let mut contract = Contract::new("task", "verify");
         contract.mark_ready().unwrap();
         contract.claim("agent-1").unwrap();
+        assert!(contract.claimed_at.is_some());
         contract.unclaim().unwrap();
 
         assert_eq!(contract.status, ContractStatus::Ready);
         assert!(contract.owner.is_none());
+        assert!(contract.claimed_at.is_none());
     }
 
     #[test]
@@ -368,7 +699,7 @@ mod tests {
         contract.claim("agent").unwrap();
         contract.start().unwrap();
         contract.begin_verify().unwrap();
-        contract.complete(true, None);
+        contract.complete(true, None).unwrap();
 
         // Completed is terminal — can't go anywhere
         assert!(contract.status.is_terminal());
@@ -383,13 +714,29 @@ mod tests {
         contract.claim("agent").unwrap();
         contract.start().unwrap();
         contract.begin_verify().unwrap();
-        contract.complete(false, Some("oops".to_string()));
+        contract.complete(false, Some("oops".to_string())).unwrap();
 
         // Failed -> Ready (retry)
         contract.mark_ready().unwrap();
         assert_eq!(contract.status, ContractStatus::Ready);
     }
 
+    #[test]
+    fn test_retry_releases_owner() {
+        let mut contract = Contract::new("task", "verify");
+        assert!(contract.retry().is_err());
+
+        contract.mark_ready().unwrap();
+        contract.claim("agent").unwrap();
+        contract.start().unwrap();
+        contract.complete(false, None).unwrap();
+        contract.retry().unwrap();
+        assert_eq!(contract.status, ContractStatus::Ready);
+        assert_eq!(contract.owner, None);
+        assert_eq!(contract.claimed_at, None);
+        assert_eq!(contract.completed_at, None);
+    }
+
     #[test]
     fn test_valid_transitions() {
         assert!(ContractStatus::Pending.can_transition_to(ContractStatus::Ready));
@@ -398,6 +745,22 @@ mod tests {
         assert!(!ContractStatus::Completed.can_transition_to(ContractStatus::Failed));
     }
 
+    #[test]
+    fn test_status_display_pads() {
+        assert_eq!(ContractStatus::MAX_NAME_LEN, "awaitingapproval".len());
+        assert_eq!(
+            format!(
+                "{:w$}|",
+                ContractStatus::Failed,
+                w = ContractStatus::MAX_NAME_LEN
+            ),
+            "failed          |"
+        );
+        for status in ContractStatus::ALL {
+            assert_eq!(status.to_string().parse::<ContractStatus>(), Ok(status));
+        }
+    }
+
     #[test]
     fn test_status_serialization() {
         let status = ContractStatus::Completed;
@@ -415,6 +778,7 @@ mod tests {
             (ContractStatus::Claimed, "\"claimed\""),
             (ContractStatus::Executing, "\"executing\""),
             (ContractStatus::Verifying, "\"verifying\""),
+            (ContractStatus::AwaitingApproval, "\"awaitingapproval\""),
             (ContractStatus::RollingBack, "\"rollingback\""),
             (ContractStatus::RolledBack, "\"rolledback\""),
             (ContractStatus::Cancelled, "\"cancelled\""),
@@ -441,6 +805,10 @@ mod tests {
         assert!(!json.contains("owner"));
         assert!(!json.contains("blocked_by"));
         assert!(!json.contains("blocks"));
+        assert!(!json.contains("parent_id"));
+        assert!(!json.contains("await_children"));
+        assert!(!json.contains("requires_approval"));
+        assert!(!json.contains("approval"));
     }
 
     #[test]
@@ -482,6 +850,8 @@ mod tests {
         assert_eq!(contract.status, ContractStatus::Pending);
         assert!(contract.owner.is_none());
         assert!(contract.blocked_by.is_empty());
+        assert!(contract.parent_id.is_none());
+        assert!(!contract.await_children);
     }
 
     #[test]
@@ -509,6 +879,10 @@ mod tests {
         assert_eq!(ContractStatus::Claimed.to_string(), "claimed");
         assert_eq!(ContractStatus::Executing.to_string(), "executing");
         assert_eq!(ContractStatus::Verifying.to_string(), "verifying");
+        assert_eq!(
+            ContractStatus::AwaitingApproval.to_string(),
+            "awaitingapproval"
+        );
         assert_eq!(ContractStatus::Completed.to_string(), "completed");
         assert_eq!(ContractStatus::Failed.to_string(), "failed");
         assert_eq!(ContractStatus::RollingBack.to_string(), "rollingback");
@@ -0,0 +1,183 @@
+//! Contract archive
+//!
+//! Finished contracts moved out of live storage by `stead archive` are kept
+//! in `.stead/archive.jsonl`, one contract per line, whichever backend the
+//! project uses. Live queries never read it; `stead search --archived` does.
+
+use crate::schema::Contract;
+use crate::storage::search::{search_in_memory, SearchHit, SearchQuery};
+use crate::storage::{ensure_stead_dir, StorageError};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const ARCHIVE_FILE: &str = "archive.jsonl";
+
+/// Append-only store of archived contracts
+pub struct Archive {
+    cwd: PathBuf,
+}
+
+impl Archive {
+    pub fn new(cwd: &Path) -> Self {
+        Self {
+            cwd: cwd.to_path_buf(),
+        }
+    }
+
+    /// Path of the archive file for a project root
+    pub fn path(cwd: &Path) -> PathBuf {
+        cwd.join(super::STEAD_DIR).join(ARCHIVE_FILE)
+    }
+
+    /// Temp file `prune` writes before renaming it over the archive
+    pub(crate) fn temp_path(cwd: &Path) -> PathBuf {
+        Self::path(cwd).with_extension("jsonl.tmp")
+    }
+
+    /// Append contracts and sync the file, so they are durable before the
+    /// caller deletes them from live storage
+    pub fn append(&self, contracts: &[Contract]) -> Result<(), StorageError> {
+        ensure_stead_dir(&self.cwd)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::path(&self.cwd))?;
+        for contract in contracts {
+            writeln!(file, "{}", to_json(contract)?)?;
+        }
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Every archived contract, newest first.
+    ///
+    /// A contract archived twice (an archive interrupted after the append)
+    /// appears once, using its latest line.
+    pub fn load_all(&self) -> Result<Vec<Contract>, StorageError> {
+        let path = Self::path(&self.cwd);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut seen = HashSet::new();
+        let mut contracts = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+        for (i, line) in lines.iter().enumerate().rev() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let contract: Contract =
+                serde_json::from_str(line).map_err(|e| StorageError::Json {
+                    line: i + 1,
+                    message: e.to_string(),
+                })?;
+            if seen.insert(contract.id.clone()) {
+                contracts.push(contract);
+            }
+        }
+
+        contracts.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+        Ok(contracts)
+    }
+
+    /// Full-text search over archived contracts (term scan)
+    pub fn search(&self, query: &SearchQuery) -> Result<Vec<SearchHit>, StorageError> {
+        Ok(search_in_memory(self.load_all()?, query))
+    }
+
+    /// Drop archived contracts that finished before `cutoff`; returns how
+    /// many were removed
+    pub fn prune(&self, cutoff: DateTime<Utc>) -> Result<usize, StorageError> {
+        let contracts = self.load_all()?;
+        let before = contracts.len();
+        let kept: Vec<Contract> = contracts
+            .into_iter()
+            .filter(|c| finished_at(c) >= cutoff)
+            .collect();
+        let removed = before - kept.len();
+        if removed == 0 {
+            return Ok(0);
+        }
+
+        let path = Self::path(&self.cwd);
+        let tmp_path = Self::temp_path(&self.cwd);
+        let mut file = fs::File::create(&tmp_path)?;
+        for contract in kept.iter().rev() {
+            writeln!(file, "{}", to_json(contract)?)?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(removed)
+    }
+}
+
+/// When a contract stopped changing: `completed_at`, else `created_at`
+pub fn finished_at(contract: &Contract) -> DateTime<Utc> {
+    contract.completed_at.unwrap_or(contract.created_at)
+}
+
+/// A contract that completed `days_ago` days ago, for retention tests
+#[cfg(test)]
+pub(crate) fn finished(task: &str, days_ago: i64) -> Contract {
+    let mut c = Contract::new(task, "true");
+    c.created_at = Utc::now() - chrono::Duration::days(days_ago + 1);
+    c.finish_verification(true, None);
+    c.completed_at = Some(Utc::now() - chrono::Duration::days(days_ago));
+    c
+}
+
+fn to_json(contract: &Contract) -> Result<String, StorageError> {
+    serde_json::to_string(contract).map_err(|e| StorageError::Json {
+        line: 0,
+        message: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_append_load_and_search() {
+        let tmp = TempDir::new().unwrap();
+        let archive = Archive::new(tmp.path());
+        assert!(archive.load_all().unwrap().is_empty());
+
+        let old = finished("rotate the logs", 40);
+        let new = finished("fix the build", 35);
+        archive.append(&[old.clone(), new.clone()]).unwrap();
+        // Re-archiving after an interrupted run must not duplicate
+        archive.append(std::slice::from_ref(&old)).unwrap();
+
+        let all = archive.load_all().unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, new.id);
+
+        let hits = archive.search(&SearchQuery::new("logs")).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].contract.id, old.id);
+    }
+
+    #[test]
+    fn test_prune() {
+        let tmp = TempDir::new().unwrap();
+        let archive = Archive::new(tmp.path());
+        let ancient = finished("ancient", 400);
+        let recent = finished("recent", 40);
+        archive.append(&[ancient, recent.clone()]).unwrap();
+
+        let removed = archive.prune(Utc::now() - Duration::days(365)).unwrap();
+        assert_eq!(removed, 1);
+        let all = archive.load_all().unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].id, recent.id);
+        assert_eq!(archive.prune(Utc::now() - Duration::days(365)).unwrap(), 0);
+    }
+}
@@ -0,0 +1,308 @@
+//! Behavioural tests every `Storage` backend must pass
+//!
+//! Each case is written once against `&dyn Storage` and instantiated per
+//! backend by `conformance_tests!`.
+
+use super::search::SearchQuery;
+use super::{sqlite::SqliteStorage, ContractQuery, JsonlStorage, SortOrder, Storage, StorageError};
+use crate::schema::{Contract, ContractStatus};
+use chrono::{Duration, Utc};
+use tempfile::TempDir;
+
+/// A contract with every optional field populated
+fn full_contract() -> Contract {
+    let mut c = Contract::new("full task", "cargo test");
+    c.owner = Some("agent-1".to_string());
+    c.claimed_at = Some(Utc::now());
+    c.blocked_by = vec!["a".to_string()];
+    c.blocks = vec!["b".to_string(), "c".to_string()];
+    c.parent_id = Some("parent".to_string());
+    c.await_children = true;
+    c.requires_approval = true;
+    c.tags = vec!["x".to_string()];
+    c.output = Some("out".to_string());
+    c
+}
+
+/// A contract created `secs` seconds ago, so ordering is deterministic
+fn aged(task: &str, secs: i64) -> Contract {
+    let mut c = Contract::new(task, "true");
+    c.created_at = Utc::now() - Duration::seconds(secs);
+    c
+}
+
+fn roundtrip(storage: &dyn Storage) {
+    let c = full_contract();
+    storage.save_contract(&c).unwrap();
+    let loaded = storage.load_contract(&c.id).unwrap().unwrap();
+    assert_eq!(
+        serde_json::to_value(&loaded).unwrap(),
+        serde_json::to_value(&c).unwrap()
+    );
+}
+
+fn load_missing(storage: &dyn Storage) {
+    assert!(storage.load_contract("nope").unwrap().is_none());
+    assert!(storage.load_all_contracts().unwrap().is_empty());
+}
+
+fn load_all_newest_first(storage: &dyn Storage) {
+    let old = aged("old", 30);
+    let new = aged("new", 10);
+    let mid = aged("mid", 20);
+    for c in [&old, &new, &mid] {
+        storage.save_contract(c).unwrap();
+    }
+
+    let tasks: Vec<String> = storage
+        .load_all_contracts()
+        .unwrap()
+        .into_iter()
+        .map(|c| c.task)
+        .collect();
+    assert_eq!(tasks, vec!["new", "mid", "old"]);
+}
+
+fn update_persists(storage: &dyn Storage) {
+    let mut c = Contract::new("task", "true");
+    storage.save_contract(&c).unwrap();
+
+    c.mark_ready().unwrap();
+    c.claim("agent").unwrap();
+    c.tags.push("t".to_string());
+    storage.update_contract(&c).unwrap();
+
+    let loaded = storage.load_contract(&c.id).unwrap().unwrap();
+    assert_eq!(loaded.status, ContractStatus::Claimed);
+    assert_eq!(loaded.owner.as_deref(), Some("agent"));
+    assert_eq!(loaded.tags, vec!["t"]);
+    assert_eq!(storage.load_all_contracts().unwrap().len(), 1);
+}
+
+fn update_missing(storage: &dyn Storage) {
+    let c = Contract::new("task", "true");
+    match storage.update_contract(&c) {
+        Err(StorageError::NotFound(id)) => assert_eq!(id, c.id),
+        other => panic!("expected NotFound, got {:?}", other),
+    }
+}
+
+fn duplicate_save(storage: &dyn Storage) {
+    let c = Contract::new("task", "true");
+    storage.save_contract(&c).unwrap();
+    match storage.save_contract(&c) {
+        Err(StorageError::AlreadyExists(id)) => assert_eq!(id, c.id),
+        other => panic!("expected AlreadyExists, got {:?}", other),
+    }
+    assert_eq!(storage.load_all_contracts().unwrap().len(), 1);
+}
+
+fn query_filters(storage: &dyn Storage) {
+    let pending = aged("pending", 30);
+    let mut ready = aged("ready", 20);
+    ready.mark_ready().unwrap();
+    let mut claimed = aged("claimed", 10);
+    claimed.mark_ready().unwrap();
+    claimed.claim("bot").unwrap();
+    claimed.tags = vec!["api".to_string(), "urgent".to_string()];
+    for c in [&pending, &ready, &claimed] {
+        storage.save_contract(c).unwrap();
+    }
+
+    let tasks = |q: ContractQuery| -> Vec<String> {
+        storage
+            .query(&q)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.task)
+            .collect()
+    };
+
+    assert_eq!(
+        tasks(ContractQuery::new().status(ContractStatus::Ready)),
+        vec!["ready"]
+    );
+    assert_eq!(
+        tasks(ContractQuery::new().statuses([ContractStatus::Ready, ContractStatus::Pending])),
+        vec!["ready", "pending"]
+    );
+    assert!(tasks(ContractQuery::new().status(ContractStatus::Completed)).is_empty());
+    assert_eq!(tasks(ContractQuery::new().owner("bot")), vec!["claimed"]);
+    assert_eq!(
+        tasks(ContractQuery::new().tag("api").tag("urgent")),
+        vec!["claimed"]
+    );
+    assert!(tasks(ContractQuery::new().tag("api").tag("docs")).is_empty());
+    assert_eq!(
+        tasks(ContractQuery::new().created_before(Utc::now() - Duration::seconds(15))),
+        vec!["ready", "pending"]
+    );
+    assert_eq!(
+        tasks(ContractQuery::new().created_after(Utc::now() - Duration::seconds(25))),
+        vec!["claimed", "ready"]
+    );
+}
+
+fn query_sorts_and_paginates(storage: &dyn Storage) {
+    for i in 1..=5 {
+        storage.save_contract(&aged(&format!("c{}", i), i)).unwrap();
+    }
+    let tasks = |q: ContractQuery| -> Vec<String> {
+        storage
+            .query(&q)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.task)
+            .collect()
+    };
+
+    assert_eq!(tasks(ContractQuery::new().limit(2)), vec!["c1", "c2"]);
+    assert_eq!(
+        tasks(ContractQuery::new().limit(2).offset(2)),
+        vec!["c3", "c4"]
+    );
+    assert_eq!(
+        tasks(ContractQuery::new().offset(3).sort(SortOrder::OldestFirst)),
+        vec!["c2", "c1"]
+    );
+}
+
+fn delete_removes(storage: &dyn Storage) {
+    let keep = aged("keep", 20);
+    let drop = aged("drop", 10);
+    storage.save_contract(&keep).unwrap();
+    storage.save_contract(&drop).unwrap();
+
+    storage.delete_contract(&drop.id).unwrap();
+    assert!(storage.load_contract(&drop.id).unwrap().is_none());
+    assert_eq!(storage.load_all_contracts().unwrap().len(), 1);
+    assert!(storage
+        .search(&SearchQuery::new("drop"))
+        .unwrap()
+        .is_empty());
+
+    match storage.delete_contract(&drop.id) {
+        Err(StorageError::NotFound(id)) => assert_eq!(id, drop.id),
+        other => panic!("expected NotFound, got {:?}", other),
+    }
+}
+
+fn transaction_commits(storage: &dyn Storage) {
+    let mut a = aged("a", 20);
+    let b = aged("b", 10);
+    storage.save_contract(&a).unwrap();
+
+    storage
+        .transaction(&mut |tx| {
+            a.task = "a2".to_string();
+            tx.update_contract(&a)?;
+            tx.save_contract(&b)
+        })
+        .unwrap();
+
+    assert_eq!(storage.load_contract(&a.id).unwrap().unwrap().task, "a2");
+    assert!(storage.load_contract(&b.id).unwrap().is_some());
+}
+
+fn transaction_rolls_back(storage: &dyn Storage) {
+    let mut a = aged("a", 20);
+    storage.save_contract(&a).unwrap();
+
+    let result = storage.transaction(&mut |tx| {
+        a.task = "a2".to_string();
+        tx.update_contract(&a)?;
+        tx.save_contract(&aged("b", 10))?;
+        tx.delete_contract("missing")
+    });
+
+    assert!(matches!(result, Err(StorageError::NotFound(_))));
+    let all = storage.load_all_contracts().unwrap();
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0].task, "a");
+}
+
+fn children_oldest_first(storage: &dyn Storage) {
+    let parent = aged("parent", 40);
+    let mut second = parent.spawn_child("second", "true");
+    second.created_at = Utc::now() - Duration::seconds(10);
+    let mut first = parent.spawn_child("first", "true");
+    first.created_at = Utc::now() - Duration::seconds(20);
+    for c in [&parent, &second, &first] {
+        storage.save_contract(c).unwrap();
+    }
+    storage.save_contract(&aged("unrelated", 5)).unwrap();
+
+    let tasks: Vec<String> = storage
+        .load_children(&parent.id)
+        .unwrap()
+        .into_iter()
+        .map(|c| c.task)
+        .collect();
+    assert_eq!(tasks, vec!["first", "second"]);
+}
+
+fn search_follows_updates(storage: &dyn Storage) {
+    let mut c = aged("add throttling", 10);
+    storage.save_contract(&c).unwrap();
+    storage.save_contract(&aged("write docs", 5)).unwrap();
+    assert!(storage
+        .search(&SearchQuery::new("limiter"))
+        .unwrap()
+        .is_empty());
+
+    c.output = Some("the limiter tripped".to_string());
+    storage.update_contract(&c).unwrap();
+
+    let hits = storage.search(&SearchQuery::new("LIMITER")).unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].contract.id, c.id);
+    assert!(hits[0].snippet.contains("**limiter**"));
+
+    let mut filtered = SearchQuery::new("limiter");
+    filtered.status = Some(ContractStatus::Completed);
+    assert!(storage.search(&filtered).unwrap().is_empty());
+}
+
+macro_rules! conformance_tests {
+    ($backend:ident, $make:expr) => {
+        mod $backend {
+            use super::*;
+
+            macro_rules! case {
+                ($name:ident) => {
+                    #[test]
+                    fn $name() {
+                        // `_guard` keeps any temp dir alive for the test
+                        let (storage, _guard) = $make;
+                        super::$name(&*storage);
+                    }
+                };
+            }
+
+            case!(roundtrip);
+            case!(load_missing);
+            case!(load_all_newest_first);
+            case!(update_persists);
+            case!(update_missing);
+            case!(duplicate_save);
+            case!(query_filters);
+            case!(query_sorts_and_paginates);
+            case!(delete_removes);
+            case!(transaction_commits);
+            case!(transaction_rolls_back);
+            case!(children_oldest_first);
+            case!(search_follows_updates);
+        }
+    };
+}
+
+conformance_tests!(sqlite, {
+    let storage: Box<dyn Storage> = Box::new(SqliteStorage::open_in_memory().unwrap());
+    (storage, ())
+});
+
+conformance_tests!(jsonl, {
+    let tmp = TempDir::new().unwrap();
+    let storage: Box<dyn Storage> = Box::new(JsonlStorage::new(tmp.path()));
+    (storage, tmp)
+});
@@ -3,7 +3,9 @@
 //! Contracts are stored as JSON Lines in .stead/contracts.jsonl
 //! Each contract is one line, enabling append-only writes and streaming reads.
 
+use super::Corruption;
 use crate::schema::Contract;
+use serde::Deserialize;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
@@ -25,6 +27,21 @@ pub enum StorageError {
 
     #[error("Contract not found: {0}")]
     NotFound(String),
+
+    #[error("Contract already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Corrupt contract {id}: invalid {field} (run `stead db check`)")]
+    Corrupt { id: String, field: String },
+
+    #[error("Database schema version {found} is newer than this stead supports ({supported}); upgrade stead")]
+    SchemaTooNew { found: u32, supported: u32 },
+
+    #[error("Migration {version} failed: {message}")]
+    Migration { version: u32, message: String },
+
+    #[error("The project registry is off; run `stead init --register` to enable it")]
+    RegistryDisabled,
 }
 
 /// Get the path to the contracts file
@@ -32,6 +49,11 @@ pub fn get_contracts_path(cwd: &Path) -> PathBuf {
     cwd.join(super::STEAD_DIR).join(CONTRACTS_FILE)
 }
 
+/// Temp file a rewrite fills before renaming it over the contracts file
+pub(crate) fn get_contracts_temp_path(cwd: &Path) -> PathBuf {
+    get_contracts_path(cwd).with_extension("jsonl.tmp")
+}
+
 /// Get the .stead directory path
 pub fn get_stead_dir(cwd: &Path) -> PathBuf {
     cwd.join(super::STEAD_DIR)
@@ -83,32 +105,63 @@ pub fn write_contract(contract: &Contract, cwd: &Path) -> Result<(), StorageErro
 }
 
 /// Update a contract in storage (rewrite file with updated contract)
+///
+/// Line order is preserved and unparseable lines are kept verbatim, so the
+/// change shows up as a single-line diff.
 pub fn update_contract(contract: &Contract, cwd: &Path) -> Result<(), StorageError> {
-    let mut contracts = list_contracts(cwd)?;
+    let json = serde_json::to_string(contract).map_err(|e| StorageError::Json {
+        line: 0,
+        message: e.to_string(),
+    })?;
+    replace_line(&contract.id, Some(json), cwd)
+}
+
+/// Remove a contract's line from storage
+pub fn delete_contract(id: &str, cwd: &Path) -> Result<(), StorageError> {
+    replace_line(id, None, cwd)
+}
 
-    // Find and update the contract
-    let found = contracts.iter_mut().find(|c| c.id == contract.id);
+/// Just enough of a line to match it by ID
+#[derive(Deserialize)]
+struct LineId {
+    id: String,
+}
 
-    match found {
-        Some(existing) => {
-            *existing = contract.clone();
-        }
-        None => {
-            return Err(StorageError::NotFound(contract.id.clone()));
+/// Replace (or with `None`, drop) the first line holding contract `id`
+fn replace_line(id: &str, replacement: Option<String>, cwd: &Path) -> Result<(), StorageError> {
+    let path = get_contracts_path(cwd);
+    let content = if path.exists() {
+        fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+
+    let mut found = false;
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        if !found && serde_json::from_str::<LineId>(line).is_ok_and(|l| l.id == id) {
+            found = true;
+            lines.extend(replacement.clone());
+        } else {
+            lines.push(line.to_string());
         }
     }
 
-    // Rewrite the entire file
-    rewrite_contracts(&contracts, cwd)
+    if !found {
+        return Err(StorageError::NotFound(id.to_string()));
+    }
+
+    rewrite_lines(&lines, cwd)
 }
 
-/// Rewrite all contracts to the file
-fn rewrite_contracts(contracts: &[Contract], cwd: &Path) -> Result<(), StorageError> {
+/// Atomically replace the contracts file with `lines`
+fn rewrite_lines(lines: &[String], cwd: &Path) -> Result<(), StorageError> {
     ensure_stead_dir(cwd)?;
 
     let path = get_contracts_path(cwd);
+    let tmp_path = get_contracts_temp_path(cwd);
 
-    let mut file = File::create(&path).map_err(|e| {
+    let mut file = File::create(&tmp_path).map_err(|e| {
         if e.kind() == std::io::ErrorKind::PermissionDenied {
             StorageError::PermissionDenied(format!("Cannot write to: {}", path.display()))
         } else {
@@ -116,13 +169,11 @@ fn rewrite_contracts(contracts: &[Contract], cwd: &Path) -> Result<(), StorageEr
         }
     })?;
 
-    for contract in contracts {
-        let json = serde_json::to_string(contract).map_err(|e| StorageError::Json {
-            line: 0,
-            message: e.to_string(),
-        })?;
-        writeln!(file, "{}", json)?;
+    for line in lines {
+        writeln!(file, "{}", line)?;
     }
+    file.sync_all()?;
+    fs::rename(&tmp_path, &path)?;
 
     Ok(())
 }
@@ -134,7 +185,45 @@ pub fn read_contract(id: &str, cwd: &Path) -> Result<Option<Contract>, StorageEr
 }
 
 /// List all contracts, sorted by created_at descending
+///
+/// Fails on the first line that doesn't decode, like the SQLite backend
+/// does for a corrupt row.
 pub fn list_contracts(cwd: &Path) -> Result<Vec<Contract>, StorageError> {
+    let mut contracts = Vec::new();
+    for (line_num, line) in read_lines(cwd)? {
+        let contract = serde_json::from_str::<Contract>(&line).map_err(|e| StorageError::Json {
+            line: line_num,
+            message: format!("{} (run `stead db check`)", e),
+        })?;
+        contracts.push(contract);
+    }
+
+    // Sort by created_at descending (newest first)
+    contracts.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+
+    Ok(contracts)
+}
+
+/// Report every line of the contracts file that doesn't decode.
+///
+/// Whole lines are reported, so nothing here is repairable automatically.
+pub fn check_integrity(cwd: &Path) -> Result<Vec<Corruption>, StorageError> {
+    Ok(read_lines(cwd)?
+        .into_iter()
+        .filter(|(_, line)| serde_json::from_str::<Contract>(line).is_err())
+        .map(|(line_num, line)| Corruption {
+            id: serde_json::from_str::<LineId>(&line)
+                .map(|l| l.id)
+                .unwrap_or_else(|_| "?".to_string()),
+            field: format!("line {}", line_num),
+            value: Some(line),
+            repairable: false,
+        })
+        .collect())
+}
+
+/// Non-blank lines of the contracts file with their 1-based line numbers
+fn read_lines(cwd: &Path) -> Result<Vec<(usize, String)>, StorageError> {
     let path = get_contracts_path(cwd);
 
     if !path.exists() {
@@ -149,36 +238,14 @@ pub fn list_contracts(cwd: &Path) -> Result<Vec<Contract>, StorageError> {
         }
     })?;
 
-    let reader = BufReader::new(file);
-    let mut contracts = Vec::new();
-    let mut errors = Vec::new();
-
-    for (line_num, line) in reader.lines().enumerate() {
+    let mut lines = Vec::new();
+    for (line_num, line) in BufReader::new(file).lines().enumerate() {
         let line = line?;
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        match serde_json::from_str::<Contract>(&line) {
-            Ok(contract) => contracts.push(contract),
-            Err(e) => {
-                errors.push((line_num + 1, e.to_string()));
-            }
+        if !line.trim().is_empty() {
+            lines.push((line_num + 1, line));
         }
     }
-
-    // Log warnings for corrupted entries
-    if !errors.is_empty() {
-        eprintln!("Warning: {} contract(s) could not be loaded:", errors.len());
-        for (line, error) in errors {
-            eprintln!("  - Line {}: {}", line, error);
-        }
-    }
-
-    // Sort by created_at descending (newest first)
-    contracts.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-
-    Ok(contracts)
+    Ok(lines)
 }
 
 /// Check if stead is initialized in this directory
@@ -201,6 +268,9 @@ impl JsonlStorage {
 
 impl super::Storage for JsonlStorage {
     fn save_contract(&self, contract: &Contract) -> Result<(), StorageError> {
+        if read_contract(&contract.id, &self.cwd)?.is_some() {
+            return Err(StorageError::AlreadyExists(contract.id.clone()));
+        }
         write_contract(contract, &self.cwd)
     }
 
@@ -216,13 +286,47 @@ impl super::Storage for JsonlStorage {
         update_contract(contract, &self.cwd)
     }
 
-    fn filter_by_status(&self, status: &str) -> Result<Vec<Contract>, StorageError> {
-        let contracts = list_contracts(&self.cwd)?;
-        let status_lower = status.to_lowercase();
-        Ok(contracts
-            .into_iter()
-            .filter(|c| c.status.to_string() == status_lower)
-            .collect())
+    fn delete_contract(&self, id: &str) -> Result<(), StorageError> {
+        delete_contract(id, &self.cwd)
+    }
+
+    /// Hash of the file contents (0 when there is no file)
+    fn data_version(&self) -> Result<Option<u64>, StorageError> {
+        use std::hash::{Hash, Hasher};
+
+        let path = get_contracts_path(&self.cwd);
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Some(0)),
+            Err(e) => return Err(e.into()),
+        };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        Ok(Some(hasher.finish()))
+    }
+
+    /// Snapshots the file and restores it if `f` fails. This rolls back
+    /// failed batches but, unlike SQLite, is not crash-safe.
+    fn transaction(
+        &self,
+        f: &mut dyn FnMut(&dyn super::Storage) -> Result<(), StorageError>,
+    ) -> Result<(), StorageError> {
+        let path = get_contracts_path(&self.cwd);
+        let snapshot = if path.exists() {
+            Some(fs::read(&path)?)
+        } else {
+            None
+        };
+
+        let result = f(self);
+        if result.is_err() {
+            match snapshot {
+                Some(bytes) => fs::write(&path, bytes)?,
+                None if path.exists() => fs::remove_file(&path)?,
+                None => {}
+            }
+        }
+        result
     }
 }
 
@@ -310,25 +414,55 @@ mod tests {
     }
 
     #[test]
-    fn test_graceful_corruption_handling() {
+    fn test_corrupt_line_is_an_error() {
         let tmp = setup();
 
         // Write valid contract
         let contract = Contract::new("task", "verify");
         write_contract(&contract, tmp.path()).unwrap();
 
-        // Append corrupted line directly
+        // Append corrupted lines directly
         let path = get_contracts_path(tmp.path());
         let mut file = OpenOptions::new().append(true).open(&path).unwrap();
         writeln!(file, "{{invalid json").unwrap();
+        writeln!(file, r#"{{"id":"c-bad","status":"nope"}}"#).unwrap();
 
-        // Write another valid contract
-        let contract2 = Contract::new("task 2", "verify 2");
-        write_contract(&contract2, tmp.path()).unwrap();
+        match list_contracts(tmp.path()) {
+            Err(StorageError::Json { line, .. }) => assert_eq!(line, 2),
+            other => panic!("expected Json error, got {:?}", other.map(|c| c.len())),
+        }
+        assert!(read_contract(&contract.id, tmp.path()).is_err());
+
+        let problems = check_integrity(tmp.path()).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].id, "?");
+        assert_eq!(problems[0].field, "line 2");
+        assert_eq!(problems[1].id, "c-bad");
+        assert_eq!(problems[1].field, "line 3");
+        assert!(problems.iter().all(|p| !p.repairable));
+    }
 
-        // Should load valid contracts despite corruption
-        let contracts = list_contracts(tmp.path()).unwrap();
-        assert_eq!(contracts.len(), 2);
+    #[test]
+    fn test_update_preserves_line_order_and_corrupt_lines() {
+        let tmp = setup();
+        let c1 = Contract::new("task 1", "verify");
+        let mut c2 = Contract::new("task 2", "verify");
+        write_contract(&c1, tmp.path()).unwrap();
+        write_contract(&c2, tmp.path()).unwrap();
+
+        let path = get_contracts_path(tmp.path());
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{{invalid json").unwrap();
+
+        c2.status = ContractStatus::Completed;
+        update_contract(&c2, tmp.path()).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains(&c1.id));
+        assert!(lines[1].contains(&c2.id) && lines[1].contains("completed"));
+        assert_eq!(lines[2], "{invalid json");
     }
 
     #[test]
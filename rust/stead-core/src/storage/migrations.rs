@@ -0,0 +1,330 @@
+//! Versioned SQLite schema migrations
+//!
+//! The schema version lives in `PRAGMA user_version`. Each migration runs
+//! in its own transaction together with the version bump, so a failure
+//! leaves the database at the previous version. Column additions check
+//! `table_info` first: databases created before versioning already have
+//! some of these columns and start at version 0.
+
+use crate::storage::StorageError;
+use rusqlite::Connection;
+
+/// One schema change
+#[derive(Debug)]
+enum Step {
+    /// Arbitrary idempotent SQL
+    Sql(&'static str),
+    /// `ALTER TABLE .. ADD COLUMN`, skipped when the column already exists
+    AddColumn {
+        table: &'static str,
+        column: &'static str,
+        definition: &'static str,
+    },
+}
+
+/// An ordered, numbered schema migration
+#[derive(Debug)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    steps: &'static [Step],
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Create contracts table",
+        steps: &[Step::Sql(
+            "CREATE TABLE IF NOT EXISTS contracts (
+                id TEXT PRIMARY KEY,
+                task TEXT NOT NULL,
+                verify_cmd TEXT NOT NULL,
+                status TEXT NOT NULL,
+                output TEXT,
+                created_at TEXT NOT NULL,
+                completed_at TEXT,
+                project_path TEXT NOT NULL DEFAULT ''
+            );
+            CREATE INDEX IF NOT EXISTS idx_contracts_status ON contracts(status);
+            CREATE INDEX IF NOT EXISTS idx_contracts_project_path ON contracts(project_path);",
+        )],
+    },
+    Migration {
+        version: 2,
+        description: "Add owner and dependency columns",
+        steps: &[
+            Step::AddColumn {
+                table: "contracts",
+                column: "owner",
+                definition: "TEXT",
+            },
+            Step::AddColumn {
+                table: "contracts",
+                column: "blocked_by",
+                definition: "TEXT NOT NULL DEFAULT '[]'",
+            },
+            Step::AddColumn {
+                table: "contracts",
+                column: "blocks",
+                definition: "TEXT NOT NULL DEFAULT '[]'",
+            },
+            Step::Sql("CREATE INDEX IF NOT EXISTS idx_contracts_owner ON contracts(owner);"),
+        ],
+    },
+    Migration {
+        version: 3,
+        description: "Add sub-contract hierarchy",
+        steps: &[
+            Step::AddColumn {
+                table: "contracts",
+                column: "parent_id",
+                definition: "TEXT",
+            },
+            Step::AddColumn {
+                table: "contracts",
+                column: "await_children",
+                definition: "INTEGER NOT NULL DEFAULT 0",
+            },
+            Step::Sql(
+                "CREATE INDEX IF NOT EXISTS idx_contracts_parent_id ON contracts(parent_id);",
+            ),
+        ],
+    },
+    Migration {
+        version: 4,
+        description: "Add approval gates",
+        steps: &[
+            Step::AddColumn {
+                table: "contracts",
+                column: "requires_approval",
+                definition: "INTEGER NOT NULL DEFAULT 0",
+            },
+            Step::AddColumn {
+                table: "contracts",
+                column: "approval",
+                definition: "TEXT",
+            },
+        ],
+    },
+    Migration {
+        version: 5,
+        description: "Add contract tags",
+        steps: &[Step::AddColumn {
+            table: "contracts",
+            column: "tags",
+            definition: "TEXT NOT NULL DEFAULT '[]'",
+        }],
+    },
+    Migration {
+        version: 6,
+        description: "Add full-text search index",
+        steps: &[Step::Sql(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS contracts_fts USING fts5(
+                task, verify_cmd, output,
+                content='contracts', content_rowid='rowid',
+                tokenize='porter unicode61'
+            );
+            CREATE TRIGGER IF NOT EXISTS contracts_fts_insert AFTER INSERT ON contracts BEGIN
+                INSERT INTO contracts_fts(rowid, task, verify_cmd, output)
+                VALUES (new.rowid, new.task, new.verify_cmd, new.output);
+            END;
+            CREATE TRIGGER IF NOT EXISTS contracts_fts_delete AFTER DELETE ON contracts BEGIN
+                INSERT INTO contracts_fts(contracts_fts, rowid, task, verify_cmd, output)
+                VALUES ('delete', old.rowid, old.task, old.verify_cmd, old.output);
+            END;
+            CREATE TRIGGER IF NOT EXISTS contracts_fts_update
+            AFTER UPDATE OF task, verify_cmd, output ON contracts BEGIN
+                INSERT INTO contracts_fts(contracts_fts, rowid, task, verify_cmd, output)
+                VALUES ('delete', old.rowid, old.task, old.verify_cmd, old.output);
+                INSERT INTO contracts_fts(rowid, task, verify_cmd, output)
+                VALUES (new.rowid, new.task, new.verify_cmd, new.output);
+            END;
+            INSERT INTO contracts_fts(contracts_fts) VALUES ('rebuild');",
+        )],
+    },
+    Migration {
+        version: 7,
+        description: "Add claim timestamp",
+        steps: &[Step::AddColumn {
+            table: "contracts",
+            column: "claimed_at",
+            definition: "TEXT",
+        }],
+    },
+];
+
+/// Schema version this binary writes
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|m| m.version).unwrap_or(0)
+}
+
+/// Current schema version of a database
+pub fn current_version(conn: &Connection) -> Result<u32, StorageError> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+        .map_err(sql_err)
+}
+
+/// Migrations not yet applied to `conn`.
+///
+/// Fails with `SchemaTooNew` if the database was written by a newer binary.
+pub fn pending(conn: &Connection) -> Result<Vec<&'static Migration>, StorageError> {
+    let current = current_version(conn)?;
+    let latest = latest_version();
+    if current > latest {
+        return Err(StorageError::SchemaTooNew {
+            found: current,
+            supported: latest,
+        });
+    }
+    Ok(MIGRATIONS.iter().filter(|m| m.version > current).collect())
+}
+
+/// Every migration, in order (for reporting on databases that don't exist yet)
+pub fn all() -> Vec<&'static Migration> {
+    MIGRATIONS.iter().collect()
+}
+
+/// Apply pending migrations, returning the ones that ran
+pub fn migrate(conn: &Connection) -> Result<Vec<&'static Migration>, StorageError> {
+    let pending = pending(conn)?;
+    for migration in &pending {
+        apply(conn, migration).map_err(|e| StorageError::Migration {
+            version: migration.version,
+            message: e.to_string(),
+        })?;
+    }
+    Ok(pending)
+}
+
+fn apply(conn: &Connection, migration: &Migration) -> rusqlite::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    for step in migration.steps {
+        match step {
+            Step::Sql(sql) => tx.execute_batch(sql)?,
+            Step::AddColumn {
+                table,
+                column,
+                definition,
+            } => {
+                if !has_column(&tx, table, column)? {
+                    tx.execute_batch(&format!(
+                        "ALTER TABLE {} ADD COLUMN {} {}",
+                        table, column, definition
+                    ))?;
+                }
+            }
+        }
+    }
+    tx.pragma_update(None, "user_version", migration.version)?;
+    tx.commit()
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> rusqlite::Result<bool> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let names = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(names.iter().any(|n| n == column))
+}
+
+fn sql_err(e: rusqlite::Error) -> StorageError {
+    StorageError::Io(std::io::Error::other(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_are_sequential() {
+        for (i, m) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(m.version as usize, i + 1);
+        }
+    }
+
+    #[test]
+    fn test_fresh_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        assert_eq!(pending(&conn).unwrap().len(), MIGRATIONS.len());
+
+        let applied = migrate(&conn).unwrap();
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        assert!(pending(&conn).unwrap().is_empty());
+
+        // Re-running is a no-op
+        assert!(migrate(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_legacy_unversioned_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        // Shape of a pre-versioning database that already has some columns
+        conn.execute_batch(
+            "CREATE TABLE contracts (
+                id TEXT PRIMARY KEY,
+                task TEXT NOT NULL,
+                verify_cmd TEXT NOT NULL,
+                status TEXT NOT NULL,
+                output TEXT,
+                created_at TEXT NOT NULL,
+                completed_at TEXT,
+                project_path TEXT NOT NULL DEFAULT '',
+                owner TEXT,
+                blocked_by TEXT NOT NULL DEFAULT '[]',
+                blocks TEXT NOT NULL DEFAULT '[]'
+            );
+            INSERT INTO contracts (id, task, verify_cmd, status, created_at)
+            VALUES ('c1', 'task', 'true', 'pending', '2026-01-01T00:00:00Z');",
+        )
+        .unwrap();
+
+        migrate(&conn).unwrap();
+        assert_eq!(current_version(&conn).unwrap(), latest_version());
+        assert!(has_column(&conn, "contracts", "tags").unwrap());
+
+        let tags: String = conn
+            .query_row("SELECT tags FROM contracts WHERE id = 'c1'", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(tags, "[]");
+
+        // Existing rows are indexed for search
+        let indexed: i64 = conn
+            .query_row(
+                "SELECT count(*) FROM contracts_fts WHERE contracts_fts MATCH 'task'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(indexed, 1);
+    }
+
+    #[test]
+    fn test_newer_database_is_rejected() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", latest_version() + 1)
+            .unwrap();
+
+        match migrate(&conn) {
+            Err(StorageError::SchemaTooNew { found, supported }) => {
+                assert_eq!(found, latest_version() + 1);
+                assert_eq!(supported, latest_version());
+            }
+            other => panic!("expected SchemaTooNew, got {:?}", other.map(|m| m.len())),
+        }
+    }
+
+    #[test]
+    fn test_failed_migration_rolls_back() {
+        let conn = Connection::open_in_memory().unwrap();
+        // A `contracts` view makes the very first migration fail
+        conn.execute_batch("CREATE VIEW contracts AS SELECT 1 AS id;")
+            .unwrap();
+
+        let err = migrate(&conn).unwrap_err();
+        assert!(matches!(err, StorageError::Migration { .. }));
+        assert_eq!(current_version(&conn).unwrap(), 0);
+    }
+}
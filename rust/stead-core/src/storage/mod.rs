@@ -1,21 +1,168 @@
 //! Contract storage backends
 //!
-//! Supports JSONL (legacy) and SQLite (default).
+//! Supports SQLite (default, `.stead/stead.db`) and JSONL
+//! (`.stead/contracts.jsonl`, one contract per line for git-friendly diffs).
+//! The backend is chosen per project in `.stead/config.toml` or with
+//! `--storage`.
 
+pub mod archive;
+#[cfg(test)]
+mod conformance;
 mod jsonl;
+pub mod migrations;
+pub mod query;
+pub mod registry;
+pub mod search;
 pub mod sqlite;
+pub mod watch;
 
 pub use jsonl::*;
+pub use query::{ContractQuery, SortOrder};
 
 use crate::schema::Contract;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 pub(crate) const STEAD_DIR: &str = ".stead";
 
+/// Which backend holds a project's contracts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    Sqlite,
+    Jsonl,
+}
+
+impl StorageBackend {
+    /// File holding a project's contracts for this backend
+    pub fn path(&self, cwd: &Path) -> PathBuf {
+        match self {
+            StorageBackend::Sqlite => sqlite::SqliteStorage::db_path(cwd),
+            StorageBackend::Jsonl => get_contracts_path(cwd),
+        }
+    }
+}
+
+impl std::fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageBackend::Sqlite => write!(f, "sqlite"),
+            StorageBackend::Jsonl => write!(f, "jsonl"),
+        }
+    }
+}
+
+impl std::str::FromStr for StorageBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sqlite" => Ok(StorageBackend::Sqlite),
+            "jsonl" => Ok(StorageBackend::Jsonl),
+            _ => Err(format!(
+                "Invalid storage backend '{}'. Valid values: sqlite, jsonl",
+                s
+            )),
+        }
+    }
+}
+
+/// Open a project's contract storage, creating it if needed.
+///
+/// Registers the project in the global registry when it is enabled.
+pub fn open(cwd: &Path, backend: StorageBackend) -> Result<Box<dyn Storage>, StorageError> {
+    match backend {
+        StorageBackend::Sqlite => Ok(Box::new(sqlite::open_default(cwd)?)),
+        StorageBackend::Jsonl => {
+            ensure_stead_dir(cwd)?;
+            // Create the (empty) file up front so the project counts as initialized
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(get_contracts_path(cwd))?;
+            registry::register_if_enabled(cwd);
+            Ok(Box::new(JsonlStorage::new(cwd)))
+        }
+    }
+}
+
+/// Open a project's existing contract storage without creating anything.
+///
+/// Returns None when the backend's file doesn't exist yet.
+pub fn open_existing(
+    cwd: &Path,
+    backend: StorageBackend,
+) -> Result<Option<Box<dyn Storage>>, StorageError> {
+    if !backend.path(cwd).exists() {
+        return Ok(None);
+    }
+    match backend {
+        StorageBackend::Sqlite => Ok(Some(Box::new(sqlite::SqliteStorage::open(cwd)?))),
+        StorageBackend::Jsonl => Ok(Some(Box::new(JsonlStorage::new(cwd)))),
+    }
+}
+
+/// Stored data that failed strict decoding, as reported by `stead db check`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Corruption {
+    pub id: String,
+    /// Column (SQLite) or `line N` (JSONL) holding the bad value
+    pub field: String,
+    /// Stored value (None for NULL)
+    pub value: Option<String>,
+    /// Whether `repair` can safely reset this field
+    pub repairable: bool,
+}
+
 /// Storage backend trait for contract persistence
 pub trait Storage {
+    /// Persist a new contract; fails with `AlreadyExists` if the ID is taken
     fn save_contract(&self, contract: &Contract) -> Result<(), StorageError>;
     fn load_contract(&self, id: &str) -> Result<Option<Contract>, StorageError>;
+    /// Every contract, newest first
     fn load_all_contracts(&self) -> Result<Vec<Contract>, StorageError>;
+    /// Replace a stored contract; fails with `NotFound` if the ID is unknown
     fn update_contract(&self, contract: &Contract) -> Result<(), StorageError>;
-    fn filter_by_status(&self, status: &str) -> Result<Vec<Contract>, StorageError>;
+    /// Remove a contract; fails with `NotFound` if the ID is unknown
+    fn delete_contract(&self, id: &str) -> Result<(), StorageError>;
+
+    /// Run `f` atomically: if it fails, none of its writes are kept.
+    ///
+    /// `f` receives the storage to write through. Keep side effects other
+    /// than storage writes (hooks, output) outside the closure.
+    fn transaction(
+        &self,
+        f: &mut dyn FnMut(&dyn Storage) -> Result<(), StorageError>,
+    ) -> Result<(), StorageError>;
+
+    /// Contracts selected by `query`
+    fn query(&self, query: &ContractQuery) -> Result<Vec<Contract>, StorageError> {
+        Ok(query.apply(self.load_all_contracts()?))
+    }
+
+    /// Direct sub-contracts of `parent_id`, oldest first
+    fn load_children(&self, parent_id: &str) -> Result<Vec<Contract>, StorageError> {
+        self.query(
+            &ContractQuery::new()
+                .parent(parent_id)
+                .sort(SortOrder::OldestFirst),
+        )
+    }
+
+    /// Full-text search, best match first
+    fn search(&self, query: &search::SearchQuery) -> Result<Vec<search::SearchHit>, StorageError> {
+        Ok(search::search_in_memory(self.load_all_contracts()?, query))
+    }
+
+    /// Token that changes whenever the stored contracts may have changed,
+    /// used by `watch` to skip reloading; None if the backend can't tell
+    fn data_version(&self) -> Result<Option<u64>, StorageError> {
+        Ok(None)
+    }
+
+    /// Reclaim space left by deletions (no-op where there is nothing to reclaim)
+    fn compact(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
 }
@@ -0,0 +1,191 @@
+//! Contract queries
+//!
+//! `ContractQuery` describes a filtered, sorted, paginated selection.
+//! SQLite turns it into a WHERE/ORDER/LIMIT clause; other backends use
+//! `ContractQuery::apply` over every contract.
+
+use crate::schema::{Contract, ContractStatus};
+use chrono::{DateTime, Utc};
+
+/// Order of query results by `created_at`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+/// Builder for a contract selection; every filter is optional and they combine with AND
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContractQuery {
+    /// Any of these statuses (empty = any status)
+    pub statuses: Vec<ContractStatus>,
+    pub owner: Option<String>,
+    pub parent_id: Option<String>,
+    /// Inclusive lower bound on `created_at`
+    pub created_after: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `created_at`
+    pub created_before: Option<DateTime<Utc>>,
+    /// Contract must carry all of these tags
+    pub tags: Vec<String>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+    pub sort: SortOrder,
+}
+
+impl ContractQuery {
+    /// Every contract, newest first
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: ContractStatus) -> Self {
+        self.statuses.push(status);
+        self
+    }
+
+    pub fn statuses(mut self, statuses: impl IntoIterator<Item = ContractStatus>) -> Self {
+        self.statuses.extend(statuses);
+        self
+    }
+
+    pub fn owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    pub fn parent(mut self, parent_id: impl Into<String>) -> Self {
+        self.parent_id = Some(parent_id.into());
+        self
+    }
+
+    pub fn created_after(mut self, at: DateTime<Utc>) -> Self {
+        self.created_after = Some(at);
+        self
+    }
+
+    pub fn created_before(mut self, at: DateTime<Utc>) -> Self {
+        self.created_before = Some(at);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn sort(mut self, sort: SortOrder) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Whether `contract` passes every filter (ignores sort and pagination)
+    pub fn matches(&self, contract: &Contract) -> bool {
+        (self.statuses.is_empty() || self.statuses.contains(&contract.status))
+            && self
+                .owner
+                .as_ref()
+                .is_none_or(|o| contract.owner.as_ref() == Some(o))
+            && self
+                .parent_id
+                .as_ref()
+                .is_none_or(|p| contract.parent_id.as_ref() == Some(p))
+            && self.created_after.is_none_or(|t| contract.created_at >= t)
+            && self.created_before.is_none_or(|t| contract.created_at < t)
+            && self.tags.iter().all(|t| contract.tags.contains(t))
+    }
+
+    /// Filter, sort and paginate contracts in memory
+    pub fn apply(&self, contracts: Vec<Contract>) -> Vec<Contract> {
+        let mut selected: Vec<Contract> =
+            contracts.into_iter().filter(|c| self.matches(c)).collect();
+        match self.sort {
+            SortOrder::NewestFirst => selected.sort_by_key(|c| std::cmp::Reverse(c.created_at)),
+            SortOrder::OldestFirst => selected.sort_by_key(|c| c.created_at),
+        }
+        selected
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn aged(task: &str, hours: i64) -> Contract {
+        let mut c = Contract::new(task, "true");
+        c.created_at = Utc::now() - Duration::hours(hours);
+        c
+    }
+
+    #[test]
+    fn test_builder() {
+        let q = ContractQuery::new()
+            .status(ContractStatus::Ready)
+            .owner("bot")
+            .tag("api")
+            .limit(5)
+            .offset(10)
+            .sort(SortOrder::OldestFirst);
+        assert_eq!(q.statuses, vec![ContractStatus::Ready]);
+        assert_eq!(q.owner.as_deref(), Some("bot"));
+        assert_eq!(q.tags, vec!["api"]);
+        assert_eq!((q.limit, q.offset), (Some(5), 10));
+        assert_eq!(q.sort, SortOrder::OldestFirst);
+    }
+
+    #[test]
+    fn test_apply_filters_and_paginates() {
+        let contracts: Vec<Contract> = (1..=5).map(|h| aged(&format!("c{}", h), h)).collect();
+
+        let tasks = |q: ContractQuery| -> Vec<String> {
+            q.apply(contracts.clone())
+                .into_iter()
+                .map(|c| c.task)
+                .collect()
+        };
+
+        assert_eq!(tasks(ContractQuery::new().limit(2)), vec!["c1", "c2"]);
+        assert_eq!(
+            tasks(ContractQuery::new().offset(3).sort(SortOrder::OldestFirst)),
+            vec!["c2", "c1"]
+        );
+        assert_eq!(
+            tasks(
+                ContractQuery::new()
+                    .created_after(Utc::now() - Duration::minutes(150))
+                    .created_before(Utc::now() - Duration::minutes(30))
+            ),
+            vec!["c1", "c2"]
+        );
+    }
+
+    #[test]
+    fn test_matches_owner_and_statuses() {
+        let mut c = Contract::new("task", "true");
+        c.owner = Some("bot".to_string());
+
+        assert!(ContractQuery::new().owner("bot").matches(&c));
+        assert!(!ContractQuery::new().owner("human").matches(&c));
+        assert!(ContractQuery::new()
+            .statuses([ContractStatus::Ready, ContractStatus::Pending])
+            .matches(&c));
+        assert!(!ContractQuery::new()
+            .status(ContractStatus::Completed)
+            .matches(&c));
+    }
+}
@@ -0,0 +1,396 @@
+//! Global project registry
+//!
+//! Opt-in index of every project with a `.stead` directory, stored at
+//! `~/.local/share/stead/registry.db` (override with `STEAD_REGISTRY`).
+//! The registry is enabled once the database exists; from then on every
+//! project that opens its storage registers itself.
+
+use crate::config::Config;
+use crate::schema::Contract;
+use crate::storage::{ContractQuery, SortOrder, StorageError};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const REGISTRY_DIR: &str = "~/.local/share/stead";
+const REGISTRY_FILE: &str = "registry.db";
+
+/// Environment variable overriding the registry database path
+pub const REGISTRY_ENV: &str = "STEAD_REGISTRY";
+
+/// A project known to the registry
+#[derive(Debug, Clone, Serialize)]
+pub struct RegisteredProject {
+    /// Absolute project root (the directory containing `.stead`)
+    pub path: PathBuf,
+    pub registered_at: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// A contract tagged with the project it belongs to
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectContract {
+    pub project: String,
+    #[serde(flatten)]
+    pub contract: Contract,
+}
+
+/// A registered project whose contracts couldn't be read
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectError {
+    pub project: String,
+    pub message: String,
+}
+
+/// Contracts gathered across projects, plus the projects that were skipped
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AllContracts {
+    pub contracts: Vec<ProjectContract>,
+    pub errors: Vec<ProjectError>,
+}
+
+/// SQLite-backed registry of stead projects
+pub struct Registry {
+    conn: Connection,
+}
+
+impl Registry {
+    /// Path of the registry database (`STEAD_REGISTRY` or the default location)
+    pub fn default_path() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var(REGISTRY_ENV) {
+            if !path.is_empty() {
+                return Some(PathBuf::from(path));
+            }
+        }
+        let dir = if let Some(rest) = REGISTRY_DIR.strip_prefix("~/") {
+            dirs::home_dir()?.join(rest)
+        } else {
+            PathBuf::from(REGISTRY_DIR)
+        };
+        Some(dir.join(REGISTRY_FILE))
+    }
+
+    /// Open (or create) the registry at the default path
+    pub fn open_default() -> Result<Self, StorageError> {
+        let path = Self::default_path().ok_or_else(|| {
+            StorageError::Io(std::io::Error::other("cannot determine home directory"))
+        })?;
+        Self::open(&path)
+    }
+
+    /// Open the default registry only if it has been enabled (the file exists)
+    pub fn open_if_enabled() -> Result<Option<Self>, StorageError> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::open(&path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Open (or create) a registry database at `path`
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path)
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        let registry = Self { conn };
+        registry.init_schema()?;
+        Ok(registry)
+    }
+
+    /// Create an in-memory registry (for tests)
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        let conn = Connection::open_in_memory()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        let registry = Self { conn };
+        registry.init_schema()?;
+        Ok(registry)
+    }
+
+    fn init_schema(&self) -> Result<(), StorageError> {
+        self.conn
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS projects (
+                    path TEXT PRIMARY KEY,
+                    registered_at TEXT NOT NULL,
+                    last_seen TEXT NOT NULL
+                );",
+            )
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))
+    }
+
+    /// Record a project root, refreshing `last_seen` if already known
+    pub fn register(&self, project: &Path) -> Result<(), StorageError> {
+        let path = canonical_string(project);
+        let now = Utc::now().to_rfc3339();
+        self.conn
+            .execute(
+                "INSERT INTO projects (path, registered_at, last_seen) VALUES (?1, ?2, ?2)
+                 ON CONFLICT(path) DO UPDATE SET last_seen = excluded.last_seen",
+                params![path, now],
+            )
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        Ok(())
+    }
+
+    /// Forget a project
+    pub fn unregister(&self, project: &Path) -> Result<(), StorageError> {
+        self.conn
+            .execute(
+                "DELETE FROM projects WHERE path = ?1",
+                params![canonical_string(project)],
+            )
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        Ok(())
+    }
+
+    /// All registered projects, most recently seen first
+    pub fn projects(&self) -> Result<Vec<RegisteredProject>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, registered_at, last_seen FROM projects ORDER BY last_seen DESC")
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+
+        rows.into_iter()
+            .map(|(path, registered_at, last_seen)| {
+                Ok(RegisteredProject {
+                    registered_at: parse_time(&path, "registered_at", &registered_at)?,
+                    last_seen: parse_time(&path, "last_seen", &last_seen)?,
+                    path: PathBuf::from(path),
+                })
+            })
+            .collect()
+    }
+
+    /// Run `query` across every registered project.
+    ///
+    /// Sort and pagination apply to the combined result. Projects whose
+    /// `.stead` directory has disappeared are skipped; projects whose
+    /// storage can't be opened or queried are skipped and reported in
+    /// [`AllContracts::errors`] so one broken project doesn't hide the rest.
+    pub fn load_all_contracts(&self, query: &ContractQuery) -> Result<AllContracts, StorageError> {
+        // Each project contributes at most enough rows to fill the requested page
+        let per_project = ContractQuery {
+            limit: query.limit.map(|l| l + query.offset),
+            offset: 0,
+            ..query.clone()
+        };
+        let mut all = Vec::new();
+        let mut errors = Vec::new();
+
+        for project in self.projects()? {
+            if !super::is_initialized(&project.path) {
+                continue;
+            }
+
+            // An unreadable config shouldn't hide the project; assume the default backend
+            let backend = Config::load(&project.path)
+                .map(|c| c.storage.backend)
+                .unwrap_or_default();
+            let project_name = project.path.display().to_string();
+            let contracts = match super::open_existing(&project.path, backend) {
+                Ok(Some(storage)) => storage.query(&per_project),
+                Ok(None) => continue,
+                Err(e) => Err(e),
+            };
+            match contracts {
+                Ok(contracts) => {
+                    all.extend(contracts.into_iter().map(|contract| ProjectContract {
+                        project: project_name.clone(),
+                        contract,
+                    }))
+                }
+                Err(e) => errors.push(ProjectError {
+                    project: project_name,
+                    message: e.to_string(),
+                }),
+            }
+        }
+
+        match query.sort {
+            SortOrder::NewestFirst => {
+                all.sort_by_key(|pc| std::cmp::Reverse(pc.contract.created_at))
+            }
+            SortOrder::OldestFirst => all.sort_by_key(|pc| pc.contract.created_at),
+        }
+        let contracts = all
+            .into_iter()
+            .skip(query.offset)
+            .take(query.limit.unwrap_or(usize::MAX))
+            .collect();
+        Ok(AllContracts { contracts, errors })
+    }
+}
+
+/// Register `project` in the global registry if the registry is enabled.
+///
+/// Best-effort: registry failures never block local contract operations.
+pub fn register_if_enabled(project: &Path) {
+    if let Ok(Some(registry)) = Registry::open_if_enabled() {
+        let _ = registry.register(project);
+    }
+}
+
+/// Absolute, symlink-resolved path string used as the registry key
+pub(crate) fn canonical_string(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn parse_time(project: &str, field: &str, s: &str) -> Result<DateTime<Utc>, StorageError> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| StorageError::Corrupt {
+            id: project.to_string(),
+            field: field.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::ContractStatus;
+    use crate::storage::sqlite::SqliteStorage;
+    use crate::storage::{JsonlStorage, Storage};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_register_and_list() {
+        let registry = Registry::open_in_memory().unwrap();
+        let p1 = TempDir::new().unwrap();
+        let p2 = TempDir::new().unwrap();
+
+        registry.register(p1.path()).unwrap();
+        registry.register(p2.path()).unwrap();
+        // Re-registering does not duplicate
+        registry.register(p1.path()).unwrap();
+
+        let projects = registry.projects().unwrap();
+        assert_eq!(projects.len(), 2);
+    }
+
+    #[test]
+    fn test_unregister() {
+        let registry = Registry::open_in_memory().unwrap();
+        let p = TempDir::new().unwrap();
+
+        registry.register(p.path()).unwrap();
+        registry.unregister(p.path()).unwrap();
+        assert!(registry.projects().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_load_all_contracts_across_projects() {
+        let registry = Registry::open_in_memory().unwrap();
+        let p1 = TempDir::new().unwrap();
+        let p2 = TempDir::new().unwrap();
+
+        let db1 = SqliteStorage::open(p1.path()).unwrap();
+        db1.save_contract(&Contract::new("task in p1", "verify"))
+            .unwrap();
+        // p2 stores its contracts as JSONL
+        crate::storage::ensure_stead_dir(p2.path()).unwrap();
+        std::fs::write(Config::path(p2.path()), "[storage]\nbackend = \"jsonl\"\n").unwrap();
+        let db2 = JsonlStorage::new(p2.path());
+        let mut done = Contract::new("task in p2", "verify");
+        done.finish_verification(true, None);
+        db2.save_contract(&done).unwrap();
+
+        registry.register(p1.path()).unwrap();
+        registry.register(p2.path()).unwrap();
+
+        let all = registry
+            .load_all_contracts(&ContractQuery::new())
+            .unwrap()
+            .contracts;
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(
+            |pc| pc.project == canonical_string(p1.path()) && pc.contract.task == "task in p1"
+        ));
+
+        let completed = registry
+            .load_all_contracts(&ContractQuery::new().status(ContractStatus::Completed))
+            .unwrap()
+            .contracts;
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].contract.task, "task in p2");
+    }
+
+    #[test]
+    fn test_load_all_skips_missing_projects() {
+        let registry = Registry::open_in_memory().unwrap();
+        let p = TempDir::new().unwrap();
+        registry.register(p.path()).unwrap();
+
+        // No .stead directory in the project; reading leaves the registry alone
+        let all = registry.load_all_contracts(&ContractQuery::new()).unwrap();
+        assert!(all.contracts.is_empty());
+        assert!(all.errors.is_empty());
+        assert_eq!(registry.projects().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_load_all_reports_unreadable_projects() {
+        let registry = Registry::open_in_memory().unwrap();
+        let good = TempDir::new().unwrap();
+        let bad = TempDir::new().unwrap();
+
+        SqliteStorage::open(good.path())
+            .unwrap()
+            .save_contract(&Contract::new("task", "verify"))
+            .unwrap();
+        crate::storage::ensure_stead_dir(bad.path()).unwrap();
+        std::fs::write(bad.path().join(".stead/stead.db"), "not a database").unwrap();
+
+        registry.register(good.path()).unwrap();
+        registry.register(bad.path()).unwrap();
+
+        let all = registry.load_all_contracts(&ContractQuery::new()).unwrap();
+        assert_eq!(all.contracts.len(), 1);
+        assert_eq!(all.errors.len(), 1);
+        assert_eq!(all.errors[0].project, canonical_string(bad.path()));
+    }
+
+    #[test]
+    fn test_corrupt_timestamp_is_an_error() {
+        let registry = Registry::open_in_memory().unwrap();
+        let p = TempDir::new().unwrap();
+        registry.register(p.path()).unwrap();
+        registry
+            .conn
+            .execute("UPDATE projects SET last_seen = 'yesterday'", [])
+            .unwrap();
+
+        match registry.projects() {
+            Err(StorageError::Corrupt { field, .. }) => assert_eq!(field, "last_seen"),
+            other => panic!("expected Corrupt, got {:?}", other.map(|p| p.len())),
+        }
+    }
+
+    #[test]
+    fn test_project_contract_json_is_flat() {
+        let pc = ProjectContract {
+            project: "/home/user/app".to_string(),
+            contract: Contract::new("task", "verify"),
+        };
+        let json = serde_json::to_value(&pc).unwrap();
+        assert_eq!(json["project"], "/home/user/app");
+        assert_eq!(json["task"], "task");
+    }
+}
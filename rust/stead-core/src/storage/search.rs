@@ -0,0 +1,194 @@
+//! Full-text search over contracts
+//!
+//! SQLite answers queries from an FTS5 index over `task`, `verification`
+//! and `output` (see migration 6), ranked by BM25. Other backends fall back
+//! to `search_in_memory`, a plain term scan with the same result shape.
+
+use crate::schema::{Contract, ContractStatus};
+use serde::Serialize;
+
+/// Marker placed around matched terms in snippets
+pub const HIGHLIGHT: &str = "**";
+
+/// Words of context kept around the first match in a snippet
+pub(crate) const SNIPPET_WORDS: usize = 12;
+
+/// A search request: every term must match; filters narrow the result
+#[derive(Debug, Clone, Default)]
+pub struct SearchQuery {
+    pub text: String,
+    pub status: Option<ContractStatus>,
+    /// Contract must carry all of these tags
+    pub tags: Vec<String>,
+    pub limit: Option<usize>,
+}
+
+impl SearchQuery {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Search terms, split on whitespace
+    pub fn terms(&self) -> Vec<&str> {
+        self.text.split_whitespace().collect()
+    }
+
+    /// Whether `contract` passes the status and tag filters
+    pub fn accepts(&self, contract: &Contract) -> bool {
+        self.status.is_none_or(|s| contract.status == s)
+            && self.tags.iter().all(|t| contract.tags.contains(t))
+    }
+
+    /// FTS5 MATCH expression: each term quoted, so punctuation in user
+    /// input is never parsed as query syntax
+    pub(crate) fn fts_expression(&self) -> String {
+        self.terms()
+            .iter()
+            .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// One matching contract
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub contract: Contract,
+    /// Relevance; higher is better (only comparable within one search)
+    pub score: f64,
+    /// Excerpt of the best-matching field with terms wrapped in `HIGHLIGHT`
+    pub snippet: String,
+}
+
+/// Term-scan search for backends without an index
+pub fn search_in_memory(contracts: Vec<Contract>, query: &SearchQuery) -> Vec<SearchHit> {
+    let terms: Vec<String> = query.terms().iter().map(|t| t.to_lowercase()).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<SearchHit> = contracts
+        .into_iter()
+        .filter(|c| query.accepts(c))
+        .filter_map(|contract| {
+            let fields = [
+                contract.task.as_str(),
+                contract.verification.as_str(),
+                contract.output.as_deref().unwrap_or(""),
+            ];
+            let lowered: Vec<String> = fields.iter().map(|f| f.to_lowercase()).collect();
+            if !terms.iter().all(|t| lowered.iter().any(|f| f.contains(t))) {
+                return None;
+            }
+
+            let counts: Vec<usize> = lowered
+                .iter()
+                .map(|f| terms.iter().map(|t| f.matches(t.as_str()).count()).sum())
+                .collect();
+            let best = (0..fields.len()).max_by_key(|&i| counts[i]).unwrap_or(0);
+            let snippet = snippet(fields[best], &terms);
+            Some(SearchHit {
+                score: counts.iter().sum::<usize>() as f64,
+                snippet,
+                contract,
+            })
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.contract.created_at.cmp(&a.contract.created_at))
+    });
+    if let Some(limit) = query.limit {
+        hits.truncate(limit);
+    }
+    hits
+}
+
+/// Window of words around the first match, with matching words highlighted
+fn snippet(text: &str, terms: &[String]) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let is_match = |w: &str| {
+        let w = w.to_lowercase();
+        terms.iter().any(|t| w.contains(t.as_str()))
+    };
+
+    let first = words.iter().position(|w| is_match(w)).unwrap_or(0);
+    let start = first.saturating_sub(SNIPPET_WORDS / 2);
+    let end = (start + SNIPPET_WORDS).min(words.len());
+
+    let mut out: Vec<String> = words[start..end]
+        .iter()
+        .map(|w| {
+            if is_match(w) {
+                format!("{HIGHLIGHT}{w}{HIGHLIGHT}")
+            } else {
+                w.to_string()
+            }
+        })
+        .collect();
+    if start > 0 {
+        out.insert(0, "...".to_string());
+    }
+    if end < words.len() {
+        out.push("...".to_string());
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fts_expression_quotes_terms() {
+        let q = SearchQuery::new("rate-limit  say \"hi\"");
+        assert_eq!(q.fts_expression(), r#""rate-limit" "say" """hi""""#);
+    }
+
+    #[test]
+    fn test_in_memory_requires_all_terms() {
+        let a = Contract::new("Add rate limiting to the API", "cargo test");
+        let b = Contract::new("Add caching", "cargo test rate");
+        let c = Contract::new("Fix docs", "true");
+
+        let hits = search_in_memory(vec![a.clone(), b, c], &SearchQuery::new("RATE limiting"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].contract.id, a.id);
+        assert_eq!(hits[0].snippet, "Add **rate** **limiting** to the API");
+    }
+
+    #[test]
+    fn test_in_memory_filters() {
+        let mut tagged = Contract::new("rate limit", "true");
+        tagged.tags = vec!["api".to_string()];
+        let untagged = Contract::new("rate limit again", "true");
+
+        let mut query = SearchQuery::new("rate");
+        query.tags = vec!["api".to_string()];
+        let hits = search_in_memory(vec![tagged.clone(), untagged.clone()], &query);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].contract.id, tagged.id);
+
+        let mut query = SearchQuery::new("rate");
+        query.status = Some(ContractStatus::Completed);
+        assert!(search_in_memory(vec![tagged, untagged], &query).is_empty());
+    }
+
+    #[test]
+    fn test_snippet_window() {
+        let text = (0..40)
+            .map(|i| format!("w{}", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let s = snippet(&text, &["w20".to_string()]);
+        assert!(s.starts_with("... w14"));
+        assert!(s.contains("**w20**"));
+        assert!(s.ends_with("..."));
+    }
+}
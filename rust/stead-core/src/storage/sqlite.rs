@@ -2,82 +2,167 @@
 //!
 //! Default storage backend using .stead/stead.db
 
-use crate::schema::{Contract, ContractStatus};
-use crate::storage::StorageError;
+use crate::schema::{Approval, Contract, ContractStatus};
+use crate::storage::search::{SearchHit, SearchQuery, HIGHLIGHT, SNIPPET_WORDS};
+use crate::storage::{ContractQuery, Corruption, SortOrder, Storage, StorageError};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
 use std::path::{Path, PathBuf};
 
 const DB_FILE: &str = "stead.db";
 
+/// Column list shared by every contract SELECT (order matches `RawContract::from_row`)
+const CONTRACT_COLUMNS: &str = "id, task, verify_cmd, status, output, created_at, completed_at, owner, blocked_by, blocks, parent_id, await_children, requires_approval, approval, tags, claimed_at";
+
 /// SQLite storage backend
 pub struct SqliteStorage {
     conn: Connection,
+    /// Project root recorded on each saved contract (empty for in-memory DBs)
+    project_path: String,
 }
 
 impl SqliteStorage {
     /// Open (or create) the SQLite database at .stead/stead.db
     pub fn open(cwd: &Path) -> Result<Self, StorageError> {
+        let storage = Self::open_unmigrated(cwd)?;
+        storage.init_schema()?;
+        Ok(storage)
+    }
+
+    /// Open (or create) the database without applying pending migrations
+    pub fn open_unmigrated(cwd: &Path) -> Result<Self, StorageError> {
         let dir = super::ensure_stead_dir(cwd)?;
         let db_path = dir.join(DB_FILE);
         let conn = Connection::open(&db_path)
             .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
-        let storage = Self { conn };
-        storage.init_schema()?;
+        let storage = Self {
+            conn,
+            project_path: super::registry::canonical_string(cwd),
+        };
         Ok(storage)
     }
 
     /// Create an in-memory database (for tests)
     #[cfg(test)]
     pub fn open_in_memory() -> Result<Self, StorageError> {
-        let conn = Connection::open_in_memory().map_err(|e| {
-            StorageError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                e.to_string(),
-            ))
-        })?;
-        let storage = Self { conn };
+        let conn = Connection::open_in_memory()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        let storage = Self {
+            conn,
+            project_path: String::new(),
+        };
         storage.init_schema()?;
         Ok(storage)
     }
 
+    /// Project root this database belongs to
+    pub fn project_path(&self) -> &str {
+        &self.project_path
+    }
+
+    /// Schema version of the open database
+    pub fn schema_version(&self) -> Result<u32, StorageError> {
+        super::migrations::current_version(&self.conn)
+    }
+
+    /// Migrations not yet applied to this database
+    pub fn pending_migrations(
+        &self,
+    ) -> Result<Vec<&'static super::migrations::Migration>, StorageError> {
+        super::migrations::pending(&self.conn)
+    }
+
+    /// Apply pending migrations, returning the ones that ran
+    pub fn migrate(&self) -> Result<Vec<&'static super::migrations::Migration>, StorageError> {
+        super::migrations::migrate(&self.conn)
+    }
+
     fn init_schema(&self) -> Result<(), StorageError> {
-        self.conn
-            .execute_batch(
-                "CREATE TABLE IF NOT EXISTS contracts (
-                    id TEXT PRIMARY KEY,
-                    task TEXT NOT NULL,
-                    verify_cmd TEXT NOT NULL,
-                    status TEXT NOT NULL,
-                    output TEXT,
-                    created_at TEXT NOT NULL,
-                    completed_at TEXT,
-                    project_path TEXT NOT NULL DEFAULT '',
-                    owner TEXT,
-                    blocked_by TEXT NOT NULL DEFAULT '[]',
-                    blocks TEXT NOT NULL DEFAULT '[]'
-                );
-                CREATE INDEX IF NOT EXISTS idx_contracts_status ON contracts(status);
-                CREATE INDEX IF NOT EXISTS idx_contracts_project_path ON contracts(project_path);
-                CREATE INDEX IF NOT EXISTS idx_contracts_owner ON contracts(owner);",
-            )
+        self.migrate()?;
+        Ok(())
+    }
+
+    /// Select and strictly decode contracts matching a WHERE/ORDER clause
+    fn query_contracts<P: rusqlite::Params>(
+        &self,
+        clause: &str,
+        params: P,
+    ) -> Result<Vec<Contract>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM contracts {}",
+                CONTRACT_COLUMNS, clause
+            ))
             .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
 
-        // Migration: add new columns if they don't exist (for existing DBs)
-        for col in [
-            "owner TEXT",
-            "blocked_by TEXT NOT NULL DEFAULT '[]'",
-            "blocks TEXT NOT NULL DEFAULT '[]'",
-        ] {
-            let col_name = col.split_whitespace().next().unwrap();
-            let _ = self
-                .conn
-                .execute_batch(&format!("ALTER TABLE contracts ADD COLUMN {}", col));
-            // Ignore error — column already exists
-            let _ = col_name; // suppress unused warning
+        let rows = stmt
+            .query_map(params, RawContract::from_row)
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+
+        rows.into_iter().map(RawContract::decode).collect()
+    }
+
+    /// Scan every contract row and report fields that fail strict decoding
+    pub fn check_integrity(&self) -> Result<Vec<Corruption>, StorageError> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!(
+                "SELECT {} FROM contracts ORDER BY created_at",
+                CONTRACT_COLUMNS
+            ))
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+
+        let rows = stmt
+            .query_map([], RawContract::from_row)
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+
+        Ok(rows
+            .iter()
+            .flat_map(|raw| {
+                raw.invalid_fields()
+                    .into_iter()
+                    .map(|(field, value)| Corruption {
+                        id: raw.id.clone(),
+                        field: field.to_string(),
+                        value,
+                        repairable: repair_value(field).is_some(),
+                    })
+            })
+            .collect())
+    }
+
+    /// Reset repairable corrupt fields to their empty value.
+    ///
+    /// Only fields whose reset cannot invent state are touched (lists become
+    /// `[]`, `approval` and the optional timestamps become NULL); `status` and
+    /// `created_at` need a human. Returns the number of fields repaired.
+    pub fn repair(&self, corruptions: &[Corruption]) -> Result<usize, StorageError> {
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+
+        let mut repaired = 0;
+        for c in corruptions {
+            let Some(value) = repair_value(&c.field) else {
+                continue;
+            };
+            tx.execute(
+                &format!("UPDATE contracts SET {} = ?1 WHERE id = ?2", c.field),
+                params![value, c.id],
+            )
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+            repaired += 1;
         }
 
-        Ok(())
+        tx.commit()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        Ok(repaired)
     }
 
     /// Get the database file path for a project directory
@@ -86,12 +171,12 @@ impl SqliteStorage {
     }
 }
 
-impl super::Storage for SqliteStorage {
+impl Storage for SqliteStorage {
     fn save_contract(&self, contract: &Contract) -> Result<(), StorageError> {
         self.conn
             .execute(
-                "INSERT INTO contracts (id, task, verify_cmd, status, output, created_at, completed_at, project_path, owner, blocked_by, blocks)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                "INSERT INTO contracts (id, task, verify_cmd, status, output, created_at, completed_at, project_path, owner, blocked_by, blocks, parent_id, await_children, requires_approval, approval, tags, claimed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)",
                 params![
                     contract.id,
                     contract.task,
@@ -100,52 +185,43 @@ impl super::Storage for SqliteStorage {
                     contract.output,
                     contract.created_at.to_rfc3339(),
                     contract.completed_at.map(|dt| dt.to_rfc3339()),
-                    "",
+                    self.project_path,
                     contract.owner,
                     serde_json::to_string(&contract.blocked_by).unwrap_or_default(),
                     serde_json::to_string(&contract.blocks).unwrap_or_default(),
+                    contract.parent_id,
+                    contract.await_children,
+                    contract.requires_approval,
+                    approval_to_json(&contract.approval),
+                    serde_json::to_string(&contract.tags).unwrap_or_default(),
+                    contract.claimed_at.map(|dt| dt.to_rfc3339()),
                 ],
             )
-            .map_err(|e| {
-                StorageError::Io(std::io::Error::other(e.to_string()))
+            .map_err(|e| match e {
+                rusqlite::Error::SqliteFailure(err, _)
+                    if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+                {
+                    StorageError::AlreadyExists(contract.id.clone())
+                }
+                other => StorageError::Io(std::io::Error::other(other.to_string())),
             })?;
         Ok(())
     }
 
     fn load_contract(&self, id: &str) -> Result<Option<Contract>, StorageError> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT id, task, verify_cmd, status, output, created_at, completed_at, owner, blocked_by, blocks FROM contracts WHERE id = ?1")
-            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
-
-        let result = stmt
-            .query_row(params![id], row_to_contract)
-            .optional()
-            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
-
-        Ok(result)
+        let contracts = self.query_contracts("WHERE id = ?1", params![id])?;
+        Ok(contracts.into_iter().next())
     }
 
     fn load_all_contracts(&self) -> Result<Vec<Contract>, StorageError> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT id, task, verify_cmd, status, output, created_at, completed_at, owner, blocked_by, blocks FROM contracts ORDER BY created_at DESC")
-            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
-
-        let contracts = stmt
-            .query_map([], row_to_contract)
-            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?
-            .collect::<Result<Vec<_>, _>>()
-            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
-
-        Ok(contracts)
+        self.query_contracts("ORDER BY created_at DESC", [])
     }
 
     fn update_contract(&self, contract: &Contract) -> Result<(), StorageError> {
         let rows = self
             .conn
             .execute(
-                "UPDATE contracts SET task = ?1, verify_cmd = ?2, status = ?3, output = ?4, completed_at = ?5, owner = ?6, blocked_by = ?7, blocks = ?8 WHERE id = ?9",
+                "UPDATE contracts SET task = ?1, verify_cmd = ?2, status = ?3, output = ?4, completed_at = ?5, owner = ?6, blocked_by = ?7, blocks = ?8, parent_id = ?9, await_children = ?10, requires_approval = ?11, approval = ?12, tags = ?13, claimed_at = ?14 WHERE id = ?15",
                 params![
                     contract.task,
                     contract.verification,
@@ -155,6 +231,12 @@ impl super::Storage for SqliteStorage {
                     contract.owner,
                     serde_json::to_string(&contract.blocked_by).unwrap_or_default(),
                     serde_json::to_string(&contract.blocks).unwrap_or_default(),
+                    contract.parent_id,
+                    contract.await_children,
+                    contract.requires_approval,
+                    approval_to_json(&contract.approval),
+                    serde_json::to_string(&contract.tags).unwrap_or_default(),
+                    contract.claimed_at.map(|dt| dt.to_rfc3339()),
                     contract.id,
                 ],
             )
@@ -168,74 +250,323 @@ impl super::Storage for SqliteStorage {
         Ok(())
     }
 
-    fn filter_by_status(&self, status: &str) -> Result<Vec<Contract>, StorageError> {
+    fn delete_contract(&self, id: &str) -> Result<(), StorageError> {
+        let rows = self
+            .conn
+            .execute("DELETE FROM contracts WHERE id = ?1", params![id])
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        if rows == 0 {
+            return Err(StorageError::NotFound(id.to_string()));
+        }
+        Ok(())
+    }
+
+    fn transaction(
+        &self,
+        f: &mut dyn FnMut(&dyn Storage) -> Result<(), StorageError>,
+    ) -> Result<(), StorageError> {
+        let tx = self
+            .conn
+            .unchecked_transaction()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
+        // Dropping `tx` on error rolls back
+        f(self)?;
+        tx.commit()
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))
+    }
+
+    /// `PRAGMA data_version` moves when another connection commits, so a
+    /// watcher should use its own connection
+    fn data_version(&self) -> Result<Option<u64>, StorageError> {
+        self.conn
+            .query_row("PRAGMA data_version", [], |row| row.get::<_, i64>(0))
+            .map(|v| Some(v as u64))
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))
+    }
+
+    fn compact(&self) -> Result<(), StorageError> {
+        self.conn
+            .execute_batch("INSERT INTO contracts_fts(contracts_fts) VALUES('optimize'); VACUUM;")
+            .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))
+    }
+
+    fn query(&self, query: &ContractQuery) -> Result<Vec<Contract>, StorageError> {
+        // Anonymous `?` placeholders bind positionally, in push order
+        let mut conditions: Vec<String> = Vec::new();
+        let mut values: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+        if !query.statuses.is_empty() {
+            conditions.push(format!(
+                "status IN ({})",
+                vec!["?"; query.statuses.len()].join(", ")
+            ));
+            for status in &query.statuses {
+                values.push(Box::new(status.to_string()));
+            }
+        }
+        if let Some(owner) = &query.owner {
+            conditions.push("owner = ?".to_string());
+            values.push(Box::new(owner.clone()));
+        }
+        if let Some(parent_id) = &query.parent_id {
+            conditions.push("parent_id = ?".to_string());
+            values.push(Box::new(parent_id.clone()));
+        }
+        if let Some(after) = query.created_after {
+            conditions.push("created_at >= ?".to_string());
+            values.push(Box::new(after.to_rfc3339()));
+        }
+        if let Some(before) = query.created_before {
+            conditions.push("created_at < ?".to_string());
+            values.push(Box::new(before.to_rfc3339()));
+        }
+        for tag in &query.tags {
+            conditions.push("EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ?)".to_string());
+            values.push(Box::new(tag.clone()));
+        }
+
+        let mut clause = String::new();
+        if !conditions.is_empty() {
+            clause.push_str(&format!("WHERE {} ", conditions.join(" AND ")));
+        }
+        let order = match query.sort {
+            SortOrder::NewestFirst => "DESC",
+            SortOrder::OldestFirst => "ASC",
+        };
+        clause.push_str(&format!(
+            "ORDER BY created_at {} LIMIT {} OFFSET {}",
+            order,
+            query.limit.map(|l| l as i64).unwrap_or(-1),
+            query.offset
+        ));
+
+        self.query_contracts(&clause, rusqlite::params_from_iter(values))
+    }
+
+    fn search(&self, query: &SearchQuery) -> Result<Vec<SearchHit>, StorageError> {
+        if query.terms().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let columns = CONTRACT_COLUMNS
+            .split(", ")
+            .map(|c| format!("c.{}", c))
+            .collect::<Vec<_>>();
+        // Score and snippet follow the contract columns
+        let score_index = columns.len();
+        let columns = columns.join(", ");
         let mut stmt = self
             .conn
-            .prepare("SELECT id, task, verify_cmd, status, output, created_at, completed_at, owner, blocked_by, blocks FROM contracts WHERE status = ?1 ORDER BY created_at DESC")
+            .prepare(&format!(
+                "SELECT {}, bm25(contracts_fts), snippet(contracts_fts, -1, ?2, ?2, '...', ?3)
+                 FROM contracts_fts JOIN contracts c ON c.rowid = contracts_fts.rowid
+                 WHERE contracts_fts MATCH ?1 AND (?4 IS NULL OR c.status = ?4)
+                 ORDER BY bm25(contracts_fts), c.created_at DESC",
+                columns
+            ))
             .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
 
-        let contracts = stmt
-            .query_map(params![status], row_to_contract)
+        let status = query.status.map(|s| s.to_string());
+        let rows = stmt
+            .query_map(
+                params![
+                    query.fts_expression(),
+                    HIGHLIGHT,
+                    SNIPPET_WORDS as i64,
+                    status
+                ],
+                |row| {
+                    Ok((
+                        RawContract::from_row(row)?,
+                        row.get::<_, f64>(score_index)?,
+                        row.get::<_, String>(score_index + 1)?,
+                    ))
+                },
+            )
             .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?
             .collect::<Result<Vec<_>, _>>()
             .map_err(|e| StorageError::Io(std::io::Error::other(e.to_string())))?;
 
-        Ok(contracts)
+        let mut hits = Vec::new();
+        for (raw, bm25, snippet) in rows {
+            let contract = raw.decode()?;
+            if query.accepts(&contract) {
+                // bm25() is lower-is-better; flip it so scores read naturally
+                hits.push(SearchHit {
+                    contract,
+                    score: -bm25,
+                    snippet,
+                });
+            }
+        }
+        if let Some(limit) = query.limit {
+            hits.truncate(limit);
+        }
+        Ok(hits)
+    }
+}
+
+/// Safe replacement value for a corrupt field, if there is one
+fn repair_value(field: &str) -> Option<Option<&'static str>> {
+    match field {
+        "blocked_by" | "blocks" | "tags" => Some(Some("[]")),
+        "approval" | "completed_at" | "claimed_at" => Some(None),
+        _ => None,
+    }
+}
+
+/// A `contracts` row as stored, before validation
+struct RawContract {
+    id: String,
+    task: String,
+    verification: String,
+    status: String,
+    output: Option<String>,
+    created_at: String,
+    completed_at: Option<String>,
+    owner: Option<String>,
+    blocked_by: Option<String>,
+    blocks: Option<String>,
+    parent_id: Option<String>,
+    await_children: bool,
+    requires_approval: bool,
+    approval: Option<String>,
+    tags: Option<String>,
+    claimed_at: Option<String>,
+}
+
+impl RawContract {
+    /// Read a row selected with `CONTRACT_COLUMNS`
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            task: row.get(1)?,
+            verification: row.get(2)?,
+            status: row.get(3)?,
+            output: row.get(4)?,
+            created_at: row.get(5)?,
+            completed_at: row.get(6)?,
+            owner: row.get(7)?,
+            blocked_by: row.get(8)?,
+            blocks: row.get(9)?,
+            parent_id: row.get(10)?,
+            await_children: row.get(11)?,
+            requires_approval: row.get(12)?,
+            approval: row.get(13)?,
+            tags: row.get(14)?,
+            claimed_at: row.get(15)?,
+        })
+    }
+
+    /// Every field that fails to decode, with its stored value
+    fn invalid_fields(&self) -> Vec<(&'static str, Option<String>)> {
+        let mut invalid = Vec::new();
+        if decode_status(&self.status).is_none() {
+            invalid.push(("status", Some(self.status.clone())));
+        }
+        if decode_time(&self.created_at).is_none() {
+            invalid.push(("created_at", Some(self.created_at.clone())));
+        }
+        for (field, value) in [
+            ("completed_at", &self.completed_at),
+            ("claimed_at", &self.claimed_at),
+        ] {
+            if decode_optional(value, decode_time).is_none() {
+                invalid.push((field, value.clone()));
+            }
+        }
+        for (field, value) in [
+            ("blocked_by", &self.blocked_by),
+            ("blocks", &self.blocks),
+            ("tags", &self.tags),
+        ] {
+            if decode_list(value).is_none() {
+                invalid.push((field, value.clone()));
+            }
+        }
+        if decode_optional(&self.approval, decode_approval).is_none() {
+            invalid.push(("approval", self.approval.clone()));
+        }
+        invalid
+    }
+
+    /// Strictly decode into a Contract, failing on the first invalid field
+    fn decode(self) -> Result<Contract, StorageError> {
+        let id = self.id;
+        let corrupt = |field: &str| StorageError::Corrupt {
+            id: id.clone(),
+            field: field.to_string(),
+        };
+
+        let status = decode_status(&self.status).ok_or_else(|| corrupt("status"))?;
+        let created_at = decode_time(&self.created_at).ok_or_else(|| corrupt("created_at"))?;
+        let completed_at = decode_optional(&self.completed_at, decode_time)
+            .ok_or_else(|| corrupt("completed_at"))?;
+        let claimed_at =
+            decode_optional(&self.claimed_at, decode_time).ok_or_else(|| corrupt("claimed_at"))?;
+        let blocked_by = decode_list(&self.blocked_by).ok_or_else(|| corrupt("blocked_by"))?;
+        let blocks = decode_list(&self.blocks).ok_or_else(|| corrupt("blocks"))?;
+        let tags = decode_list(&self.tags).ok_or_else(|| corrupt("tags"))?;
+        let approval =
+            decode_optional(&self.approval, decode_approval).ok_or_else(|| corrupt("approval"))?;
+
+        Ok(Contract {
+            id,
+            task: self.task,
+            verification: self.verification,
+            status,
+            created_at,
+            completed_at,
+            output: self.output,
+            owner: self.owner,
+            claimed_at,
+            blocked_by,
+            blocks,
+            parent_id: self.parent_id,
+            await_children: self.await_children,
+            requires_approval: self.requires_approval,
+            approval,
+            tags,
+        })
     }
 }
 
-/// Parse a rusqlite Row into a Contract
-fn row_to_contract(row: &rusqlite::Row) -> rusqlite::Result<Contract> {
-    let id: String = row.get(0)?;
-    let task: String = row.get(1)?;
-    let verification: String = row.get(2)?;
-    let status_str: String = row.get(3)?;
-    let output: Option<String> = row.get(4)?;
-    let created_at_str: String = row.get(5)?;
-    let completed_at_str: Option<String> = row.get(6)?;
-    let owner: Option<String> = row.get(7)?;
-    let blocked_by_str: String = row
-        .get::<_, Option<String>>(8)?
-        .unwrap_or_else(|| "[]".to_string());
-    let blocks_str: String = row
-        .get::<_, Option<String>>(9)?
-        .unwrap_or_else(|| "[]".to_string());
-
-    let status = status_str
-        .parse::<ContractStatus>()
-        .unwrap_or(ContractStatus::Pending);
-
-    let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+fn decode_status(s: &str) -> Option<ContractStatus> {
+    s.parse().ok()
+}
+
+fn decode_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
         .map(|dt| dt.with_timezone(&Utc))
-        .unwrap_or_else(|_| Utc::now());
-
-    let completed_at = completed_at_str.and_then(|s| {
-        DateTime::parse_from_rfc3339(&s)
-            .map(|dt| dt.with_timezone(&Utc))
-            .ok()
-    });
-
-    let blocked_by: Vec<String> = serde_json::from_str(&blocked_by_str).unwrap_or_default();
-    let blocks: Vec<String> = serde_json::from_str(&blocks_str).unwrap_or_default();
-
-    Ok(Contract {
-        id,
-        task,
-        verification,
-        status,
-        created_at,
-        completed_at,
-        output,
-        owner,
-        blocked_by,
-        blocks,
-    })
+        .ok()
+}
+
+/// JSON array of strings; NULL is invalid (the list columns are NOT NULL)
+fn decode_list(s: &Option<String>) -> Option<Vec<String>> {
+    s.as_deref().and_then(|s| serde_json::from_str(s).ok())
+}
+
+fn decode_approval(s: &str) -> Option<Approval> {
+    serde_json::from_str(s).ok()
+}
+
+/// Decode a nullable column: `Some(None)` for NULL, `None` when invalid
+fn decode_optional<T>(s: &Option<String>, decode: fn(&str) -> Option<T>) -> Option<Option<T>> {
+    match s {
+        None => Some(None),
+        Some(s) => decode(s).map(Some),
+    }
+}
+
+/// Serialize an approval decision for the `approval` column
+fn approval_to_json(approval: &Option<Approval>) -> Option<String> {
+    approval
+        .as_ref()
+        .and_then(|a| serde_json::to_string(a).ok())
 }
 
 /// Import contracts from JSONL file into SQLite
 pub fn migrate_from_jsonl(cwd: &Path) -> Result<usize, StorageError> {
-    use super::Storage;
-
     let jsonl_path = super::get_contracts_path(cwd);
     if !jsonl_path.exists() {
         return Ok(0);
@@ -252,34 +583,24 @@ pub fn migrate_from_jsonl(cwd: &Path) -> Result<usize, StorageError> {
     }
 
     let storage = SqliteStorage::open(cwd)?;
-    let count = contracts.len();
-    for contract in &contracts {
-        storage.save_contract(contract)?;
-    }
+    storage.transaction(&mut |tx| {
+        for contract in &contracts {
+            tx.save_contract(contract)?;
+        }
+        Ok(())
+    })?;
 
-    Ok(count)
+    Ok(contracts.len())
 }
 
 /// Convenience: get the default storage for a project directory.
-/// Auto-migrates from JSONL if needed.
+/// Auto-migrates from JSONL if needed and registers the project in the
+/// global registry when it is enabled.
 pub fn open_default(cwd: &Path) -> Result<SqliteStorage, StorageError> {
     migrate_from_jsonl(cwd)?;
-    SqliteStorage::open(cwd)
-}
-
-// Need the optional() method on rusqlite results
-trait OptionalExt<T> {
-    fn optional(self) -> Result<Option<T>, rusqlite::Error>;
-}
-
-impl<T> OptionalExt<T> for Result<T, rusqlite::Error> {
-    fn optional(self) -> Result<Option<T>, rusqlite::Error> {
-        match self {
-            Ok(v) => Ok(Some(v)),
-            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-            Err(e) => Err(e),
-        }
-    }
+    let storage = SqliteStorage::open(cwd)?;
+    super::registry::register_if_enabled(cwd);
+    Ok(storage)
 }
 
 #[cfg(test)]
@@ -340,7 +661,7 @@ mod tests {
         let mut contract = Contract::new("task", "verify");
         db.save_contract(&contract).unwrap();
 
-        contract.complete(true, Some("All good".to_string()));
+        contract.finish_verification(true, Some("All good".to_string()));
         db.update_contract(&contract).unwrap();
 
         let loaded = db.load_contract(&contract.id).unwrap().unwrap();
@@ -359,25 +680,95 @@ mod tests {
     }
 
     #[test]
-    fn test_filter_by_status() {
+    fn test_query_by_status() {
         let db = SqliteStorage::open_in_memory().unwrap();
 
         let c1 = Contract::new("pending task", "verify");
         db.save_contract(&c1).unwrap();
 
         let mut c2 = Contract::new("completed task", "verify");
-        c2.complete(true, None);
+        c2.finish_verification(true, None);
         db.save_contract(&c2).unwrap();
 
-        let pending = db.filter_by_status("pending").unwrap();
+        let pending = db
+            .query(&ContractQuery::new().status(ContractStatus::Pending))
+            .unwrap();
         assert_eq!(pending.len(), 1);
         assert_eq!(pending[0].task, "pending task");
 
-        let completed = db.filter_by_status("completed").unwrap();
+        let completed = db
+            .query(&ContractQuery::new().status(ContractStatus::Completed))
+            .unwrap();
         assert_eq!(completed.len(), 1);
         assert_eq!(completed[0].task, "completed task");
     }
 
+    #[test]
+    fn test_transaction_rolls_back_on_error() {
+        let db = SqliteStorage::open_in_memory().unwrap();
+        let existing = Contract::new("existing", "verify");
+        db.save_contract(&existing).unwrap();
+
+        let result = db.transaction(&mut |tx| {
+            tx.save_contract(&Contract::new("new", "verify"))?;
+            tx.save_contract(&existing)
+        });
+        assert!(matches!(result, Err(StorageError::AlreadyExists(_))));
+        assert_eq!(db.load_all_contracts().unwrap().len(), 1);
+
+        // The connection is usable for another transaction afterwards
+        db.transaction(&mut |tx| tx.save_contract(&Contract::new("new", "verify")))
+            .unwrap();
+        assert_eq!(db.load_all_contracts().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_parent_and_children() {
+        let db = SqliteStorage::open_in_memory().unwrap();
+
+        let mut parent = Contract::new("parent", "verify");
+        parent.await_children = true;
+        db.save_contract(&parent).unwrap();
+
+        let c1 = parent.spawn_child("child 1", "verify");
+        db.save_contract(&c1).unwrap();
+        let c2 = parent.spawn_child("child 2", "verify");
+        db.save_contract(&c2).unwrap();
+        db.save_contract(&Contract::new("unrelated", "verify"))
+            .unwrap();
+
+        let loaded = db.load_contract(&parent.id).unwrap().unwrap();
+        assert!(loaded.await_children);
+        assert!(loaded.parent_id.is_none());
+
+        let children = db.load_children(&parent.id).unwrap();
+        assert_eq!(children.len(), 2);
+        assert!(children
+            .iter()
+            .all(|c| c.parent_id.as_deref() == Some(parent.id.as_str())));
+    }
+
+    #[test]
+    fn test_approval_roundtrip() {
+        let db = SqliteStorage::open_in_memory().unwrap();
+        let mut contract = Contract::new("release", "verify");
+        contract.requires_approval = true;
+        db.save_contract(&contract).unwrap();
+
+        contract.finish_verification(true, None);
+        db.update_contract(&contract).unwrap();
+        let loaded = db.load_contract(&contract.id).unwrap().unwrap();
+        assert!(loaded.requires_approval);
+        assert_eq!(loaded.status, ContractStatus::AwaitingApproval);
+        assert!(loaded.approval.is_none());
+
+        contract.reject("alice", "not yet").unwrap();
+        db.update_contract(&contract).unwrap();
+        let loaded = db.load_contract(&contract.id).unwrap().unwrap();
+        assert_eq!(loaded.status, ContractStatus::Failed);
+        assert_eq!(loaded.approval, contract.approval);
+    }
+
     #[test]
     fn test_migration_from_jsonl() {
         use tempfile::TempDir;
@@ -419,6 +810,75 @@ mod tests {
         assert_eq!(count, 0);
     }
 
+    fn corrupt(db: &SqliteStorage, id: &str, column: &str, value: Option<&str>) {
+        db.conn
+            .execute(
+                &format!("UPDATE contracts SET {} = ?1 WHERE id = ?2", column),
+                params![value, id],
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_rows_are_reported() {
+        let contract = Contract::new("task", "verify");
+
+        for (column, value) in [
+            ("status", Some("bogus")),
+            ("created_at", Some("yesterday")),
+            ("completed_at", Some("soon")),
+            ("claimed_at", Some("earlier")),
+            ("blocked_by", Some("not json")),
+            ("tags", Some(r#"{"a": 1}"#)),
+            ("approval", Some("{}")),
+        ] {
+            let db = SqliteStorage::open_in_memory().unwrap();
+            db.save_contract(&contract).unwrap();
+            corrupt(&db, &contract.id, column, value);
+
+            match db.load_contract(&contract.id) {
+                Err(StorageError::Corrupt { id, field }) => {
+                    assert_eq!(id, contract.id);
+                    assert_eq!(field, column);
+                }
+                other => panic!("{}: expected Corrupt, got {:?}", column, other),
+            }
+            assert!(db.load_all_contracts().is_err());
+        }
+    }
+
+    #[test]
+    fn test_check_and_repair() {
+        let db = SqliteStorage::open_in_memory().unwrap();
+        let good = Contract::new("good", "verify");
+        let bad = Contract::new("bad", "verify");
+        db.save_contract(&good).unwrap();
+        db.save_contract(&bad).unwrap();
+        corrupt(&db, &bad.id, "blocks", Some("[1, 2"));
+        corrupt(&db, &bad.id, "status", Some("unknown"));
+
+        let problems = db.check_integrity().unwrap();
+        assert_eq!(problems.len(), 2);
+        assert!(problems.iter().all(|p| p.id == bad.id));
+        let blocks = problems.iter().find(|p| p.field == "blocks").unwrap();
+        assert!(blocks.repairable);
+        assert_eq!(blocks.value.as_deref(), Some("[1, 2"));
+        assert!(
+            !problems
+                .iter()
+                .find(|p| p.field == "status")
+                .unwrap()
+                .repairable
+        );
+
+        assert_eq!(db.repair(&problems).unwrap(), 1);
+
+        let remaining = db.check_integrity().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].field, "status");
+        assert!(db.load_contract(&good.id).unwrap().is_some());
+    }
+
     #[test]
     fn test_concurrent_reads() {
         let db = SqliteStorage::open_in_memory().unwrap();
@@ -436,6 +896,32 @@ mod tests {
         assert_eq!(r3.len(), 1);
     }
 
+    #[test]
+    fn test_search_ranks_and_stems() {
+        use crate::storage::search::SearchQuery;
+
+        let db = SqliteStorage::open_in_memory().unwrap();
+        let strong = Contract::new("Rate limit the rate limiter", "cargo test limits");
+        let weak = Contract::new("Tune caching", "grep -q 'rate limiting' log");
+        db.save_contract(&weak).unwrap();
+        db.save_contract(&strong).unwrap();
+        db.save_contract(&Contract::new("Unrelated", "true"))
+            .unwrap();
+
+        // "limiting" stems to "limit"
+        let hits = db.search(&SearchQuery::new("rate limiting")).unwrap();
+        let ids: Vec<&str> = hits.iter().map(|h| h.contract.id.as_str()).collect();
+        assert_eq!(ids, vec![strong.id.as_str(), weak.id.as_str()]);
+        assert!(hits[0].score > hits[1].score);
+        assert!(hits[0].snippet.contains("**Rate**"));
+
+        // Query syntax in user input is treated as text
+        assert!(db
+            .search(&SearchQuery::new("rate OR ("))
+            .unwrap()
+            .is_empty());
+    }
+
     #[test]
     fn test_open_default() {
         use tempfile::TempDir;
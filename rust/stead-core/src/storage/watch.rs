@@ -0,0 +1,199 @@
+//! Contract change notifications
+//!
+//! `ContractWatcher` keeps a snapshot of a project's contracts and turns
+//! each change into a `ContractEvent`. Polling is cheap: the full reload
+//! and diff only happen when `Storage::data_version` moves (SQLite's
+//! `PRAGMA data_version`, a hash of the JSONL file).
+
+use crate::schema::{Contract, ContractStatus};
+use crate::storage::{Storage, StorageError};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::time::Duration;
+
+/// Default pause between polls
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A change to one contract
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum ContractEvent {
+    Created {
+        contract: Contract,
+    },
+    Updated {
+        contract: Contract,
+        /// Status before the change (equal to the current one for edits
+        /// that didn't transition)
+        previous_status: ContractStatus,
+    },
+    Deleted {
+        id: String,
+    },
+}
+
+impl ContractEvent {
+    /// ID of the contract the event is about
+    pub fn contract_id(&self) -> &str {
+        match self {
+            ContractEvent::Created { contract } | ContractEvent::Updated { contract, .. } => {
+                &contract.id
+            }
+            ContractEvent::Deleted { id } => id,
+        }
+    }
+}
+
+/// Snapshot of a project's contracts that reports what changed since the
+/// last poll
+pub struct ContractWatcher {
+    version: Option<u64>,
+    known: HashMap<String, Contract>,
+}
+
+impl ContractWatcher {
+    /// Start watching from the current state; existing contracts produce
+    /// no events
+    pub fn new(storage: &dyn Storage) -> Result<Self, StorageError> {
+        let version = storage.data_version()?;
+        let known = snapshot(storage.load_all_contracts()?);
+        Ok(Self { version, known })
+    }
+
+    /// Events for every change since the previous poll, oldest contract
+    /// first
+    pub fn poll(&mut self, storage: &dyn Storage) -> Result<Vec<ContractEvent>, StorageError> {
+        let version = storage.data_version()?;
+        if version.is_some() && version == self.version {
+            return Ok(Vec::new());
+        }
+        self.version = version;
+
+        let current = snapshot(storage.load_all_contracts()?);
+        let events = diff(&self.known, &current);
+        self.known = current;
+        Ok(events)
+    }
+}
+
+/// Poll `storage` every `interval`, passing each non-empty batch of events
+/// to `on_events` until it returns `ControlFlow::Break`
+pub fn watch(
+    storage: &dyn Storage,
+    interval: Duration,
+    on_events: &mut dyn FnMut(&[ContractEvent]) -> ControlFlow<()>,
+) -> Result<(), StorageError> {
+    let mut watcher = ContractWatcher::new(storage)?;
+    loop {
+        std::thread::sleep(interval);
+        let events = watcher.poll(storage)?;
+        if !events.is_empty() && on_events(&events).is_break() {
+            return Ok(());
+        }
+    }
+}
+
+fn snapshot(contracts: Vec<Contract>) -> HashMap<String, Contract> {
+    contracts.into_iter().map(|c| (c.id.clone(), c)).collect()
+}
+
+/// Events turning `old` into `new`
+fn diff(old: &HashMap<String, Contract>, new: &HashMap<String, Contract>) -> Vec<ContractEvent> {
+    let mut changed: Vec<&Contract> = new
+        .values()
+        .filter(|c| old.get(&c.id) != Some(*c))
+        .collect();
+    changed.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+
+    let mut events: Vec<ContractEvent> = changed
+        .into_iter()
+        .map(|c| match old.get(&c.id) {
+            Some(before) => ContractEvent::Updated {
+                contract: c.clone(),
+                previous_status: before.status,
+            },
+            None => ContractEvent::Created {
+                contract: c.clone(),
+            },
+        })
+        .collect();
+
+    let mut deleted: Vec<&String> = old.keys().filter(|id| !new.contains_key(*id)).collect();
+    deleted.sort();
+    events.extend(
+        deleted
+            .into_iter()
+            .map(|id| ContractEvent::Deleted { id: id.clone() }),
+    );
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::sqlite::SqliteStorage;
+    use crate::storage::JsonlStorage;
+    use tempfile::TempDir;
+
+    /// Create, transition and delete through `writer`; observe through
+    /// `reader` (a separate handle, as another process would have)
+    fn check_events(writer: &dyn Storage, reader: &dyn Storage) {
+        let mut watcher = ContractWatcher::new(reader).unwrap();
+        assert!(watcher.poll(reader).unwrap().is_empty());
+
+        let mut c = Contract::new("task", "true");
+        writer.save_contract(&c).unwrap();
+        let events = watcher.poll(reader).unwrap();
+        assert!(
+            matches!(&events[..], [ContractEvent::Created { contract }] if contract.id == c.id)
+        );
+        assert!(watcher.poll(reader).unwrap().is_empty());
+
+        c.mark_ready().unwrap();
+        writer.update_contract(&c).unwrap();
+        let events = watcher.poll(reader).unwrap();
+        match &events[..] {
+            [ContractEvent::Updated {
+                contract,
+                previous_status,
+            }] => {
+                assert_eq!(contract.status, ContractStatus::Ready);
+                assert_eq!(*previous_status, ContractStatus::Pending);
+            }
+            other => panic!("expected one update, got {:?}", other),
+        }
+
+        writer.delete_contract(&c.id).unwrap();
+        let events = watcher.poll(reader).unwrap();
+        assert_eq!(events, vec![ContractEvent::Deleted { id: c.id.clone() }]);
+    }
+
+    #[test]
+    fn test_sqlite_events_across_connections() {
+        let tmp = TempDir::new().unwrap();
+        let writer = SqliteStorage::open(tmp.path()).unwrap();
+        let reader = SqliteStorage::open(tmp.path()).unwrap();
+        check_events(&writer, &reader);
+    }
+
+    #[test]
+    fn test_jsonl_events() {
+        let tmp = TempDir::new().unwrap();
+        let writer = JsonlStorage::new(tmp.path());
+        let reader = JsonlStorage::new(tmp.path());
+        check_events(&writer, &reader);
+    }
+
+    #[test]
+    fn test_event_json() {
+        let event = ContractEvent::Deleted {
+            id: "abc".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"deleted","id":"abc"}"#
+        );
+        assert_eq!(event.contract_id(), "abc");
+    }
+}
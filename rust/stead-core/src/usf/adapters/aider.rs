@@ -0,0 +1,683 @@
+//! Aider session adapter
+//!
+//! Parses `.aider.chat.history.md` in project directories, with input
+//! timestamps from `.aider.input.history` next to it. Aider keeps one
+//! history per repo rather than a central store, so the adapter looks in
+//! candidate roots (see `candidate_project_roots`). Each "# aider chat
+//! started at" block is one session.
+//!
+//! In the markdown, `#### ` lines are user input, `> ` lines are aider's
+//! own output and everything else is the model's reply. "Applied edit to
+//! <file>" notices become `Edit` tool calls, and `/run`, `/test` and `!`
+//! commands become `Bash` calls whose result is the output that follows.
+//! "Tokens: ... Cost: ..." notices give each reply's usage and cost; aider
+//! rounds the counts (`2.1k`), so totals are approximate.
+
+use super::{path_hash, AdapterError, SessionAdapter};
+use crate::usf::{
+    AssistantMessage, CliType, ModelInfo, ProjectInfo, SessionMetadata, SessionSource,
+    SessionSummary, SystemMessage, TimelineEntry, TokenUsage, ToolCall, ToolResult,
+    UniversalSession, UniversalTool, UserMessage, USF_VERSION,
+};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CHAT_HISTORY_FILE: &str = ".aider.chat.history.md";
+const INPUT_HISTORY_FILE: &str = ".aider.input.history";
+const CHAT_START_PREFIX: &str = "# aider chat started at ";
+const APPLIED_EDIT_PREFIX: &str = "Applied edit to ";
+const TOKENS_PREFIX: &str = "Tokens: ";
+
+/// Session IDs embed the start time in this format
+const STAMP_FORMAT: &str = "%Y%m%d%H%M%S";
+
+/// Aider session adapter
+pub struct AiderAdapter {
+    /// Project roots that have a chat history
+    roots: Vec<PathBuf>,
+}
+
+impl AiderAdapter {
+    /// Create a new adapter if any known project has an Aider chat history
+    pub fn new() -> Option<Self> {
+        Self::with_roots(super::candidate_project_roots())
+    }
+
+    /// Create an adapter over the given project roots instead of the
+    /// candidate ones, if any of them has a chat history
+    pub fn with_roots(roots: Vec<PathBuf>) -> Option<Self> {
+        let roots: Vec<PathBuf> = roots
+            .into_iter()
+            .filter(|root| root.join(CHAT_HISTORY_FILE).is_file())
+            .collect();
+        if roots.is_empty() {
+            None
+        } else {
+            Some(Self { roots })
+        }
+    }
+
+    /// Parse every session in a chat history file
+    fn parse_history_file(&self, path: &Path) -> Result<Vec<UniversalSession>, AdapterError> {
+        let root = path.parent().unwrap_or(Path::new("/"));
+        let text = fs::read_to_string(path)?;
+        let inputs = fs::read_to_string(root.join(INPUT_HISTORY_FILE))
+            .map(|s| parse_input_history(&s))
+            .unwrap_or_default();
+        let modified = fs::metadata(path)
+            .and_then(|m| m.modified())
+            .map(DateTime::<Utc>::from)
+            .unwrap_or_else(|_| Utc::now());
+
+        let chats = split_chats(&text);
+        let last = chats.len().saturating_sub(1);
+        Ok(chats
+            .into_iter()
+            .enumerate()
+            .map(|(i, chat)| {
+                // Aider is still appending to the newest chat
+                let end = (i == last).then_some(modified);
+                build_session(root, &chat, &inputs, end)
+            })
+            .filter(|s| !s.timeline.is_empty())
+            .collect())
+    }
+}
+
+impl SessionAdapter for AiderAdapter {
+    fn cli_type(&self) -> CliType {
+        CliType::Aider
+    }
+
+    fn is_available(&self) -> bool {
+        !self.roots.is_empty()
+    }
+
+    fn base_dir(&self) -> Option<PathBuf> {
+        None // Histories live in each project
+    }
+
+    fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError> {
+        Ok(self
+            .roots
+            .iter()
+            .map(|root| root.join(CHAT_HISTORY_FILE))
+            .filter(|path| path.is_file())
+            .collect())
+    }
+
+    fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError> {
+        Ok(self
+            .parse_history_file(path)?
+            .iter()
+            .map(SessionSummary::from)
+            .collect())
+    }
+
+    fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError> {
+        // ID format: aider-{root hash prefix}-{start stamp}
+        let session_id = id.strip_prefix("aider-").unwrap_or(id);
+        let (root_key, _stamp) = session_id
+            .split_once('-')
+            .ok_or_else(|| AdapterError::NotFound(id.to_string()))?;
+
+        for root in &self.roots {
+            if !path_hash(&root.to_string_lossy()).starts_with(root_key) {
+                continue;
+            }
+            let sessions = self.parse_history_file(&root.join(CHAT_HISTORY_FILE))?;
+            if let Some(session) = sessions
+                .into_iter()
+                .find(|s| s.source.original_id.as_deref() == Some(session_id))
+            {
+                return Ok(session);
+            }
+        }
+
+        Err(AdapterError::NotFound(id.to_string()))
+    }
+}
+
+/// One "# aider chat started at" block of the history
+struct Chat<'a> {
+    started: DateTime<Utc>,
+    lines: Vec<&'a str>,
+}
+
+/// Split a history into chats; anything before the first header is ignored
+fn split_chats(text: &str) -> Vec<Chat<'_>> {
+    let mut chats: Vec<Chat> = Vec::new();
+    for line in text.lines() {
+        if let Some(started) = line.strip_prefix(CHAT_START_PREFIX) {
+            if let Some(started) = parse_local_time(started.trim(), "%Y-%m-%d %H:%M:%S") {
+                chats.push(Chat {
+                    started,
+                    lines: Vec::new(),
+                });
+                continue;
+            }
+        }
+        if let Some(chat) = chats.last_mut() {
+            chat.lines.push(line);
+        }
+    }
+    chats
+}
+
+/// Entries of `.aider.input.history`: a `# <timestamp>` line followed by
+/// the input, each line prefixed with `+`
+fn parse_input_history(text: &str) -> Vec<(DateTime<Utc>, String)> {
+    let mut inputs: Vec<(DateTime<Utc>, String)> = Vec::new();
+    let mut current: Option<(DateTime<Utc>, Vec<&str>)> = None;
+
+    for line in text.lines() {
+        if let Some(ts) = line.strip_prefix("# ") {
+            if let Some((ts, lines)) = current.take() {
+                inputs.push((ts, lines.join("\n")));
+            }
+            current =
+                parse_local_time(ts.trim(), "%Y-%m-%d %H:%M:%S%.f").map(|ts| (ts, Vec::new()));
+        } else if let Some(input) = line.strip_prefix('+') {
+            if let Some((_, lines)) = current.as_mut() {
+                lines.push(input);
+            }
+        }
+    }
+    if let Some((ts, lines)) = current {
+        inputs.push((ts, lines.join("\n")));
+    }
+    inputs
+}
+
+/// Aider writes local wall-clock times
+fn parse_local_time(s: &str, format: &str) -> Option<DateTime<Utc>> {
+    let naive = NaiveDateTime::parse_from_str(s, format).ok()?;
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum BlockKind {
+    User,
+    Aider,
+    Model,
+}
+
+/// Consecutive lines of one kind, with their markdown prefixes removed;
+/// blank lines belong to the block they follow, and fenced code (edit
+/// blocks contain `>>>>>>> REPLACE`) always belongs to the model
+fn blocks<'a>(lines: &[&'a str]) -> Vec<(BlockKind, Vec<&'a str>)> {
+    let mut blocks: Vec<(BlockKind, Vec<&str>)> = Vec::new();
+    let mut in_fence = false;
+    for line in lines {
+        let (kind, content) = if in_fence {
+            (BlockKind::Model, *line)
+        } else if let Some(rest) = line.strip_prefix("####") {
+            (BlockKind::User, rest.strip_prefix(' ').unwrap_or(rest))
+        } else if *line == ">" || line.starts_with("> ") {
+            // Aider ends these lines with two spaces (markdown line breaks)
+            (
+                BlockKind::Aider,
+                line[1..].trim_start_matches(' ').trim_end(),
+            )
+        } else if line.trim().is_empty() {
+            if let Some((_, lines)) = blocks.last_mut() {
+                lines.push("");
+            }
+            continue;
+        } else {
+            (BlockKind::Model, *line)
+        };
+        if kind == BlockKind::Model && line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+
+        match blocks.last_mut() {
+            Some((last, lines)) if *last == kind => lines.push(content),
+            _ => blocks.push((kind, vec![content])),
+        }
+    }
+    blocks
+}
+
+/// Chat command and shell command of a `/run`, `/test` or `!` input
+fn run_command(input: &str) -> Option<(&'static str, &str)> {
+    ["/run", "/test", "!"].iter().find_map(|prefix| {
+        let command = input.strip_prefix(prefix)?;
+        // "/runner" isn't "/run"
+        if *prefix != "!" && !command.starts_with(' ') {
+            return None;
+        }
+        Some((*prefix, command.trim())).filter(|(_, cmd)| !cmd.is_empty())
+    })
+}
+
+/// Model name from a startup line like "Model: gpt-4o with diff edit format"
+fn model_from_header(line: &str) -> Option<String> {
+    ["Main model: ", "Model: ", "Models: "]
+        .iter()
+        .find_map(|prefix| line.strip_prefix(prefix))
+        .and_then(|rest| rest.split(" with ").next())
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())
+}
+
+/// Usage and message cost from a notice like "Tokens: 12k sent, 1.1k cache
+/// write, 2.5k cache hit, 310 received. Cost: $0.05 message, $0.12 session."
+fn usage_from_notice(line: &str) -> Option<(TokenUsage, Option<f64>)> {
+    let rest = line.strip_prefix(TOKENS_PREFIX)?;
+    let (counts, cost) = match rest.split_once(" Cost: ") {
+        Some((counts, cost)) => (counts, Some(cost)),
+        None => (rest, None),
+    };
+
+    let mut usage = TokenUsage::default();
+    for item in counts.trim_end_matches('.').split(", ") {
+        let (count, label) = item.split_once(' ')?;
+        let count = token_count(count)?;
+        match label {
+            "sent" => usage.input = count,
+            "received" => usage.output = count,
+            "cache hit" => usage.cache_read = count,
+            "cache write" => usage.cache_write = count,
+            _ => {}
+        }
+    }
+    let cost = cost.and_then(|cost| {
+        cost.split(", ")
+            .find_map(|item| item.strip_suffix(" message"))
+            .and_then(|amount| amount.trim_start_matches('$').parse().ok())
+    });
+    Some((usage, cost))
+}
+
+/// A count as aider prints it: `310`, `2.1k`, `12k` or `1.2M`
+fn token_count(s: &str) -> Option<u64> {
+    let (number, scale) = match s.chars().last()? {
+        'k' => (&s[..s.len() - 1], 1_000.0),
+        'M' => (&s[..s.len() - 1], 1_000_000.0),
+        _ => (s, 1.0),
+    };
+    let value: f64 = number.replace(',', "").parse().ok()?;
+    Some((value * scale).round() as u64)
+}
+
+fn build_session(
+    root: &Path,
+    chat: &Chat,
+    inputs: &[(DateTime<Utc>, String)],
+    end: Option<DateTime<Utc>>,
+) -> UniversalSession {
+    let mut timeline: Vec<TimelineEntry> = Vec::new();
+    let mut model: Option<String> = None;
+    let mut ts = chat.started;
+    let mut seen_input = false;
+    let mut pending_run: Option<String> = None;
+    let mut tokens: Option<TokenUsage> = None;
+    let mut cost: Option<f64> = None;
+    let mut next_input = inputs
+        .iter()
+        .position(|(input_ts, _)| *input_ts >= chat.started)
+        .unwrap_or(inputs.len());
+    let mut next_id = 0usize;
+    let mut id = || {
+        next_id += 1;
+        next_id.to_string()
+    };
+
+    for (kind, lines) in blocks(&chat.lines) {
+        let text = lines.join("\n").trim().to_string();
+        match kind {
+            BlockKind::User => {
+                seen_input = true;
+                pending_run = None;
+                // Take the timestamp of the matching input history entry
+                if let Some(offset) = inputs[next_input..]
+                    .iter()
+                    .position(|(_, input)| input.trim() == text)
+                {
+                    ts = inputs[next_input + offset].0;
+                    next_input += offset + 1;
+                }
+
+                if let Some((prefix, command)) = run_command(&text) {
+                    let call_id = id();
+                    timeline.push(TimelineEntry::ToolCall(ToolCall {
+                        id: call_id.clone(),
+                        timestamp: ts,
+                        tool: UniversalTool::from_aider(prefix),
+                        input: serde_json::json!({ "command": command }),
+                        original_tool: Some(prefix.to_string()),
+                    }));
+                    pending_run = Some(call_id);
+                } else if !text.is_empty() {
+                    timeline.push(TimelineEntry::User(UserMessage {
+                        id: id(),
+                        timestamp: ts,
+                        content: text,
+                    }));
+                }
+            }
+            BlockKind::Aider if !seen_input => {
+                // Startup banner: version, model, repo
+                if model.is_none() {
+                    model = lines.iter().find_map(|l| model_from_header(l));
+                }
+            }
+            BlockKind::Aider => {
+                if let Some(call_id) = pending_run.take() {
+                    timeline.push(TimelineEntry::ToolResult(ToolResult {
+                        id: id(),
+                        timestamp: ts,
+                        call_id,
+                        success: true, // Aider doesn't record exit codes
+                        output: Some(text),
+                        error: None,
+                    }));
+                    continue;
+                }
+
+                let mut notices: Vec<&str> = Vec::new();
+                for line in &lines {
+                    if let Some((usage, message_cost)) = usage_from_notice(line) {
+                        *tokens.get_or_insert_with(TokenUsage::default) += usage;
+                        if let Some(message_cost) = message_cost {
+                            *cost.get_or_insert(0.0) += message_cost;
+                        }
+                        // The notice follows the reply it's for
+                        if let Some(TimelineEntry::Assistant(reply)) = timeline
+                            .iter_mut()
+                            .rev()
+                            .find(|e| !matches!(e, TimelineEntry::System(_)))
+                        {
+                            reply.usage.get_or_insert(usage);
+                        }
+                    }
+                    let Some(file) = line.strip_prefix(APPLIED_EDIT_PREFIX) else {
+                        notices.push(line);
+                        continue;
+                    };
+                    let call_id = id();
+                    timeline.push(TimelineEntry::ToolCall(ToolCall {
+                        id: call_id.clone(),
+                        timestamp: ts,
+                        tool: UniversalTool::from_aider("edit"),
+                        input: serde_json::json!({ "file_path": file.trim() }),
+                        original_tool: Some("edit".to_string()),
+                    }));
+                    timeline.push(TimelineEntry::ToolResult(ToolResult {
+                        id: id(),
+                        timestamp: ts,
+                        call_id,
+                        success: true,
+                        output: Some(line.to_string()),
+                        error: None,
+                    }));
+                }
+                let notices = notices.join("\n").trim().to_string();
+                if !notices.is_empty() {
+                    timeline.push(TimelineEntry::System(SystemMessage {
+                        id: id(),
+                        timestamp: ts,
+                        content: notices,
+                    }));
+                }
+            }
+            BlockKind::Model => {
+                pending_run = None;
+                timeline.push(TimelineEntry::Assistant(AssistantMessage {
+                    id: id(),
+                    timestamp: ts,
+                    content: text,
+                    thinking: None,
+                    usage: None,
+                }));
+            }
+        }
+    }
+
+    let project_path = root.to_string_lossy().to_string();
+    let original_id = format!(
+        "{}-{}",
+        &path_hash(&project_path)[..8],
+        chat.started.format(STAMP_FORMAT)
+    );
+    let model = model.unwrap_or_else(|| "unknown".to_string());
+    let provider = model
+        .split_once('/')
+        .map(|(provider, _)| provider.to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    UniversalSession {
+        id: format!("aider-{}", original_id),
+        version: USF_VERSION.to_string(),
+        source: SessionSource {
+            cli: CliType::Aider,
+            original_id: Some(original_id),
+        },
+        project: ProjectInfo {
+            name: project_path.split('/').next_back().map(|s| s.to_string()),
+            path: project_path,
+            git: None,
+        },
+        model: ModelInfo {
+            provider,
+            model,
+            config: None,
+        },
+        timeline,
+        metadata: SessionMetadata {
+            created: chat.started,
+            last_modified: end.map_or(ts, |end| end.max(ts)),
+            tokens,
+            model_usage: Vec::new(),
+            cost,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CHAT_HISTORY: &str = "
+# aider chat started at 2026-01-04 10:00:00
+
+> /usr/local/bin/aider --model gpt-4o
+> Aider v0.60.0
+> Model: gpt-4o with diff edit format
+> Git repo: .git with 12 files
+
+#### add a hello function to main.py
+
+I'll add it.
+
+main.py
+```python
+<<<<<<< SEARCH
+=======
+def hello():
+    print(\"hello\")
+>>>>>>> REPLACE
+```
+
+> Tokens: 2.1k sent, 120 received. Cost: $0.01 message, $0.01 session.
+> Applied edit to main.py
+> Commit 1a2b3c4 feat: add hello function
+
+#### /run python main.py
+
+> hello
+> Add command output to the chat? (Y)es/(N)o [Yes]: n
+
+# aider chat started at 2026-01-05 09:00:00
+
+> Aider v0.60.0
+
+# aider chat started at 2026-01-05 09:30:00
+
+> Model: gpt-4o with diff edit format
+
+#### what does main.py do?
+#### answer briefly
+
+It prints hello.
+";
+
+    const INPUT_HISTORY: &str = "
+# 2026-01-04 10:00:05.123456
++add a hello function to main.py
+
+# 2026-01-04 10:01:00.000000
++/run python main.py
+
+# 2026-01-05 09:31:00.000000
++what does main.py do?
++answer briefly
+";
+
+    fn fixture() -> (TempDir, AiderAdapter) {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join(CHAT_HISTORY_FILE), CHAT_HISTORY).unwrap();
+        fs::write(tmp.path().join(INPUT_HISTORY_FILE), INPUT_HISTORY).unwrap();
+        let adapter = AiderAdapter {
+            roots: vec![tmp.path().to_path_buf()],
+        };
+        (tmp, adapter)
+    }
+
+    fn local(s: &str) -> DateTime<Utc> {
+        parse_local_time(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_adapter_creation() {
+        if let Some(adapter) = AiderAdapter::new() {
+            assert_eq!(adapter.cli_type(), CliType::Aider);
+        }
+    }
+
+    #[test]
+    fn test_one_session_per_chat() {
+        let (tmp, adapter) = fixture();
+        let sessions = adapter.list_sessions().unwrap();
+
+        // The chat without any input is dropped; newest first
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].title, "what does main.py do?");
+        assert_eq!(sessions[1].title, "add a hello function to main.py");
+        assert!(sessions
+            .iter()
+            .all(|s| s.project_path == tmp.path().to_string_lossy()));
+        assert_eq!(sessions[1].created, local("2026-01-04 10:00:00"));
+    }
+
+    #[test]
+    fn test_edits_and_run_commands() {
+        let (_tmp, adapter) = fixture();
+        let id = adapter.list_sessions().unwrap()[1].id.clone();
+        let session = adapter.load_session(&id).unwrap();
+        assert_eq!(session.model.model, "gpt-4o");
+
+        let tools: Vec<(UniversalTool, &serde_json::Value)> = session
+            .timeline
+            .iter()
+            .filter_map(|e| match e {
+                TimelineEntry::ToolCall(c) => Some((c.tool, &c.input)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].0, UniversalTool::Edit);
+        assert_eq!(tools[0].1["file_path"], "main.py");
+        assert_eq!(tools[1].0, UniversalTool::Bash);
+        assert_eq!(tools[1].1["command"], "python main.py");
+
+        // The run's output is its result, stamped from the input history
+        match session.timeline.last().unwrap() {
+            TimelineEntry::ToolResult(result) => {
+                assert!(result.output.as_deref().unwrap().starts_with("hello"));
+                assert_eq!(result.timestamp, local("2026-01-04 10:01:00"));
+            }
+            other => panic!("expected tool result, got {:?}", other),
+        }
+
+        // Token and commit notices are kept as system messages
+        assert!(session
+            .timeline
+            .iter()
+            .any(|e| matches!(e, TimelineEntry::System(m) if m.content.starts_with("Tokens:"))));
+        let reply = session
+            .timeline
+            .iter()
+            .find_map(|e| match e {
+                TimelineEntry::Assistant(m) => Some(m),
+                _ => None,
+            })
+            .unwrap();
+        assert!(reply.content.contains("<<<<<<< SEARCH"));
+
+        // ...and also give the reply's usage and the session's cost
+        let usage = TokenUsage {
+            input: 2100,
+            output: 120,
+            ..Default::default()
+        };
+        assert_eq!(reply.usage, Some(usage));
+        assert_eq!(session.metadata.tokens, Some(usage));
+        assert_eq!(session.metadata.cost, Some(0.01));
+    }
+
+    #[test]
+    fn test_usage_from_notice() {
+        let (usage, cost) = usage_from_notice(
+            "Tokens: 12k sent, 1.1k cache write, 2.5k cache hit, 310 received. \
+             Cost: $0.0512 message, $0.12 session.",
+        )
+        .unwrap();
+        assert_eq!(
+            usage,
+            TokenUsage {
+                input: 12_000,
+                output: 310,
+                cache_read: 2_500,
+                cache_write: 1_100,
+            }
+        );
+        assert_eq!(cost, Some(0.0512));
+
+        assert_eq!(token_count("1.2M"), Some(1_200_000));
+        assert!(usage_from_notice("Tokens: lots").is_none());
+        assert!(usage_from_notice("Applied edit to main.py").is_none());
+    }
+
+    #[test]
+    fn test_parse_input_history() {
+        let inputs = parse_input_history(INPUT_HISTORY);
+        assert_eq!(inputs.len(), 3);
+        assert_eq!(inputs[2].1, "what does main.py do?\nanswer briefly");
+    }
+
+    #[test]
+    fn test_model_from_header() {
+        assert_eq!(
+            model_from_header("Main model: anthropic/claude-sonnet with diff edit format"),
+            Some("anthropic/claude-sonnet".to_string())
+        );
+        assert_eq!(model_from_header("Git repo: .git"), None);
+    }
+
+    #[test]
+    fn test_run_command() {
+        assert_eq!(run_command("/run cargo test"), Some(("/run", "cargo test")));
+        assert_eq!(run_command("/test pytest -x"), Some(("/test", "pytest -x")));
+        assert_eq!(run_command("!ls"), Some(("!", "ls")));
+        assert_eq!(run_command("/runner"), None);
+        assert_eq!(run_command("/run"), None);
+        assert_eq!(run_command("run the tests"), None);
+    }
+}
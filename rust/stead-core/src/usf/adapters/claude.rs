@@ -2,18 +2,19 @@
 //!
 //! Parses sessions from ~/.claude/projects/
 
-use super::{expand_home, AdapterError, SessionAdapter};
+use super::tail::{LineParser, SessionTail};
+use super::{expand_home, push_reply, push_thinking, usage_by_model, AdapterError, SessionAdapter};
 use crate::usf::{
-    AssistantMessage, CliType, GitInfo, ModelInfo, ProjectInfo, SessionMetadata, SessionSource,
-    SessionSummary, TimelineEntry, ToolCall, ToolResult, UniversalSession, UniversalTool,
-    UserMessage, USF_VERSION,
+    AssistantMessage, CliType, GitInfo, ModelInfo, ModelUsage, ProjectInfo, SessionMetadata,
+    SessionSource, SessionSummary, TimelineEntry, TokenUsage, ToolCall, ToolResult,
+    UniversalSession, UniversalTool, UserMessage, USF_VERSION,
 };
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const CLAUDE_DIR: &str = "~/.claude";
 const PROJECTS_DIR: &str = "projects";
@@ -26,7 +27,12 @@ pub struct ClaudeAdapter {
 impl ClaudeAdapter {
     /// Create a new adapter if Claude Code is installed
     pub fn new() -> Option<Self> {
-        let base_dir = expand_home(CLAUDE_DIR)?;
+        Self::with_base_dir(expand_home(CLAUDE_DIR)?)
+    }
+
+    /// Create an adapter reading from `base_dir` instead of the default
+    /// location, if it holds Claude Code data
+    pub fn with_base_dir(base_dir: PathBuf) -> Option<Self> {
         if base_dir.join(PROJECTS_DIR).is_dir() {
             Some(Self { base_dir })
         } else {
@@ -39,167 +45,221 @@ impl ClaudeAdapter {
     }
 
     /// Parse a session JSONL file
-    fn parse_session_file(&self, path: &PathBuf) -> Result<UniversalSession, AdapterError> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+    fn parse_session_file(&self, path: &Path) -> Result<UniversalSession, AdapterError> {
+        let mut tail = SessionTail::new::<ClaudeParser>(path);
+        let timeline = tail.read_to_end()?;
+        Ok(tail.session(timeline))
+    }
 
-        let mut session_id: Option<String> = None;
-        let mut cwd: Option<String> = None;
-        let mut git_branch: Option<String> = None;
-        let mut model: Option<String> = None;
-        let mut created: Option<DateTime<Utc>> = None;
-        let mut last_modified: Option<DateTime<Utc>> = None;
-        let mut timeline: Vec<TimelineEntry> = Vec::new();
+    /// Find the JSONL file for a session ID
+    fn find_session_file(&self, id: &str) -> Result<PathBuf, AdapterError> {
+        // ID format: claude-{session_id} or just {session_id}
+        let session_id = id.strip_prefix("claude-").unwrap_or(id);
 
-        // Track tool calls to match with results
-        let mut pending_tool_calls: HashMap<String, (String, UniversalTool, serde_json::Value)> =
-            HashMap::new();
+        // Search for the session file in all project directories
+        let projects_dir = self.projects_dir();
 
-        for line in reader.lines() {
-            let line = line?;
-            if line.trim().is_empty() {
+        for project_entry in fs::read_dir(&projects_dir)? {
+            let project_entry = project_entry?;
+            let project_path = project_entry.path();
+
+            if !project_path.is_dir() {
                 continue;
             }
 
-            // Try to parse as a Claude entry
-            let entry: Result<ClaudeEntry, _> = serde_json::from_str(&line);
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue, // Skip unparseable lines gracefully
-            };
-
-            // Extract session metadata from first entry
-            if session_id.is_none() {
-                session_id = entry.session_id.clone();
-            }
-            if cwd.is_none() {
-                cwd = entry.cwd.clone();
-            }
-            if git_branch.is_none() {
-                git_branch = entry.git_branch.clone();
+            // Look for {session_id}.jsonl
+            let session_file = project_path.join(format!("{}.jsonl", session_id));
+            if session_file.exists() {
+                return Ok(session_file);
             }
+        }
 
-            // Track timestamps
-            if let Some(ts) = entry.timestamp {
-                if created.is_none() || ts < created.unwrap() {
-                    created = Some(ts);
-                }
-                if last_modified.is_none() || ts > last_modified.unwrap() {
-                    last_modified = Some(ts);
-                }
+        Err(AdapterError::NotFound(id.to_string()))
+    }
+}
+
+/// Line-by-line Claude Code parser state, resumable through `SessionTail`
+#[derive(Default)]
+pub struct ClaudeParser {
+    session_id: Option<String>,
+    cwd: Option<String>,
+    git_branch: Option<String>,
+    model: Option<String>,
+    created: Option<DateTime<Utc>>,
+    last_modified: Option<DateTime<Utc>>,
+    // Track tool calls to match with results
+    pending_tool_calls: HashMap<String, (String, UniversalTool, serde_json::Value)>,
+    usage: MessageUsage,
+}
+
+impl LineParser for ClaudeParser {
+    fn parse_line(&mut self, line: &str, timeline: &mut Vec<TimelineEntry>) {
+        // Try to parse as a Claude entry
+        let entry: Result<ClaudeEntry, _> = serde_json::from_str(line);
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => return, // Skip unparseable lines gracefully
+        };
+
+        // Extract session metadata from first entry
+        if self.session_id.is_none() {
+            self.session_id = entry.session_id.clone();
+        }
+        if self.cwd.is_none() {
+            self.cwd = entry.cwd.clone();
+        }
+        if self.git_branch.is_none() {
+            self.git_branch = entry.git_branch.clone();
+        }
+
+        // Track timestamps
+        if let Some(ts) = entry.timestamp {
+            if self.created.is_none() || ts < self.created.unwrap() {
+                self.created = Some(ts);
+            }
+            if self.last_modified.is_none() || ts > self.last_modified.unwrap() {
+                self.last_modified = Some(ts);
             }
+        }
 
-            // Process message content
-            if let Some(msg) = &entry.message {
-                // Extract model from assistant messages
-                if model.is_none() {
-                    if let Some(m) = &msg.model {
-                        model = Some(m.clone());
-                    }
-                }
+        // Process message content
+        let Some(msg) = &entry.message else {
+            return;
+        };
 
-                match msg.role.as_str() {
-                    "user" => {
-                        // User messages may contain text or tool results
-                        if let Some(content) = &msg.content {
-                            for item in content {
-                                match item {
-                                    ContentItem::Text { text } => {
-                                        timeline.push(TimelineEntry::User(UserMessage {
-                                            id: entry.uuid.clone().unwrap_or_default(),
-                                            timestamp: entry.timestamp.unwrap_or_else(Utc::now),
-                                            content: text.clone(),
-                                        }));
-                                    }
-                                    ContentItem::ToolResult {
-                                        tool_use_id,
-                                        content: result_content,
-                                        is_error,
-                                    } => {
-                                        // Match with pending tool call
-                                        let (original_id, _tool, _input) = pending_tool_calls
-                                            .remove(tool_use_id)
-                                            .unwrap_or_else(|| {
-                                                (
-                                                    tool_use_id.clone(),
-                                                    UniversalTool::Unknown,
-                                                    serde_json::Value::Null,
-                                                )
-                                            });
-
-                                        timeline.push(TimelineEntry::ToolResult(ToolResult {
-                                            id: entry.uuid.clone().unwrap_or_default(),
-                                            timestamp: entry.timestamp.unwrap_or_else(Utc::now),
-                                            call_id: original_id,
-                                            success: !is_error.unwrap_or(false),
-                                            output: Some(result_content.clone()),
-                                            error: if is_error.unwrap_or(false) {
-                                                Some(result_content.clone())
-                                            } else {
-                                                None
-                                            },
-                                        }));
-                                    }
-                                    _ => {}
-                                }
+        // Extract model from assistant messages
+        if self.model.is_none() {
+            if let Some(m) = &msg.model {
+                self.model = Some(m.clone());
+            }
+        }
+        // A message's usage goes on its first assistant entry, whichever line
+        // that comes from
+        let message_key = self
+            .usage
+            .record(msg, entry.uuid.as_deref(), entry.timestamp);
+
+        match msg.role.as_str() {
+            "user" => {
+                // User messages may contain text or tool results
+                if let Some(content) = &msg.content {
+                    for item in content {
+                        match item {
+                            ContentItem::Text { text } => {
+                                timeline.push(TimelineEntry::User(UserMessage {
+                                    id: entry.uuid.clone().unwrap_or_default(),
+                                    timestamp: entry.timestamp.unwrap_or_else(Utc::now),
+                                    content: text.clone(),
+                                }));
+                            }
+                            ContentItem::ToolResult {
+                                tool_use_id,
+                                content: result_content,
+                                is_error,
+                            } => {
+                                // Match with pending tool call
+                                let (original_id, _tool, _input) = self
+                                    .pending_tool_calls
+                                    .remove(tool_use_id)
+                                    .unwrap_or_else(|| {
+                                        (
+                                            tool_use_id.clone(),
+                                            UniversalTool::Unknown,
+                                            serde_json::Value::Null,
+                                        )
+                                    });
+
+                                timeline.push(TimelineEntry::ToolResult(ToolResult {
+                                    id: entry.uuid.clone().unwrap_or_default(),
+                                    timestamp: entry.timestamp.unwrap_or_else(Utc::now),
+                                    call_id: original_id,
+                                    success: !is_error.unwrap_or(false),
+                                    output: Some(result_content.clone()),
+                                    error: if is_error.unwrap_or(false) {
+                                        Some(result_content.clone())
+                                    } else {
+                                        None
+                                    },
+                                }));
                             }
+                            _ => {}
                         }
                     }
-                    "assistant" => {
-                        if let Some(content) = &msg.content {
-                            for item in content {
-                                match item {
-                                    ContentItem::Text { text } => {
-                                        timeline.push(TimelineEntry::Assistant(AssistantMessage {
-                                            id: entry.uuid.clone().unwrap_or_default(),
-                                            timestamp: entry.timestamp.unwrap_or_else(Utc::now),
-                                            content: text.clone(),
-                                            thinking: None,
-                                        }));
-                                    }
-                                    ContentItem::ToolUse { id, name, input } => {
-                                        let tool = UniversalTool::from_claude(name);
-                                        let tool_call_id = id.clone();
-
-                                        // Store for matching with result
-                                        pending_tool_calls
-                                            .insert(id.clone(), (id.clone(), tool, input.clone()));
-
-                                        timeline.push(TimelineEntry::ToolCall(ToolCall {
-                                            id: tool_call_id,
-                                            timestamp: entry.timestamp.unwrap_or_else(Utc::now),
-                                            tool,
-                                            input: input.clone(),
-                                            original_tool: Some(name.clone()),
-                                        }));
-                                    }
-                                    ContentItem::Thinking { thinking } => {
-                                        // Add thinking to the last assistant message if exists
-                                        if let Some(TimelineEntry::Assistant(msg)) =
-                                            timeline.last_mut()
+                }
+            }
+            "assistant" => {
+                if let Some(content) = &msg.content {
+                    for item in content {
+                        match item {
+                            ContentItem::Text { text } => {
+                                push_reply(
+                                    timeline,
+                                    AssistantMessage {
+                                        id: entry.uuid.clone().unwrap_or_default(),
+                                        timestamp: entry.timestamp.unwrap_or_else(Utc::now),
+                                        content: text.clone(),
+                                        thinking: None,
+                                        usage: message_key
+                                            .as_deref()
+                                            .and_then(|key| self.usage.take(key)),
+                                    },
+                                );
+                            }
+                            ContentItem::ToolUse { id, name, input } => {
+                                let tool = UniversalTool::from_claude(name);
+                                let tool_call_id = id.clone();
+
+                                // Store for matching with result
+                                self.pending_tool_calls
+                                    .insert(id.clone(), (id.clone(), tool, input.clone()));
+
+                                timeline.push(TimelineEntry::ToolCall(ToolCall {
+                                    id: tool_call_id,
+                                    timestamp: entry.timestamp.unwrap_or_else(Utc::now),
+                                    tool,
+                                    input: input.clone(),
+                                    original_tool: Some(name.clone()),
+                                }));
+                            }
+                            // Claude Code writes reasoning on its own line, before the reply
+                            ContentItem::Thinking { thinking } => {
+                                push_thinking(
+                                    timeline,
+                                    entry.uuid.clone().unwrap_or_default(),
+                                    entry.timestamp.unwrap_or_else(Utc::now),
+                                    thinking.clone(),
+                                );
+                                if let Some(TimelineEntry::Assistant(msg)) = timeline.last_mut() {
+                                    if msg.is_thinking_only() {
+                                        if let Some(usage) = message_key
+                                            .as_deref()
+                                            .and_then(|key| self.usage.take(key))
                                         {
-                                            msg.thinking = Some(thinking.clone());
+                                            *msg.usage.get_or_insert_with(TokenUsage::default) +=
+                                                usage;
                                         }
                                     }
-                                    _ => {}
                                 }
                             }
+                            _ => {}
                         }
                     }
-                    _ => {}
                 }
             }
+            _ => {}
         }
+    }
 
+    fn session(&self, path: &Path, timeline: Vec<TimelineEntry>) -> UniversalSession {
         // Build the session
         let now = Utc::now();
-        let session_id = session_id.unwrap_or_else(|| {
+        let session_id = self.session_id.clone().unwrap_or_else(|| {
             path.file_stem()
                 .and_then(|s| s.to_str())
                 .unwrap_or("unknown")
                 .to_string()
         });
-        let project_path = cwd.unwrap_or_else(|| "/unknown".to_string());
+        let project_path = self.cwd.clone().unwrap_or_else(|| "/unknown".to_string());
 
         let mut session = UniversalSession {
             id: format!("claude-{}", session_id),
@@ -211,7 +271,7 @@ impl ClaudeAdapter {
             project: ProjectInfo {
                 path: project_path,
                 name: None,
-                git: git_branch.map(|branch| GitInfo {
+                git: self.git_branch.clone().map(|branch| GitInfo {
                     branch,
                     commit: None,
                     remote: None,
@@ -219,14 +279,15 @@ impl ClaudeAdapter {
             },
             model: ModelInfo {
                 provider: "anthropic".to_string(),
-                model: model.unwrap_or_else(|| "unknown".to_string()),
+                model: self.model.clone().unwrap_or_else(|| "unknown".to_string()),
                 config: None,
             },
             timeline,
             metadata: SessionMetadata {
-                created: created.unwrap_or(now),
-                last_modified: last_modified.unwrap_or(now),
-                tokens: None,
+                created: self.created.unwrap_or(now),
+                last_modified: self.last_modified.unwrap_or(now),
+                tokens: self.usage.total(),
+                model_usage: self.usage.by_model(self.model.as_deref()),
                 cost: None,
             },
         };
@@ -239,7 +300,7 @@ impl ClaudeAdapter {
             .next_back()
             .map(|s| s.to_string());
 
-        Ok(session)
+        session
     }
 }
 
@@ -256,12 +317,12 @@ impl SessionAdapter for ClaudeAdapter {
         Some(self.base_dir.clone())
     }
 
-    fn list_sessions(&self) -> Result<Vec<SessionSummary>, AdapterError> {
-        let mut sessions = Vec::new();
+    fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError> {
+        let mut files = Vec::new();
         let projects_dir = self.projects_dir();
 
         if !projects_dir.exists() {
-            return Ok(sessions);
+            return Ok(files);
         }
 
         // Iterate over project directories
@@ -287,49 +348,32 @@ impl SessionAdapter for ClaudeAdapter {
                     continue;
                 }
 
-                // Parse just enough to build summary (first few lines)
-                match self.parse_session_summary(&session_path) {
-                    Ok(summary) => sessions.push(summary),
-                    Err(_) => continue, // Skip unparseable sessions
-                }
+                files.push(session_path);
             }
         }
 
-        // Sort by last_modified descending
-        sessions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+        Ok(files)
+    }
 
-        Ok(sessions)
+    fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError> {
+        // Parse just enough to build summary (first few lines)
+        Ok(vec![self.parse_session_summary(path)?])
     }
 
     fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError> {
-        // ID format: claude-{session_id} or just {session_id}
-        let session_id = id.strip_prefix("claude-").unwrap_or(id);
-
-        // Search for the session file in all project directories
-        let projects_dir = self.projects_dir();
-
-        for project_entry in fs::read_dir(&projects_dir)? {
-            let project_entry = project_entry?;
-            let project_path = project_entry.path();
-
-            if !project_path.is_dir() {
-                continue;
-            }
-
-            // Look for {session_id}.jsonl
-            let session_file = project_path.join(format!("{}.jsonl", session_id));
-            if session_file.exists() {
-                return self.parse_session_file(&session_file);
-            }
-        }
+        self.parse_session_file(&self.find_session_file(id)?)
+    }
 
-        Err(AdapterError::NotFound(id.to_string()))
+    fn tail_session(&self, id: &str) -> Result<SessionTail, AdapterError> {
+        Ok(SessionTail::new::<ClaudeParser>(
+            &self.find_session_file(id)?,
+        ))
     }
 }
 
 impl ClaudeAdapter {
     /// Parse just enough of a session file to build a summary
-    fn parse_session_summary(&self, path: &PathBuf) -> Result<SessionSummary, AdapterError> {
+    fn parse_session_summary(&self, path: &Path) -> Result<SessionSummary, AdapterError> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
 
@@ -340,8 +384,11 @@ impl ClaudeAdapter {
         let mut last_modified: Option<DateTime<Utc>> = None;
         let mut first_user_message: Option<String> = None;
         let mut message_count = 0;
+        let mut model: Option<String> = None;
+        let mut usage = MessageUsage::default();
 
-        for (line_num, line) in reader.lines().enumerate() {
+        // Token totals need every line; the index keeps this to changed files
+        for line in reader.lines() {
             let line = line?;
             if line.trim().is_empty() {
                 continue;
@@ -380,6 +427,10 @@ impl ClaudeAdapter {
                 if msg.role == "user" || msg.role == "assistant" {
                     message_count += 1;
                 }
+                if model.is_none() {
+                    model = msg.model.clone();
+                }
+                usage.record(msg, entry.uuid.as_deref(), entry.timestamp);
                 if first_user_message.is_none() && msg.role == "user" {
                     if let Some(content) = &msg.content {
                         for item in content {
@@ -391,12 +442,6 @@ impl ClaudeAdapter {
                     }
                 }
             }
-
-            // Stop after reading enough lines for summary (optimization)
-            if line_num > 100 && session_id.is_some() && first_user_message.is_some() {
-                // Continue counting messages but faster
-                break;
-            }
         }
 
         let now = Utc::now();
@@ -420,10 +465,71 @@ impl ClaudeAdapter {
             last_modified: last_modified.unwrap_or(now),
             message_count,
             git_branch,
+            model_usage: usage.by_model(model.as_deref()),
+            model,
+            tokens: usage.total(),
+            cost: None,
+            project_hash: None,
         })
     }
 }
 
+/// Token usage per API message. Claude Code writes one line per content
+/// block, each repeating the message's usage, so usage is keyed by message
+/// ID and the last line seen wins.
+#[derive(Default)]
+struct MessageUsage {
+    by_message: HashMap<String, RecordedUsage>,
+    /// Usage not yet attached to an assistant entry in the timeline
+    pending: HashMap<String, TokenUsage>,
+}
+
+/// One API message's usage, with the model that served it and when
+struct RecordedUsage {
+    model: Option<String>,
+    at: Option<DateTime<Utc>>,
+    usage: TokenUsage,
+}
+
+impl MessageUsage {
+    /// Remember `msg`'s usage; returns the key it is kept under
+    fn record(
+        &mut self,
+        msg: &ClaudeMessage,
+        uuid: Option<&str>,
+        at: Option<DateTime<Utc>>,
+    ) -> Option<String> {
+        let usage = TokenUsage::from(msg.usage.as_ref()?);
+        let key = msg.id.as_deref().or(uuid)?.to_string();
+        let recorded = RecordedUsage {
+            model: msg.model.clone(),
+            at,
+            usage,
+        };
+        if self.by_message.insert(key.clone(), recorded).is_none() {
+            self.pending.insert(key.clone(), usage);
+        }
+        Some(key)
+    }
+
+    /// The usage of message `key`, the first time it is asked for
+    fn take(&mut self, key: &str) -> Option<TokenUsage> {
+        self.pending.remove(key)
+    }
+
+    fn total(&self) -> Option<TokenUsage> {
+        (!self.by_message.is_empty()).then(|| self.by_message.values().map(|r| r.usage).sum())
+    }
+
+    /// Usage per model; messages without one count towards `fallback`
+    fn by_model(&self, fallback: Option<&str>) -> Vec<ModelUsage> {
+        usage_by_model(self.by_message.values().map(|r| {
+            let model = r.model.as_deref().or(fallback).unwrap_or("unknown");
+            (model.to_string(), r.at, r.usage)
+        }))
+    }
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
     let s = s.trim();
     let first_line = s.lines().next().unwrap_or(s);
@@ -452,9 +558,36 @@ struct ClaudeEntry {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ClaudeMessage {
+    /// API message ID, shared by every line of one response
+    id: Option<String>,
     role: String,
     model: Option<String>,
     content: Option<Vec<ContentItem>>,
+    usage: Option<ClaudeUsage>,
+}
+
+/// Anthropic API usage block
+#[derive(Debug, Deserialize)]
+struct ClaudeUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+}
+
+impl From<&ClaudeUsage> for TokenUsage {
+    fn from(usage: &ClaudeUsage) -> Self {
+        Self {
+            input: usage.input_tokens,
+            output: usage.output_tokens,
+            cache_read: usage.cache_read_input_tokens,
+            cache_write: usage.cache_creation_input_tokens,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -534,4 +667,172 @@ mod tests {
         let item: ContentItem = serde_json::from_str(json).unwrap();
         assert!(matches!(item, ContentItem::Other));
     }
+
+    #[test]
+    fn test_incremental_parse_resumes_from_offset() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        let call = r#"{"type":"assistant","uuid":"a1","sessionId":"abc","cwd":"/work/app","timestamp":"2026-01-04T10:00:00Z","message":{"role":"assistant","model":"claude-x","content":[{"type":"tool_use","id":"t1","name":"Read","input":{"file_path":"/f"}}]}}"#;
+        let result = r#"{"type":"user","uuid":"u1","timestamp":"2026-01-04T10:00:05Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#;
+        fs::write(&path, format!("{}\n", call)).unwrap();
+
+        let mut tail = SessionTail::new::<ClaudeParser>(&path);
+        let first = tail.read_new().unwrap();
+        assert!(
+            matches!(&first[..], [TimelineEntry::ToolCall(c)] if c.tool == UniversalTool::Read)
+        );
+
+        // Only the appended result is returned, matched to the earlier call
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, format!("{}\n", result).as_bytes()).unwrap();
+        let second = tail.read_new().unwrap();
+        assert!(matches!(&second[..], [TimelineEntry::ToolResult(r)] if r.call_id == "t1"));
+        assert!(tail.read_new().unwrap().is_empty());
+
+        let session = tail.session(second);
+        assert_eq!(session.id, "claude-abc");
+        assert_eq!(session.project.path, "/work/app");
+        assert_eq!(session.model.model, "claude-x");
+
+        // A one-shot parse of the same file sees both entries
+        let adapter = ClaudeAdapter {
+            base_dir: tmp.path().to_path_buf(),
+        };
+        assert_eq!(adapter.parse_session_file(&path).unwrap().timeline.len(), 2);
+    }
+
+    #[test]
+    fn test_usage_counted_once_per_message() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        let usage = r#""usage":{"input_tokens":10,"output_tokens":200,"cache_creation_input_tokens":300,"cache_read_input_tokens":4000}"#;
+        // One API message split over two lines, each repeating its usage
+        let lines = [
+            format!(
+                r#"{{"type":"assistant","uuid":"a1","sessionId":"abc","cwd":"/work/app","timestamp":"2026-01-04T10:00:00Z","message":{{"id":"msg_1","role":"assistant","model":"claude-sonnet-4-5","content":[{{"type":"text","text":"Reading it"}}],{}}}}}"#,
+                usage
+            ),
+            format!(
+                r#"{{"type":"assistant","uuid":"a2","sessionId":"abc","timestamp":"2026-01-04T10:00:01Z","message":{{"id":"msg_1","role":"assistant","model":"claude-sonnet-4-5","content":[{{"type":"text","text":"Done"}}],{}}}}}"#,
+                usage
+            ),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        let adapter = ClaudeAdapter {
+            base_dir: tmp.path().to_path_buf(),
+        };
+
+        let expected = TokenUsage {
+            input: 10,
+            output: 200,
+            cache_read: 4000,
+            cache_write: 300,
+        };
+        let session = adapter.parse_session_file(&path).unwrap();
+        assert_eq!(session.metadata.tokens, Some(expected));
+        let usages: Vec<_> = session
+            .timeline
+            .iter()
+            .filter_map(|e| match e {
+                TimelineEntry::Assistant(m) => Some(m.usage),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(usages, vec![Some(expected), None]);
+
+        let summary = adapter.summarize_file(&path).unwrap().remove(0);
+        assert_eq!(summary.tokens, Some(expected));
+        assert_eq!(summary.model.as_deref(), Some("claude-sonnet-4-5"));
+    }
+    #[test]
+    fn test_usage_kept_for_first_assistant_entry() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        let line = |uuid: &str, msg: &str, output: u64, item: &str| {
+            format!(
+                r#"{{"type":"assistant","uuid":"{}","sessionId":"abc","timestamp":"2026-01-04T10:00:00Z","message":{{"id":"{}","role":"assistant","content":[{}],"usage":{{"input_tokens":10,"output_tokens":{}}}}}}}"#,
+                uuid, msg, item, output
+            )
+        };
+        // A tool call, then reasoning, come before each message's text
+        let lines = [
+            line(
+                "a1",
+                "msg_1",
+                40,
+                r#"{"type":"tool_use","id":"t1","name":"Read","input":{}}"#,
+            ),
+            line("a2", "msg_1", 40, r#"{"type":"text","text":"Reading it"}"#),
+            line(
+                "a3",
+                "msg_2",
+                60,
+                r#"{"type":"thinking","thinking":"Looks fine"}"#,
+            ),
+            line("a4", "msg_2", 60, r#"{"type":"text","text":"Done"}"#),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        let adapter = ClaudeAdapter {
+            base_dir: tmp.path().to_path_buf(),
+        };
+
+        let session = adapter.parse_session_file(&path).unwrap();
+        let usages: Vec<_> = session
+            .timeline
+            .iter()
+            .filter_map(|e| match e {
+                TimelineEntry::Assistant(m) => Some(m.usage.map(|u| u.output)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(usages, vec![Some(40), Some(60)]);
+        assert_eq!(session.metadata.tokens.map(|t| t.output), Some(100));
+    }
+
+    #[test]
+    fn test_thinking_on_its_own_line_precedes_reply() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("abc.jsonl");
+        let line = |uuid: &str, msg: &str, item: &str| {
+            format!(
+                r#"{{"type":"assistant","uuid":"{}","sessionId":"abc","timestamp":"2026-01-04T10:00:00Z","message":{{"id":"{}","role":"assistant","content":[{}]}}}}"#,
+                uuid, msg, item
+            )
+        };
+        let lines = [
+            line(
+                "a1",
+                "msg_1",
+                r#"{"type":"thinking","thinking":"Check the README"}"#,
+            ),
+            line("a2", "msg_1", r#"{"type":"text","text":"Reading it"}"#),
+            line(
+                "a3",
+                "msg_2",
+                r#"{"type":"thinking","thinking":"Looks fine"}"#,
+            ),
+            line("a4", "msg_2", r#"{"type":"text","text":"Done"}"#),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        let adapter = ClaudeAdapter {
+            base_dir: tmp.path().to_path_buf(),
+        };
+
+        let session = adapter.parse_session_file(&path).unwrap();
+        let replies: Vec<_> = session
+            .timeline
+            .iter()
+            .filter_map(|e| match e {
+                TimelineEntry::Assistant(m) => Some((m.content.as_str(), m.thinking.as_deref())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            replies,
+            vec![
+                ("Reading it", Some("Check the README")),
+                ("Done", Some("Looks fine")),
+            ]
+        );
+    }
 }
@@ -1,19 +1,22 @@
 //! Codex CLI session adapter
 //!
-//! Parses sessions from ~/.codex/sessions/
+//! Parses sessions from ~/.codex/sessions/. Reasoning items become the
+//! `thinking` of the reply that follows; Codex usually stores only their
+//! summary, and the full text when the model returns it.
 
-use super::{expand_home, AdapterError, SessionAdapter};
+use super::tail::{LineParser, SessionTail};
+use super::{expand_home, push_reply, push_thinking, usage_by_model, AdapterError, SessionAdapter};
 use crate::usf::{
-    AssistantMessage, CliType, GitInfo, ModelInfo, ProjectInfo, SessionMetadata, SessionSource,
-    SessionSummary, TimelineEntry, ToolCall, ToolResult, UniversalSession, UniversalTool,
-    UserMessage, USF_VERSION,
+    AssistantMessage, CliType, GitInfo, ModelInfo, ModelUsage, ProjectInfo, SessionMetadata,
+    SessionSource, SessionSummary, TimelineEntry, TokenUsage, ToolCall, ToolResult,
+    UniversalSession, UniversalTool, UserMessage, USF_VERSION,
 };
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const CODEX_DIR: &str = "~/.codex";
 const SESSIONS_DIR: &str = "sessions";
@@ -26,7 +29,12 @@ pub struct CodexAdapter {
 impl CodexAdapter {
     /// Create a new adapter if Codex CLI is installed
     pub fn new() -> Option<Self> {
-        let base_dir = expand_home(CODEX_DIR)?;
+        Self::with_base_dir(expand_home(CODEX_DIR)?)
+    }
+
+    /// Create an adapter reading from `base_dir` instead of the default
+    /// location, if it holds Codex CLI data
+    pub fn with_base_dir(base_dir: PathBuf) -> Option<Self> {
         if base_dir.join(SESSIONS_DIR).is_dir() {
             Some(Self { base_dir })
         } else {
@@ -39,257 +47,14 @@ impl CodexAdapter {
     }
 
     /// Parse a session JSONL file
-    fn parse_session_file(&self, path: &PathBuf) -> Result<UniversalSession, AdapterError> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
-
-        let mut session_id: Option<String> = None;
-        let mut cwd: Option<String> = None;
-        let mut git_branch: Option<String> = None;
-        let mut git_commit: Option<String> = None;
-        let mut git_remote: Option<String> = None;
-        let mut model: Option<String> = None;
-        let mut provider: Option<String> = None;
-        let mut created: Option<DateTime<Utc>> = None;
-        let mut last_modified: Option<DateTime<Utc>> = None;
-        let mut timeline: Vec<TimelineEntry> = Vec::new();
-
-        // Track tool calls to match with results
-        let mut pending_tool_calls: HashMap<String, (UniversalTool, serde_json::Value)> =
-            HashMap::new();
-        let mut entry_index = 0u64;
-
-        for line in reader.lines() {
-            let line = line?;
-            if line.trim().is_empty() {
-                continue;
-            }
-
-            let entry: Result<CodexEntry, _> = serde_json::from_str(&line);
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            // Track timestamps
-            if let Some(ts) = &entry.timestamp {
-                if let Ok(parsed) = DateTime::parse_from_rfc3339(ts) {
-                    let ts = parsed.with_timezone(&Utc);
-                    if created.is_none() || ts < created.unwrap() {
-                        created = Some(ts);
-                    }
-                    if last_modified.is_none() || ts > last_modified.unwrap() {
-                        last_modified = Some(ts);
-                    }
-                }
-            }
-
-            match entry.entry_type.as_str() {
-                "session_meta" => {
-                    if let Some(payload) = entry.payload {
-                        if let Some(id) = payload.id {
-                            session_id = Some(id);
-                        }
-                        if let Some(c) = payload.cwd {
-                            cwd = Some(c);
-                        }
-                        if let Some(m) = payload.model_provider {
-                            provider = Some(m);
-                        }
-                        if let Some(git) = payload.git {
-                            git_branch = git.branch;
-                            git_commit = git.commit_hash;
-                            git_remote = git.repository_url;
-                        }
-                    }
-                }
-                "turn_context" => {
-                    if let Some(payload) = entry.payload {
-                        if model.is_none() {
-                            model = payload.model;
-                        }
-                    }
-                }
-                "response_item" => {
-                    if let Some(payload) = entry.payload {
-                        let ts = entry
-                            .timestamp
-                            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                            .map(|dt| dt.with_timezone(&Utc))
-                            .unwrap_or_else(Utc::now);
-
-                        if let Some(item_type) = payload.item_type {
-                            match item_type.as_str() {
-                                "message" => {
-                                    if let Some(role) = payload.role {
-                                        if let Some(content) = payload.content {
-                                            for item in content {
-                                                match item.content_type.as_deref() {
-                                                    Some("input_text") | Some("text") => {
-                                                        if let Some(text) = item.text {
-                                                            if role == "user" {
-                                                                timeline.push(TimelineEntry::User(
-                                                                    UserMessage {
-                                                                        id: format!(
-                                                                            "{}",
-                                                                            entry_index
-                                                                        ),
-                                                                        timestamp: ts,
-                                                                        content: text,
-                                                                    },
-                                                                ));
-                                                            } else if role == "assistant" {
-                                                                timeline.push(
-                                                                    TimelineEntry::Assistant(
-                                                                        AssistantMessage {
-                                                                            id: format!(
-                                                                                "{}",
-                                                                                entry_index
-                                                                            ),
-                                                                            timestamp: ts,
-                                                                            content: text,
-                                                                            thinking: None,
-                                                                        },
-                                                                    ),
-                                                                );
-                                                            }
-                                                        }
-                                                    }
-                                                    _ => {}
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                                "function_call" => {
-                                    if let Some(name) = payload.name {
-                                        let tool = UniversalTool::from_codex(&name);
-                                        let id = payload
-                                            .call_id
-                                            .unwrap_or_else(|| format!("{}", entry_index));
-                                        let arguments = payload
-                                            .arguments
-                                            .map(|s| {
-                                                serde_json::from_str(&s)
-                                                    .unwrap_or(serde_json::Value::Null)
-                                            })
-                                            .unwrap_or(serde_json::Value::Null);
-
-                                        pending_tool_calls
-                                            .insert(id.clone(), (tool, arguments.clone()));
-
-                                        timeline.push(TimelineEntry::ToolCall(ToolCall {
-                                            id: id.clone(),
-                                            timestamp: ts,
-                                            tool,
-                                            input: arguments,
-                                            original_tool: Some(name),
-                                        }));
-                                    }
-                                }
-                                "function_call_output" => {
-                                    let call_id = payload.call_id.unwrap_or_default();
-                                    let output = payload.output;
-
-                                    timeline.push(TimelineEntry::ToolResult(ToolResult {
-                                        id: format!("{}", entry_index),
-                                        timestamp: ts,
-                                        call_id,
-                                        success: true, // Codex doesn't have explicit error flag
-                                        output,
-                                        error: None,
-                                    }));
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
-                }
-                "event_msg" => {
-                    if let Some(payload) = entry.payload {
-                        let ts = entry
-                            .timestamp
-                            .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
-                            .map(|dt| dt.with_timezone(&Utc))
-                            .unwrap_or_else(Utc::now);
-
-                        if let Some(msg_type) = &payload.item_type {
-                            if msg_type == "user_message" {
-                                if let Some(message) = payload.message {
-                                    timeline.push(TimelineEntry::User(UserMessage {
-                                        id: format!("{}", entry_index),
-                                        timestamp: ts,
-                                        content: message,
-                                    }));
-                                }
-                            }
-                        }
-                    }
-                }
-                _ => {}
-            }
-
-            entry_index += 1;
-        }
-
-        // Build the session
-        let now = Utc::now();
-        let session_id = session_id.unwrap_or_else(|| {
-            path.file_stem()
-                .and_then(|s| s.to_str())
-                .unwrap_or("unknown")
-                .to_string()
-        });
-        let project_path = cwd.unwrap_or_else(|| "/unknown".to_string());
-
-        let git_info = if git_branch.is_some() || git_commit.is_some() || git_remote.is_some() {
-            Some(GitInfo {
-                branch: git_branch.unwrap_or_default(),
-                commit: git_commit,
-                remote: git_remote,
-            })
-        } else {
-            None
-        };
-
-        let mut session = UniversalSession {
-            id: format!("codex-{}", session_id),
-            version: USF_VERSION.to_string(),
-            source: SessionSource {
-                cli: CliType::Codex,
-                original_id: Some(session_id),
-            },
-            project: ProjectInfo {
-                path: project_path,
-                name: None,
-                git: git_info,
-            },
-            model: ModelInfo {
-                provider: provider.unwrap_or_else(|| "openai".to_string()),
-                model: model.unwrap_or_else(|| "unknown".to_string()),
-                config: None,
-            },
-            timeline,
-            metadata: SessionMetadata {
-                created: created.unwrap_or(now),
-                last_modified: last_modified.unwrap_or(now),
-                tokens: None,
-                cost: None,
-            },
-        };
-
-        session.project.name = session
-            .project
-            .path
-            .split('/')
-            .next_back()
-            .map(|s| s.to_string());
-
-        Ok(session)
+    fn parse_session_file(&self, path: &Path) -> Result<UniversalSession, AdapterError> {
+        let mut tail = SessionTail::new::<CodexParser>(path);
+        let timeline = tail.read_to_end()?;
+        Ok(tail.session(timeline))
     }
 
     /// Parse just enough of a session file to build a summary
-    fn parse_session_summary(&self, path: &PathBuf) -> Result<SessionSummary, AdapterError> {
+    fn parse_session_summary(&self, path: &Path) -> Result<SessionSummary, AdapterError> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
 
@@ -300,8 +65,12 @@ impl CodexAdapter {
         let mut last_modified: Option<DateTime<Utc>> = None;
         let mut first_user_message: Option<String> = None;
         let mut message_count = 0;
+        let mut model: Option<String> = None;
+        let mut tokens: Option<TokenUsage> = None;
+        let mut usage = ModelTotals::default();
 
-        for (line_num, line) in reader.lines().enumerate() {
+        // Token totals need every line; the index keeps this to changed files
+        for line in reader.lines() {
             let line = line?;
             if line.trim().is_empty() {
                 continue;
@@ -342,6 +111,14 @@ impl CodexAdapter {
                 }
             }
 
+            if entry.entry_type == "turn_context" {
+                let turn_model = entry.payload.as_ref().and_then(|p| p.model.clone());
+                if model.is_none() {
+                    model = turn_model.clone();
+                }
+                usage.switch_model(turn_model);
+            }
+
             if entry.entry_type == "event_msg" {
                 if let Some(payload) = &entry.payload {
                     if payload.item_type.as_deref() == Some("user_message") {
@@ -350,6 +127,10 @@ impl CodexAdapter {
                             first_user_message = payload.message.clone();
                         }
                     }
+                    if let Some(total) = payload.total_usage() {
+                        tokens = Some(total);
+                        usage.update(total, parse_time(entry.timestamp.as_deref()));
+                    }
                 }
             }
 
@@ -370,11 +151,6 @@ impl CodexAdapter {
                     }
                 }
             }
-
-            // Stop after reading enough lines for summary
-            if line_num > 50 && session_id.is_some() && first_user_message.is_some() {
-                break;
-            }
         }
 
         let now = Utc::now();
@@ -398,6 +174,11 @@ impl CodexAdapter {
             last_modified: last_modified.unwrap_or(now),
             message_count,
             git_branch,
+            model,
+            tokens,
+            model_usage: usage.model_usage(),
+            cost: None,
+            project_hash: None,
         })
     }
 }
@@ -415,38 +196,44 @@ impl SessionAdapter for CodexAdapter {
         Some(self.base_dir.clone())
     }
 
-    fn list_sessions(&self) -> Result<Vec<SessionSummary>, AdapterError> {
-        let mut sessions = Vec::new();
-        let sessions_dir = self.sessions_dir();
-
-        if !sessions_dir.exists() {
-            return Ok(sessions);
-        }
+    fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError> {
+        let mut files = Vec::new();
 
         // Codex stores sessions in year/month/day directories
         // e.g., sessions/2026/01/04/rollout-....jsonl
-        Self::walk_session_files(&sessions_dir, &mut |path| {
+        Self::walk_session_files(&self.sessions_dir(), &mut |path| {
             if path.extension().map(|e| e == "jsonl").unwrap_or(false) {
-                if let Ok(summary) = self.parse_session_summary(&path) {
-                    sessions.push(summary);
-                }
+                files.push(path);
             }
         })?;
 
-        // Sort by last_modified descending
-        sessions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+        Ok(files)
+    }
 
-        Ok(sessions)
+    fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError> {
+        Ok(vec![self.parse_session_summary(path)?])
     }
 
     fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError> {
+        self.parse_session_file(&self.find_session_file(id)?)
+    }
+
+    fn tail_session(&self, id: &str) -> Result<SessionTail, AdapterError> {
+        Ok(SessionTail::new::<CodexParser>(
+            &self.find_session_file(id)?,
+        ))
+    }
+}
+
+impl CodexAdapter {
+    /// Find the JSONL file for a session ID
+    fn find_session_file(&self, id: &str) -> Result<PathBuf, AdapterError> {
         // ID format: codex-{session_id} or just {session_id}
         let session_id = id.strip_prefix("codex-").unwrap_or(id);
-        let sessions_dir = self.sessions_dir();
 
         // Search recursively for the session file
         let mut found_path: Option<PathBuf> = None;
-        Self::walk_session_files(&sessions_dir, &mut |path| {
+        Self::walk_session_files(&self.sessions_dir(), &mut |path| {
             if found_path.is_some() {
                 return;
             }
@@ -458,14 +245,9 @@ impl SessionAdapter for CodexAdapter {
             }
         })?;
 
-        match found_path {
-            Some(path) => self.parse_session_file(&path),
-            None => Err(AdapterError::NotFound(id.to_string())),
-        }
+        found_path.ok_or_else(|| AdapterError::NotFound(id.to_string()))
     }
-}
 
-impl CodexAdapter {
     fn walk_session_files<F>(dir: &PathBuf, callback: &mut F) -> Result<(), AdapterError>
     where
         F: FnMut(PathBuf),
@@ -489,6 +271,279 @@ impl CodexAdapter {
     }
 }
 
+/// Line-by-line Codex parser state, resumable through `SessionTail`
+#[derive(Default)]
+pub struct CodexParser {
+    session_id: Option<String>,
+    cwd: Option<String>,
+    git_branch: Option<String>,
+    git_commit: Option<String>,
+    git_remote: Option<String>,
+    model: Option<String>,
+    provider: Option<String>,
+    created: Option<DateTime<Utc>>,
+    last_modified: Option<DateTime<Utc>>,
+    // Track tool calls to match with results
+    pending_tool_calls: HashMap<String, (UniversalTool, serde_json::Value)>,
+    entry_index: u64,
+    /// Cumulative usage from the latest token count
+    tokens: Option<TokenUsage>,
+    model_totals: ModelTotals,
+    /// An assistant message arrived since the last token count
+    awaiting_usage: bool,
+}
+
+impl LineParser for CodexParser {
+    fn parse_line(&mut self, line: &str, timeline: &mut Vec<TimelineEntry>) {
+        let entry: Result<CodexEntry, _> = serde_json::from_str(line);
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => return,
+        };
+        let entry_index = self.entry_index;
+        self.entry_index += 1;
+
+        // Track timestamps
+        if let Some(ts) = &entry.timestamp {
+            if let Ok(parsed) = DateTime::parse_from_rfc3339(ts) {
+                let ts = parsed.with_timezone(&Utc);
+                if self.created.is_none() || ts < self.created.unwrap() {
+                    self.created = Some(ts);
+                }
+                if self.last_modified.is_none() || ts > self.last_modified.unwrap() {
+                    self.last_modified = Some(ts);
+                }
+            }
+        }
+
+        match entry.entry_type.as_str() {
+            "session_meta" => {
+                if let Some(payload) = entry.payload {
+                    if let Some(id) = payload.id {
+                        self.session_id = Some(id);
+                    }
+                    if let Some(c) = payload.cwd {
+                        self.cwd = Some(c);
+                    }
+                    if let Some(m) = payload.model_provider {
+                        self.provider = Some(m);
+                    }
+                    if let Some(git) = payload.git {
+                        self.git_branch = git.branch;
+                        self.git_commit = git.commit_hash;
+                        self.git_remote = git.repository_url;
+                    }
+                }
+            }
+            "turn_context" => {
+                if let Some(payload) = entry.payload {
+                    if self.model.is_none() {
+                        self.model = payload.model.clone();
+                    }
+                    self.model_totals.switch_model(payload.model);
+                }
+            }
+            "response_item" => {
+                if let Some(mut payload) = entry.payload {
+                    let ts = entry
+                        .timestamp
+                        .and_then(|s| DateTime::parse_from_rfc3339(&s).ok())
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(Utc::now);
+
+                    if let Some(item_type) = payload.item_type.take() {
+                        match item_type.as_str() {
+                            "message" => {
+                                if let (Some(role), Some(content)) = (payload.role, payload.content)
+                                {
+                                    for item in content {
+                                        match item.content_type.as_deref() {
+                                            Some("input_text") | Some("text") => {
+                                                if let Some(text) = item.text {
+                                                    if role == "user" {
+                                                        timeline.push(TimelineEntry::User(
+                                                            UserMessage {
+                                                                id: format!("{}", entry_index),
+                                                                timestamp: ts,
+                                                                content: text,
+                                                            },
+                                                        ));
+                                                    } else if role == "assistant" {
+                                                        push_reply(
+                                                            timeline,
+                                                            AssistantMessage {
+                                                                id: format!("{}", entry_index),
+                                                                timestamp: ts,
+                                                                content: text,
+                                                                thinking: None,
+                                                                usage: None,
+                                                            },
+                                                        );
+                                                        self.awaiting_usage = true;
+                                                    }
+                                                }
+                                            }
+                                            _ => {}
+                                        }
+                                    }
+                                }
+                            }
+                            "reasoning" => {
+                                let text = payload.reasoning_text();
+                                push_thinking(timeline, format!("{}", entry_index), ts, text);
+                            }
+                            "function_call" => {
+                                if let Some(name) = payload.name {
+                                    let tool = UniversalTool::from_codex(&name);
+                                    let id = payload
+                                        .call_id
+                                        .unwrap_or_else(|| format!("{}", entry_index));
+                                    let arguments = payload
+                                        .arguments
+                                        .map(|s| {
+                                            serde_json::from_str(&s)
+                                                .unwrap_or(serde_json::Value::Null)
+                                        })
+                                        .unwrap_or(serde_json::Value::Null);
+
+                                    self.pending_tool_calls
+                                        .insert(id.clone(), (tool, arguments.clone()));
+
+                                    timeline.push(TimelineEntry::ToolCall(ToolCall {
+                                        id: id.clone(),
+                                        timestamp: ts,
+                                        tool,
+                                        input: arguments,
+                                        original_tool: Some(name),
+                                    }));
+                                }
+                            }
+                            "function_call_output" => {
+                                let call_id = payload.call_id.unwrap_or_default();
+                                self.pending_tool_calls.remove(&call_id);
+
+                                timeline.push(TimelineEntry::ToolResult(ToolResult {
+                                    id: format!("{}", entry_index),
+                                    timestamp: ts,
+                                    call_id,
+                                    success: true, // Codex doesn't have explicit error flag
+                                    output: payload.output,
+                                    error: None,
+                                }));
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            "event_msg" => {
+                if let Some(payload) = entry.payload {
+                    let at = parse_time(entry.timestamp.as_deref());
+                    let ts = at.unwrap_or_else(Utc::now);
+
+                    if let Some(total) = payload.total_usage() {
+                        self.tokens = Some(total);
+                        self.model_totals.update(total, at);
+                        // A request's usage goes on the reply it produced,
+                        // if that's still in this batch
+                        let last = payload.last_usage();
+                        if std::mem::take(&mut self.awaiting_usage) {
+                            if let Some(TimelineEntry::Assistant(msg)) = timeline
+                                .iter_mut()
+                                .rev()
+                                .find(|e| matches!(e, TimelineEntry::Assistant(_)))
+                            {
+                                msg.usage = last;
+                            }
+                        }
+                    }
+
+                    if payload.item_type.as_deref() == Some("user_message") {
+                        if let Some(message) = payload.message {
+                            timeline.push(TimelineEntry::User(UserMessage {
+                                id: format!("{}", entry_index),
+                                timestamp: ts,
+                                content: message,
+                            }));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn session(&self, path: &Path, timeline: Vec<TimelineEntry>) -> UniversalSession {
+        // Build the session
+        let now = Utc::now();
+        let session_id = self.session_id.clone().unwrap_or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown")
+                .to_string()
+        });
+        let project_path = self.cwd.clone().unwrap_or_else(|| "/unknown".to_string());
+
+        let git_info = if self.git_branch.is_some()
+            || self.git_commit.is_some()
+            || self.git_remote.is_some()
+        {
+            Some(GitInfo {
+                branch: self.git_branch.clone().unwrap_or_default(),
+                commit: self.git_commit.clone(),
+                remote: self.git_remote.clone(),
+            })
+        } else {
+            None
+        };
+
+        let mut session = UniversalSession {
+            id: format!("codex-{}", session_id),
+            version: USF_VERSION.to_string(),
+            source: SessionSource {
+                cli: CliType::Codex,
+                original_id: Some(session_id),
+            },
+            project: ProjectInfo {
+                path: project_path,
+                name: None,
+                git: git_info,
+            },
+            model: ModelInfo {
+                provider: self
+                    .provider
+                    .clone()
+                    .unwrap_or_else(|| "openai".to_string()),
+                model: self.model.clone().unwrap_or_else(|| "unknown".to_string()),
+                config: None,
+            },
+            timeline,
+            metadata: SessionMetadata {
+                created: self.created.unwrap_or(now),
+                last_modified: self.last_modified.unwrap_or(now),
+                tokens: self.tokens,
+                model_usage: self.model_totals.model_usage(),
+                cost: None,
+            },
+        };
+
+        session.project.name = session
+            .project
+            .path
+            .split('/')
+            .next_back()
+            .map(|s| s.to_string());
+
+        session
+    }
+}
+
+fn parse_time(ts: Option<&str>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts?)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
     let s = s.trim();
     let first_line = s.lines().next().unwrap_or(s);
@@ -524,6 +579,8 @@ struct CodexPayload {
     item_type: Option<String>,
     role: Option<String>,
     content: Option<Vec<CodexContentItem>>,
+    /// Reasoning summary parts
+    summary: Option<Vec<CodexContentItem>>,
     name: Option<String>,
     call_id: Option<String>,
     arguments: Option<String>,
@@ -531,6 +588,112 @@ struct CodexPayload {
 
     // event_msg fields (uses item_type for type discrimination)
     message: Option<String>,
+    /// token_count events
+    info: Option<CodexTokenInfo>,
+}
+
+impl CodexPayload {
+    /// A reasoning item's full text when present, else its summary
+    fn reasoning_text(&self) -> String {
+        let join = |items: &Option<Vec<CodexContentItem>>| {
+            items
+                .iter()
+                .flatten()
+                .filter_map(|item| item.text.as_deref())
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        };
+        let content = join(&self.content);
+        if content.is_empty() {
+            join(&self.summary)
+        } else {
+            content
+        }
+    }
+
+    /// Session total so far, from a token_count event
+    fn total_usage(&self) -> Option<TokenUsage> {
+        self.info
+            .as_ref()?
+            .total_token_usage
+            .as_ref()
+            .map(Into::into)
+    }
+
+    /// Usage of the latest model request, from a token_count event
+    fn last_usage(&self) -> Option<TokenUsage> {
+        self.info
+            .as_ref()?
+            .last_token_usage
+            .as_ref()
+            .map(Into::into)
+    }
+}
+
+/// Usage per model, from the running totals in token_count events. Each
+/// increase is counted towards the model of the latest turn.
+#[derive(Default)]
+struct ModelTotals {
+    model: Option<String>,
+    previous: TokenUsage,
+    increases: Vec<(String, Option<DateTime<Utc>>, TokenUsage)>,
+}
+
+impl ModelTotals {
+    fn switch_model(&mut self, model: Option<String>) {
+        if model.is_some() {
+            self.model = model;
+        }
+    }
+
+    /// Record a new session total reported at `at`; repeated totals add
+    /// nothing
+    fn update(&mut self, total: TokenUsage, at: Option<DateTime<Utc>>) {
+        let increase = TokenUsage {
+            input: total.input.saturating_sub(self.previous.input),
+            output: total.output.saturating_sub(self.previous.output),
+            cache_read: total.cache_read.saturating_sub(self.previous.cache_read),
+            cache_write: total.cache_write.saturating_sub(self.previous.cache_write),
+        };
+        self.previous = total;
+        if increase.total() > 0 {
+            let model = self.model.as_deref().unwrap_or("unknown");
+            self.increases.push((model.to_string(), at, increase));
+        }
+    }
+
+    fn model_usage(&self) -> Vec<ModelUsage> {
+        usage_by_model(self.increases.iter().cloned())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CodexTokenInfo {
+    total_token_usage: Option<CodexTokenUsage>,
+    last_token_usage: Option<CodexTokenUsage>,
+}
+
+/// OpenAI usage counts; `input_tokens` includes the cached ones and
+/// `output_tokens` includes reasoning
+#[derive(Debug, Deserialize)]
+struct CodexTokenUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    cached_input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+impl From<&CodexTokenUsage> for TokenUsage {
+    fn from(usage: &CodexTokenUsage) -> Self {
+        Self {
+            input: usage.input_tokens.saturating_sub(usage.cached_input_tokens),
+            output: usage.output_tokens,
+            cache_read: usage.cached_input_tokens,
+            cache_write: 0,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -575,4 +738,150 @@ mod tests {
         assert_eq!(payload.id, Some("test-id".to_string()));
         assert_eq!(payload.cwd, Some("/home/user/project".to_string()));
     }
+
+    #[test]
+    fn test_incremental_parse_resumes_from_offset() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("rollout-2026-01-04-xyz.jsonl");
+        let lines = [
+            r#"{"timestamp":"2026-01-04T10:00:00Z","type":"session_meta","payload":{"id":"xyz","cwd":"/work/app"}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:01Z","type":"response_item","payload":{"type":"function_call","name":"shell","call_id":"c1","arguments":"{\"command\":[\"ls\"]}"}}"#,
+        ];
+        fs::write(&path, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+
+        let mut tail = SessionTail::new::<CodexParser>(&path);
+        assert_eq!(tail.read_new().unwrap().len(), 1);
+
+        let output = r#"{"timestamp":"2026-01-04T10:00:02Z","type":"response_item","payload":{"type":"function_call_output","call_id":"c1","output":"a.txt"}}"#;
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, format!("{}\n", output).as_bytes()).unwrap();
+        let new = tail.read_new().unwrap();
+        match &new[..] {
+            // Entry ids keep counting from where the first read stopped
+            [TimelineEntry::ToolResult(r)] => {
+                assert_eq!((r.id.as_str(), r.call_id.as_str()), ("2", "c1"));
+            }
+            other => panic!("unexpected entries: {:?}", other),
+        }
+
+        let session = tail.session(new);
+        assert_eq!(session.id, "codex-xyz");
+        assert_eq!(session.project.path, "/work/app");
+    }
+
+    #[test]
+    fn test_reasoning_items() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("rollout-2026-01-04-xyz.jsonl");
+        let lines = [
+            r#"{"timestamp":"2026-01-04T10:00:00Z","type":"session_meta","payload":{"id":"xyz","cwd":"/work/app"}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:01Z","type":"response_item","payload":{"type":"reasoning","summary":[{"type":"summary_text","text":"**Listing files**"}],"content":null,"encrypted_content":"gAAA"}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:02Z","type":"response_item","payload":{"type":"function_call","name":"shell","call_id":"c1","arguments":"{\"command\":[\"ls\"]}"}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:03Z","type":"response_item","payload":{"type":"reasoning","summary":[{"type":"summary_text","text":"short"}],"content":[{"type":"reasoning_text","text":"The listing shows a.txt"}]}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:04Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"text","text":"There is one file."}]}}"#,
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        let adapter = CodexAdapter {
+            base_dir: tmp.path().to_path_buf(),
+        };
+
+        let session = adapter.parse_session_file(&path).unwrap();
+        match &session.timeline[..] {
+            [TimelineEntry::Assistant(plan), TimelineEntry::ToolCall(_), TimelineEntry::Assistant(reply)] =>
+            {
+                // Reasoning before a tool call stands alone
+                assert!(plan.is_thinking_only());
+                assert_eq!(plan.thinking.as_deref(), Some("**Listing files**"));
+                // The full text wins over the summary
+                assert_eq!(reply.content, "There is one file.");
+                assert_eq!(reply.thinking.as_deref(), Some("The listing shows a.txt"));
+            }
+            other => panic!("unexpected timeline: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_token_count_usage() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("rollout-2026-01-04-xyz.jsonl");
+        let lines = [
+            r#"{"timestamp":"2026-01-04T10:00:00Z","type":"session_meta","payload":{"id":"xyz","cwd":"/work/app"}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:00Z","type":"turn_context","payload":{"model":"gpt-5-codex"}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:01Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"text","text":"Hi"}]}}"#,
+            r#"{"timestamp":"2026-01-04T10:00:02Z","type":"event_msg","payload":{"type":"token_count","info":{"total_token_usage":{"input_tokens":5000,"cached_input_tokens":4000,"output_tokens":300},"last_token_usage":{"input_tokens":2000,"cached_input_tokens":1500,"output_tokens":100}}}}"#,
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        let adapter = CodexAdapter {
+            base_dir: tmp.path().to_path_buf(),
+        };
+
+        let session = adapter.parse_session_file(&path).unwrap();
+        let total = TokenUsage {
+            input: 1000,
+            output: 300,
+            cache_read: 4000,
+            cache_write: 0,
+        };
+        assert_eq!(session.metadata.tokens, Some(total));
+        let reply = session.timeline.iter().find_map(|e| match e {
+            TimelineEntry::Assistant(m) => Some(m),
+            _ => None,
+        });
+        assert_eq!(
+            reply.unwrap().usage,
+            Some(TokenUsage {
+                input: 500,
+                output: 100,
+                cache_read: 1500,
+                cache_write: 0,
+            })
+        );
+
+        let summary = adapter.summarize_file(&path).unwrap().remove(0);
+        assert_eq!(summary.tokens, Some(total));
+        assert_eq!(summary.model.as_deref(), Some("gpt-5-codex"));
+    }
+    #[test]
+    fn test_usage_split_by_model() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("rollout-2026-01-04-xyz.jsonl");
+        let count = |input: u64, output: u64| {
+            format!(
+                r#"{{"timestamp":"2026-01-04T10:00:02Z","type":"event_msg","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":{},"cached_input_tokens":0,"output_tokens":{}}}}}}}}}"#,
+                input, output
+            )
+        };
+        let lines = [
+            r#"{"timestamp":"2026-01-04T10:00:00Z","type":"turn_context","payload":{"model":"gpt-5-codex"}}"#.to_string(),
+            count(1000, 100),
+            // Repeated totals add nothing
+            count(1000, 100),
+            r#"{"timestamp":"2026-01-04T10:05:00Z","type":"turn_context","payload":{"model":"gpt-5-mini"}}"#.to_string(),
+            count(3000, 150),
+        ];
+        fs::write(&path, lines.join("\n")).unwrap();
+        let adapter = CodexAdapter {
+            base_dir: tmp.path().to_path_buf(),
+        };
+
+        let hour = "2026-01-04T10:00:00Z".parse().ok();
+        let usage = |model: &str, input: u64, output: u64| ModelUsage {
+            model: model.to_string(),
+            tokens: TokenUsage {
+                input,
+                output,
+                ..Default::default()
+            },
+            hour,
+        };
+        let expected = vec![
+            usage("gpt-5-codex", 1000, 100),
+            usage("gpt-5-mini", 2000, 50),
+        ];
+        let session = adapter.parse_session_file(&path).unwrap();
+        assert_eq!(session.metadata.model_usage, expected);
+        let summary = adapter.summarize_file(&path).unwrap().remove(0);
+        assert_eq!(summary.model_usage, expected);
+        assert_eq!(summary.model.as_deref(), Some("gpt-5-codex"));
+    }
 }
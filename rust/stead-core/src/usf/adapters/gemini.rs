@@ -0,0 +1,816 @@
+//! Gemini CLI session adapter
+//!
+//! Parses sessions from ~/.gemini/tmp/<project hash>/: chat recordings in
+//! `chats/session-*.json` and conversations saved with `/chat save <tag>`
+//! in `checkpoint-<tag>.json`. Gemini names each directory after a SHA-256
+//! of the project root, so the path is only known for roots we can hash
+//! (see `candidate_project_roots`).
+
+use super::{expand_home, path_hash, usage_by_model, AdapterError, SessionAdapter};
+use crate::usf::{
+    AssistantMessage, CliType, ModelInfo, ProjectInfo, SessionMetadata, SessionSource,
+    SessionSummary, SystemMessage, TimelineEntry, TokenUsage, ToolCall, ToolResult,
+    UniversalSession, UniversalTool, UserMessage, USF_VERSION,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+const GEMINI_DIR: &str = "~/.gemini";
+const TMP_DIR: &str = "tmp";
+const CHATS_DIR: &str = "chats";
+const CHECKPOINT_PREFIX: &str = "checkpoint-";
+
+/// Project path of sessions whose project hash matches no known root
+const UNKNOWN_PROJECT: &str = "/unknown";
+
+/// Checkpoints start with an environment briefing sent by the CLI itself
+const SETUP_PREFIX: &str = "This is the Gemini CLI.";
+
+/// Gemini CLI session adapter
+pub struct GeminiAdapter {
+    base_dir: PathBuf,
+    /// Project root by its hash
+    roots: HashMap<String, String>,
+}
+
+impl GeminiAdapter {
+    /// Create a new adapter if Gemini CLI is installed
+    pub fn new() -> Option<Self> {
+        Self::with_base_dir(expand_home(GEMINI_DIR)?)
+    }
+
+    /// Create an adapter reading from `base_dir` instead of the default
+    /// location, if it holds Gemini CLI data
+    pub fn with_base_dir(base_dir: PathBuf) -> Option<Self> {
+        if base_dir.join(TMP_DIR).is_dir() {
+            Some(Self {
+                base_dir,
+                roots: known_project_roots(),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn tmp_dir(&self) -> PathBuf {
+        self.base_dir.join(TMP_DIR)
+    }
+
+    /// Project directories under tmp/, one per project hash
+    fn project_dirs(&self) -> Result<Vec<PathBuf>, AdapterError> {
+        let tmp_dir = self.tmp_dir();
+        if !tmp_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut dirs = Vec::new();
+        for entry in fs::read_dir(&tmp_dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                dirs.push(path);
+            }
+        }
+        Ok(dirs)
+    }
+
+    /// Hash of the project directory under tmp/ that holds `path`
+    fn project_hash_of(&self, path: &Path) -> String {
+        path.ancestors()
+            .find(|p| p.parent() == Some(self.tmp_dir().as_path()))
+            .and_then(|p| p.file_name())
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Parse a chat recording or a checkpoint, depending on the file name
+    fn parse_session_file(&self, path: &Path) -> Result<UniversalSession, AdapterError> {
+        let project_hash = self.project_hash_of(path);
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown");
+
+        match stem.strip_prefix(CHECKPOINT_PREFIX) {
+            Some(tag) => self.parse_checkpoint(path, &project_hash, tag),
+            None => self.parse_chat(path, &project_hash),
+        }
+    }
+
+    fn parse_chat(
+        &self,
+        path: &Path,
+        project_hash: &str,
+    ) -> Result<UniversalSession, AdapterError> {
+        let chat: GeminiChat = load_json_file(path)?;
+        let mut timeline: Vec<TimelineEntry> = Vec::new();
+        let mut model: Option<String> = None;
+        let mut tokens: Option<TokenUsage> = None;
+        let mut usages: Vec<(String, Option<DateTime<Utc>>, TokenUsage)> = Vec::new();
+        let fallback = file_modified(path);
+
+        for msg in &chat.messages {
+            let ts = msg.timestamp.unwrap_or(fallback);
+            let content = text_of(&msg.content);
+
+            match msg.message_type.as_str() {
+                "user" => {
+                    timeline.push(TimelineEntry::User(UserMessage {
+                        id: msg.id.clone(),
+                        timestamp: ts,
+                        content,
+                    }));
+                }
+                "gemini" => {
+                    if model.is_none() {
+                        model = msg.model.clone();
+                    }
+                    let usage = msg.tokens.as_ref().map(TokenUsage::from);
+                    if let Some(usage) = usage {
+                        *tokens.get_or_insert_with(TokenUsage::default) += usage;
+                        let served_by = msg.model.as_deref().or(model.as_deref());
+                        let served_by = served_by.unwrap_or("unknown").to_string();
+                        usages.push((served_by, msg.timestamp, usage));
+                    }
+                    if !content.is_empty() {
+                        timeline.push(TimelineEntry::Assistant(AssistantMessage {
+                            id: msg.id.clone(),
+                            timestamp: ts,
+                            content,
+                            thinking: None,
+                            usage,
+                        }));
+                    }
+                    for call in &msg.tool_calls {
+                        let call_ts = call.timestamp.unwrap_or(ts);
+                        timeline.push(TimelineEntry::ToolCall(ToolCall {
+                            id: call.id.clone(),
+                            timestamp: call_ts,
+                            tool: UniversalTool::from_gemini(&call.name),
+                            input: call.args.clone(),
+                            original_tool: Some(call.name.clone()),
+                        }));
+
+                        // Calls still waiting for approval have no result yet
+                        let Some(status) = &call.status else {
+                            continue;
+                        };
+                        let output = tool_output(&call.result).or_else(|| {
+                            call.result_display
+                                .as_ref()
+                                .and_then(|d| d.as_str())
+                                .map(|s| s.to_string())
+                        });
+                        let success = status == "success";
+                        timeline.push(TimelineEntry::ToolResult(ToolResult {
+                            id: format!("{}-result", call.id),
+                            timestamp: call_ts,
+                            call_id: call.id.clone(),
+                            success,
+                            error: if success { None } else { output.clone() },
+                            output,
+                        }));
+                    }
+                }
+                "info" | "error" | "warning" if !content.is_empty() => {
+                    timeline.push(TimelineEntry::System(SystemMessage {
+                        id: msg.id.clone(),
+                        timestamp: ts,
+                        content,
+                    }));
+                }
+                _ => {}
+            }
+        }
+
+        let created = chat
+            .start_time
+            .or_else(|| timeline.first().map(entry_time))
+            .unwrap_or(fallback);
+        let last_modified = chat
+            .last_updated
+            .or_else(|| timeline.last().map(entry_time))
+            .unwrap_or(fallback);
+        let mut session = self.build_session(
+            chat.session_id,
+            chat.project_hash.as_deref().unwrap_or(project_hash),
+            model,
+            timeline,
+            created,
+            last_modified,
+        );
+        session.metadata.tokens = tokens;
+        session.metadata.model_usage = usage_by_model(usages);
+        Ok(session)
+    }
+
+    /// Checkpoints are raw Gemini API history without timestamps; every
+    /// entry gets the file's modification time
+    fn parse_checkpoint(
+        &self,
+        path: &Path,
+        project_hash: &str,
+        tag: &str,
+    ) -> Result<UniversalSession, AdapterError> {
+        let history: Vec<GeminiContent> = load_json_file(path)?;
+        let ts = file_modified(path);
+        let mut timeline: Vec<TimelineEntry> = Vec::new();
+        let mut skip_reply = false;
+
+        for (index, content) in history.iter().enumerate() {
+            let text: String = content
+                .parts
+                .iter()
+                .filter(|p| !p.thought)
+                .filter_map(|p| p.text.as_deref())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            match content.role.as_str() {
+                "user" => {
+                    if text.starts_with(SETUP_PREFIX) {
+                        skip_reply = true;
+                        continue;
+                    }
+                    if !text.is_empty() {
+                        timeline.push(TimelineEntry::User(UserMessage {
+                            id: index.to_string(),
+                            timestamp: ts,
+                            content: text,
+                        }));
+                    }
+                }
+                "model" => {
+                    // The canned acknowledgement of the setup briefing
+                    if std::mem::take(&mut skip_reply) {
+                        continue;
+                    }
+                    if !text.is_empty() {
+                        timeline.push(TimelineEntry::Assistant(AssistantMessage {
+                            id: index.to_string(),
+                            timestamp: ts,
+                            content: text,
+                            thinking: None,
+                            usage: None,
+                        }));
+                    }
+                }
+                _ => {}
+            }
+
+            for (part_index, part) in content.parts.iter().enumerate() {
+                let fallback_id = format!("{}-{}", index, part_index);
+                if let Some(call) = &part.function_call {
+                    timeline.push(TimelineEntry::ToolCall(ToolCall {
+                        id: call.id.clone().unwrap_or(fallback_id),
+                        timestamp: ts,
+                        tool: UniversalTool::from_gemini(&call.name),
+                        input: call.args.clone(),
+                        original_tool: Some(call.name.clone()),
+                    }));
+                } else if let Some(response) = &part.function_response {
+                    let error = response.response.get("error").map(value_text);
+                    timeline.push(TimelineEntry::ToolResult(ToolResult {
+                        id: fallback_id.clone(),
+                        timestamp: ts,
+                        call_id: response.id.clone().unwrap_or(fallback_id),
+                        success: error.is_none(),
+                        output: Some(function_output(&response.response)),
+                        error,
+                    }));
+                }
+            }
+        }
+
+        let hash_prefix = &project_hash[..8.min(project_hash.len())];
+        Ok(self.build_session(
+            format!("{}{}-{}", CHECKPOINT_PREFIX, hash_prefix, tag),
+            project_hash,
+            None,
+            timeline,
+            ts,
+            ts,
+        ))
+    }
+
+    fn build_session(
+        &self,
+        original_id: String,
+        project_hash: &str,
+        model: Option<String>,
+        timeline: Vec<TimelineEntry>,
+        created: DateTime<Utc>,
+        last_modified: DateTime<Utc>,
+    ) -> UniversalSession {
+        let project_path = self
+            .roots
+            .get(project_hash)
+            .cloned()
+            .unwrap_or_else(|| UNKNOWN_PROJECT.to_string());
+
+        UniversalSession {
+            id: format!("gemini-{}", original_id),
+            version: USF_VERSION.to_string(),
+            source: SessionSource {
+                cli: CliType::Gemini,
+                original_id: Some(original_id),
+            },
+            project: ProjectInfo {
+                path: project_path.clone(),
+                name: project_path.split('/').next_back().map(|s| s.to_string()),
+                git: None, // Gemini CLI doesn't store git info in sessions
+            },
+            model: ModelInfo {
+                provider: "google".to_string(),
+                model: model.unwrap_or_else(|| "unknown".to_string()),
+                config: None,
+            },
+            timeline,
+            metadata: SessionMetadata {
+                created,
+                last_modified,
+                tokens: None,
+                model_usage: Vec::new(),
+                cost: None,
+            },
+        }
+    }
+
+    /// Find the file for a session ID
+    fn find_session_file(&self, id: &str) -> Result<PathBuf, AdapterError> {
+        // ID format: gemini-{session_id} or gemini-checkpoint-{hash prefix}-{tag}
+        let session_id = id.strip_prefix("gemini-").unwrap_or(id);
+
+        if let Some((hash_prefix, tag)) = session_id
+            .strip_prefix(CHECKPOINT_PREFIX)
+            .and_then(|rest| rest.split_once('-'))
+        {
+            for dir in self.project_dirs()? {
+                let in_project = dir
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|name| name.starts_with(hash_prefix));
+                let file = dir.join(format!("{}{}.json", CHECKPOINT_PREFIX, tag));
+                if in_project && file.exists() {
+                    return Ok(file);
+                }
+            }
+            return Err(AdapterError::NotFound(id.to_string()));
+        }
+
+        // Chat recordings are named by start time, so look inside
+        for path in self.session_files()? {
+            let is_chat = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| !s.starts_with(CHECKPOINT_PREFIX));
+            if !is_chat {
+                continue;
+            }
+            let mentions_id = fs::read_to_string(&path)
+                .map(|s| s.contains(session_id))
+                .unwrap_or(false);
+            if mentions_id
+                && load_json_file::<GeminiChat>(&path).is_ok_and(|c| c.session_id == session_id)
+            {
+                return Ok(path);
+            }
+        }
+
+        Err(AdapterError::NotFound(id.to_string()))
+    }
+}
+
+impl SessionAdapter for GeminiAdapter {
+    fn cli_type(&self) -> CliType {
+        CliType::Gemini
+    }
+
+    fn is_available(&self) -> bool {
+        self.tmp_dir().is_dir()
+    }
+
+    fn base_dir(&self) -> Option<PathBuf> {
+        Some(self.base_dir.clone())
+    }
+
+    fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError> {
+        let mut files = Vec::new();
+        let is_json = |p: &Path| p.extension().map(|e| e == "json").unwrap_or(false);
+
+        for dir in self.project_dirs()? {
+            // Saved checkpoints sit next to the chats directory
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                let is_checkpoint = path
+                    .file_name()
+                    .and_then(|s| s.to_str())
+                    .is_some_and(|s| s.starts_with(CHECKPOINT_PREFIX));
+                if is_checkpoint && is_json(&path) {
+                    files.push(path);
+                }
+            }
+
+            let chats_dir = dir.join(CHATS_DIR);
+            if chats_dir.is_dir() {
+                for entry in fs::read_dir(&chats_dir)? {
+                    let path = entry?.path();
+                    if is_json(&path) {
+                        files.push(path);
+                    }
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Gemini rewrites the whole JSON document on every turn, so a summary
+    /// needs a full parse. Which roots can be hashed depends on where stead
+    /// runs, so an unresolved summary keeps its hash for `resolve_summary`.
+    fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError> {
+        let mut summary = SessionSummary::from(&self.parse_session_file(path)?);
+        if summary.project_path == UNKNOWN_PROJECT {
+            summary.project_hash = Some(self.project_hash_of(path));
+        }
+        Ok(vec![summary])
+    }
+
+    fn resolve_summary(&self, summary: &mut SessionSummary) {
+        let root = summary
+            .project_hash
+            .as_ref()
+            .and_then(|hash| self.roots.get(hash));
+        if let Some(root) = root {
+            summary.project_path = root.clone();
+            summary.project_hash = None;
+        }
+    }
+
+    fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError> {
+        self.parse_session_file(&self.find_session_file(id)?)
+    }
+}
+
+/// Candidate project roots by their hash
+fn known_project_roots() -> HashMap<String, String> {
+    super::candidate_project_roots()
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .map(|p| (path_hash(&p), p))
+        .collect()
+}
+
+fn load_json_file<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, AdapterError> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+fn file_modified(path: &Path) -> DateTime<Utc> {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now())
+}
+
+fn entry_time(entry: &TimelineEntry) -> DateTime<Utc> {
+    match entry {
+        TimelineEntry::User(m) => m.timestamp,
+        TimelineEntry::Assistant(m) => m.timestamp,
+        TimelineEntry::ToolCall(c) => c.timestamp,
+        TimelineEntry::ToolResult(r) => r.timestamp,
+        TimelineEntry::System(m) => m.timestamp,
+    }
+}
+
+/// Message content is either a string or a list of `{"text": ...}` parts
+fn text_of(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts
+            .iter()
+            .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// Output of a recorded tool call: the first function response in its result
+fn tool_output(result: &serde_json::Value) -> Option<String> {
+    result
+        .as_array()?
+        .iter()
+        .find_map(|part| part.get("functionResponse")?.get("response"))
+        .map(function_output)
+}
+
+/// A function response is `{"output": ...}` or `{"error": ...}`
+fn function_output(response: &serde_json::Value) -> String {
+    response
+        .get("output")
+        .or_else(|| response.get("error"))
+        .map(value_text)
+        .unwrap_or_else(|| response.to_string())
+}
+
+fn value_text(value: &serde_json::Value) -> String {
+    value
+        .as_str()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| value.to_string())
+}
+
+// Chat recording (chats/session-*.json)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiChat {
+    session_id: String,
+    project_hash: Option<String>,
+    start_time: Option<DateTime<Utc>>,
+    last_updated: Option<DateTime<Utc>>,
+    #[serde(default)]
+    messages: Vec<GeminiMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiMessage {
+    #[serde(default)]
+    id: String,
+    timestamp: Option<DateTime<Utc>>,
+    #[serde(rename = "type")]
+    message_type: String,
+    #[serde(default)]
+    content: serde_json::Value,
+    #[serde(default)]
+    tool_calls: Vec<GeminiToolCall>,
+    model: Option<String>,
+    tokens: Option<GeminiTokens>,
+}
+
+/// Token counts on a gemini message; `input` includes `cached`, and
+/// thoughts are billed as output
+#[derive(Debug, Deserialize)]
+struct GeminiTokens {
+    #[serde(default)]
+    input: u64,
+    #[serde(default)]
+    output: u64,
+    #[serde(default)]
+    cached: u64,
+    #[serde(default)]
+    thoughts: u64,
+    #[serde(default)]
+    tool: u64,
+}
+
+impl From<&GeminiTokens> for TokenUsage {
+    fn from(tokens: &GeminiTokens) -> Self {
+        Self {
+            input: tokens.input.saturating_sub(tokens.cached) + tokens.tool,
+            output: tokens.output + tokens.thoughts,
+            cache_read: tokens.cached,
+            cache_write: 0,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiToolCall {
+    id: String,
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+    #[serde(default)]
+    result: serde_json::Value,
+    status: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+    result_display: Option<serde_json::Value>,
+}
+
+// Checkpoint (checkpoint-<tag>.json): Gemini API conversation history
+#[derive(Debug, Deserialize)]
+struct GeminiContent {
+    role: String,
+    #[serde(default)]
+    parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiPart {
+    text: Option<String>,
+    #[serde(default)]
+    thought: bool,
+    function_call: Option<GeminiFunctionCall>,
+    function_response: Option<GeminiFunctionResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiFunctionCall {
+    id: Option<String>,
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiFunctionResponse {
+    id: Option<String>,
+    #[serde(default)]
+    response: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const CHAT: &str = r#"{
+        "sessionId": "5f2c9a1e-0000-4000-8000-000000000001",
+        "projectHash": "HASH",
+        "startTime": "2026-01-04T10:00:00.000Z",
+        "lastUpdated": "2026-01-04T10:02:00.000Z",
+        "messages": [
+            {"id": "m1", "timestamp": "2026-01-04T10:00:00.000Z", "type": "user",
+             "content": "List the source files"},
+            {"id": "m2", "timestamp": "2026-01-04T10:00:05.000Z", "type": "gemini",
+             "content": "Let me look.", "model": "gemini-2.5-pro",
+             "tokens": {"input": 1500, "output": 40, "cached": 1000, "thoughts": 10,
+                        "tool": 0, "total": 1550},
+             "toolCalls": [
+                {"id": "call-1", "name": "list_directory", "args": {"path": "/work/app/src"},
+                 "status": "success", "timestamp": "2026-01-04T10:00:06.000Z",
+                 "result": [{"functionResponse": {"id": "call-1", "name": "list_directory",
+                             "response": {"output": "main.rs\nlib.rs"}}}]},
+                {"id": "call-2", "name": "run_shell_command", "args": {"command": "false"},
+                 "status": "error", "resultDisplay": "exit code 1"}
+             ]},
+            {"id": "m3", "timestamp": "2026-01-04T10:01:00.000Z", "type": "info",
+             "content": "Request cancelled."},
+            {"id": "m4", "timestamp": "2026-01-04T10:02:00.000Z", "type": "gemini",
+             "content": [{"text": "There are two files."}]}
+        ]
+    }"#;
+
+    const CHECKPOINT: &str = r#"[
+        {"role": "user", "parts": [{"text": "This is the Gemini CLI. We are setting up the context for our chat."}]},
+        {"role": "model", "parts": [{"text": "Got it. Thanks for the context!"}]},
+        {"role": "user", "parts": [{"text": "Read the README"}]},
+        {"role": "model", "parts": [
+            {"text": "thinking it over", "thought": true},
+            {"functionCall": {"id": "fc-1", "name": "read_file", "args": {"absolute_path": "/work/app/README.md"}}}
+        ]},
+        {"role": "user", "parts": [{"functionResponse": {"id": "fc-1", "name": "read_file", "response": {"output": "hello"}}}]},
+        {"role": "model", "parts": [{"text": "It says hello."}]}
+    ]"#;
+
+    /// ~/.gemini fixture with one chat and one checkpoint for /work/app
+    fn fixture() -> (TempDir, GeminiAdapter) {
+        let tmp = TempDir::new().unwrap();
+        let hash = path_hash("/work/app");
+        let project = tmp.path().join(TMP_DIR).join(&hash);
+        fs::create_dir_all(project.join(CHATS_DIR)).unwrap();
+        fs::write(
+            project
+                .join(CHATS_DIR)
+                .join("session-2026-01-04T10-00-5f2c9a1e.json"),
+            CHAT.replace("HASH", &hash),
+        )
+        .unwrap();
+        fs::write(project.join("checkpoint-before-refactor.json"), CHECKPOINT).unwrap();
+
+        let adapter = GeminiAdapter {
+            base_dir: tmp.path().to_path_buf(),
+            roots: HashMap::from([(hash, "/work/app".to_string())]),
+        };
+        (tmp, adapter)
+    }
+
+    #[test]
+    fn test_adapter_creation() {
+        let adapter = GeminiAdapter::new();
+        if let Some(adapter) = adapter {
+            assert_eq!(adapter.cli_type(), CliType::Gemini);
+        }
+    }
+
+    #[test]
+    fn test_project_hash() {
+        // Must match the directory names Gemini CLI creates
+        assert_eq!(
+            path_hash("/work/app"),
+            "70467eff2e0a236497f0693901c782c674520c598f57e72b22e636e00311bd81"
+        );
+    }
+
+    #[test]
+    fn test_chat_recording() {
+        let (_tmp, adapter) = fixture();
+        let session = adapter
+            .load_session("gemini-5f2c9a1e-0000-4000-8000-000000000001")
+            .unwrap();
+
+        assert_eq!(session.source.cli, CliType::Gemini);
+        assert_eq!(session.project.path, "/work/app");
+        assert_eq!(session.model.model, "gemini-2.5-pro");
+        assert_eq!(session.title(), "List the source files");
+        let tokens = TokenUsage {
+            input: 500,
+            output: 50,
+            cache_read: 1000,
+            cache_write: 0,
+        };
+        assert_eq!(session.metadata.tokens, Some(tokens));
+        match &session.timeline[1] {
+            TimelineEntry::Assistant(msg) => assert_eq!(msg.usage, Some(tokens)),
+            other => panic!("expected assistant message, got {:?}", other),
+        }
+
+        let counts = session.message_counts();
+        assert_eq!(
+            (
+                counts.user,
+                counts.assistant,
+                counts.tool_calls,
+                counts.system
+            ),
+            (1, 2, 2, 1)
+        );
+        match &session.timeline[2] {
+            TimelineEntry::ToolCall(call) => assert_eq!(call.tool, UniversalTool::List),
+            other => panic!("expected tool call, got {:?}", other),
+        }
+        match &session.timeline[3] {
+            TimelineEntry::ToolResult(result) => {
+                assert!(result.success);
+                assert_eq!(result.call_id, "call-1");
+                assert_eq!(result.output.as_deref(), Some("main.rs\nlib.rs"));
+            }
+            other => panic!("expected tool result, got {:?}", other),
+        }
+        match &session.timeline[5] {
+            TimelineEntry::ToolResult(result) => {
+                assert!(!result.success);
+                assert_eq!(result.error.as_deref(), Some("exit code 1"));
+            }
+            other => panic!("expected tool result, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let (_tmp, adapter) = fixture();
+        let hash = path_hash("/work/app");
+        let id = format!("gemini-checkpoint-{}-before-refactor", &hash[..8]);
+        let session = adapter.load_session(&id).unwrap();
+
+        assert_eq!(session.id, id);
+        assert_eq!(session.project.path, "/work/app");
+        // The setup briefing and its reply are dropped; thoughts aren't text
+        assert_eq!(session.title(), "Read the README");
+        let counts = session.message_counts();
+        assert_eq!(
+            (
+                counts.user,
+                counts.assistant,
+                counts.tool_calls,
+                counts.tool_results
+            ),
+            (1, 1, 1, 1)
+        );
+        match &session.timeline[1] {
+            TimelineEntry::ToolCall(call) => {
+                assert_eq!(call.tool, UniversalTool::Read);
+                assert_eq!(call.id, "fc-1");
+            }
+            other => panic!("expected tool call, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_list_sessions() {
+        let (_tmp, mut adapter) = fixture();
+        let sessions = adapter.list_sessions().unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions.iter().all(|s| s.project_path == "/work/app"));
+
+        // Sessions of projects we can't hash are still listed
+        let roots = std::mem::take(&mut adapter.roots);
+        let mut sessions = adapter.list_sessions().unwrap();
+        assert!(sessions.iter().all(|s| s.project_path == UNKNOWN_PROJECT));
+
+        // A cached summary is resolved once the root is known
+        adapter.roots = roots;
+        for summary in &mut sessions {
+            adapter.resolve_summary(summary);
+        }
+        assert!(sessions.iter().all(|s| s.project_path == "/work/app"));
+    }
+}
@@ -2,11 +2,23 @@
 //!
 //! Each adapter converts from a CLI's native format to UniversalSession.
 
+pub mod aider;
 pub mod claude;
 pub mod codex;
+pub mod gemini;
 pub mod opencode;
+pub mod plugin;
+pub mod registry;
+pub mod tail;
 
-use crate::usf::{SessionSummary, UniversalSession};
+pub use registry::{AdapterFailure, AdapterRegistry, AdapterRegistryBuilder, Discovery};
+
+use crate::usf::{
+    AssistantMessage, ModelUsage, SessionSummary, TimelineEntry, TokenUsage, UniversalSession,
+};
+use chrono::{DateTime, DurationRound, Utc};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Adapter errors
@@ -26,111 +38,236 @@ pub enum AdapterError {
 
     #[error("Directory not found: {0}")]
     DirectoryNotFound(String),
+
+    #[error("Adapter {name} failed: {message}")]
+    Plugin { name: String, message: String },
 }
 
-/// Common trait for session adapters
-pub trait SessionAdapter {
+/// Common trait for session adapters; `Send + Sync` so a registry can
+/// list them in parallel
+pub trait SessionAdapter: Send + Sync {
     /// Get the CLI type this adapter handles
     fn cli_type(&self) -> crate::usf::CliType;
 
+    /// Name used for ID prefixes, `--cli` filters and error reports
+    fn name(&self) -> &str {
+        self.cli_type().as_str()
+    }
+
     /// Check if this adapter's CLI is installed/available
     fn is_available(&self) -> bool;
 
     /// Get the base directory for this CLI's data
     fn base_dir(&self) -> Option<std::path::PathBuf>;
 
-    /// List all available sessions (summaries only for performance)
-    fn list_sessions(&self) -> Result<Vec<SessionSummary>, AdapterError>;
+    /// Whether summaries are cached in the session index; adapters that
+    /// don't read files list their sessions directly every time
+    fn indexed(&self) -> bool {
+        true
+    }
 
-    /// Load a full session by ID
-    fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError>;
-}
+    /// Every file session summaries are read from
+    fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError>;
 
-/// Discover all available sessions across all installed CLIs
-pub fn discover_all_sessions() -> Vec<SessionSummary> {
-    let mut sessions = Vec::new();
+    /// Build the summaries for one file returned by `session_files`; most
+    /// formats hold exactly one session per file
+    fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError>;
 
-    // Try each adapter
-    if let Some(adapter) = claude::ClaudeAdapter::new() {
-        if let Ok(claude_sessions) = adapter.list_sessions() {
-            sessions.extend(claude_sessions);
-        }
+    /// List all available sessions (summaries only for performance),
+    /// newest first; unparseable files are skipped
+    fn list_sessions(&self) -> Result<Vec<SessionSummary>, AdapterError> {
+        let mut sessions: Vec<SessionSummary> = self
+            .session_files()?
+            .iter()
+            .filter_map(|path| self.summarize_file(path).ok())
+            .flatten()
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_modified));
+        Ok(sessions)
     }
 
-    if let Some(adapter) = codex::CodexAdapter::new() {
-        if let Ok(codex_sessions) = adapter.list_sessions() {
-            sessions.extend(codex_sessions);
-        }
-    }
+    /// Fill in what a cached summary couldn't know when its file was
+    /// parsed, such as a project root only resolvable from the current
+    /// directory
+    fn resolve_summary(&self, _summary: &mut SessionSummary) {}
 
-    if let Some(adapter) = opencode::OpenCodeAdapter::new() {
-        if let Ok(opencode_sessions) = adapter.list_sessions() {
-            sessions.extend(opencode_sessions);
-        }
+    /// Load a full session by ID
+    fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError>;
+
+    /// Open a session for incremental reading as its file grows; only
+    /// append-only formats support this
+    fn tail_session(&self, id: &str) -> Result<tail::SessionTail, AdapterError> {
+        Err(AdapterError::InvalidFormat(format!(
+            "{} sessions can't be read incrementally: {}",
+            self.cli_type().as_str(),
+            id
+        )))
     }
+}
 
-    // Sort by last_modified descending
-    sessions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+/// Discover all available sessions across all installed CLIs, through the
+/// session index
+pub fn discover_all_sessions() -> Vec<SessionSummary> {
+    discover_sessions(false)
+}
 
-    sessions
+/// Discover sessions, re-parsing only files that changed since the last
+/// call (every file with `refresh`). Adapters that fail are left out; use
+/// `AdapterRegistry::discover` to see why.
+pub fn discover_sessions(refresh: bool) -> Vec<SessionSummary> {
+    AdapterRegistry::installed().discover(refresh).sessions
 }
 
 /// Load a session by CLI type and ID
 pub fn load_session(cli: crate::usf::CliType, id: &str) -> Result<UniversalSession, AdapterError> {
     match cli {
-        crate::usf::CliType::Claude => claude::ClaudeAdapter::new()
-            .ok_or_else(|| AdapterError::DirectoryNotFound("~/.claude not found".to_string()))?
-            .load_session(id),
-        crate::usf::CliType::Codex => codex::CodexAdapter::new()
-            .ok_or_else(|| AdapterError::DirectoryNotFound("~/.codex not found".to_string()))?
-            .load_session(id),
-        crate::usf::CliType::OpenCode => opencode::OpenCodeAdapter::new()
-            .ok_or_else(|| {
-                AdapterError::DirectoryNotFound("~/.local/share/opencode not found".to_string())
-            })?
-            .load_session(id),
-        crate::usf::CliType::Universal => Err(AdapterError::InvalidFormat(
-            "Cannot load universal sessions directly".to_string(),
-        )),
+        // Plugin sessions are found by their ID prefix
+        crate::usf::CliType::Universal => load_session_by_id(id),
+        _ => AdapterRegistry::builder()
+            .only(cli.as_str())
+            .build()
+            .load(id),
     }
 }
 
 /// Try to load a session by ID, auto-detecting the CLI from the ID prefix
 pub fn load_session_by_id(id: &str) -> Result<UniversalSession, AdapterError> {
-    // ID format: cli-originalId
-    if let Some((cli_str, _original_id)) = id.split_once('-') {
-        let cli = match cli_str {
-            "claude" => crate::usf::CliType::Claude,
-            "codex" => crate::usf::CliType::Codex,
-            "opencode" => crate::usf::CliType::OpenCode,
-            _ => {
-                return Err(AdapterError::InvalidFormat(format!(
-                    "Unknown CLI prefix: {}",
-                    cli_str
-                )))
-            }
-        };
-        return load_session(cli, id);
+    registry_for_id(id).load(id)
+}
+
+/// Open a session for incremental reading by ID, auto-detecting the CLI
+/// from the ID prefix
+pub fn tail_session_by_id(id: &str) -> Result<tail::SessionTail, AdapterError> {
+    registry_for_id(id).tail(id)
+}
+
+/// A registry with just the adapter named by the ID's prefix, or every
+/// adapter for IDs without one
+fn registry_for_id(id: &str) -> AdapterRegistry {
+    match id.split_once('-') {
+        Some((prefix, _)) => AdapterRegistry::builder().only(prefix).build(),
+        None => AdapterRegistry::installed(),
     }
+}
 
-    // Try each adapter if no prefix
-    if let Some(adapter) = claude::ClaudeAdapter::new() {
-        if let Ok(session) = adapter.load_session(id) {
-            return Ok(session);
+/// Environment variable listing extra project roots (path-list syntax,
+/// like `PATH`)
+pub const PROJECT_ROOTS_ENV: &str = "STEAD_PROJECT_ROOTS";
+
+/// Directories that may be project roots, for CLIs that keep sessions per
+/// project instead of in one central place: projects in the stead
+/// registry, each `STEAD_PROJECT_ROOTS` entry and its immediate
+/// subdirectories, and the current directory with its ancestors
+pub(crate) fn candidate_project_roots() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    if let Ok(Some(registry)) = crate::storage::registry::Registry::open_if_enabled() {
+        roots.extend(
+            registry
+                .projects()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| p.path),
+        );
+    }
+    if let Some(configured) = std::env::var_os(PROJECT_ROOTS_ENV) {
+        for root in std::env::split_paths(&configured) {
+            let children = std::fs::read_dir(&root)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir());
+            let mut children: Vec<PathBuf> = children.collect();
+            children.sort();
+            roots.push(root);
+            roots.extend(children);
         }
     }
-    if let Some(adapter) = codex::CodexAdapter::new() {
-        if let Ok(session) = adapter.load_session(id) {
-            return Ok(session);
+    if let Ok(cwd) = std::env::current_dir() {
+        roots.extend(cwd.ancestors().map(Path::to_path_buf));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    roots.retain(|p| seen.insert(p.clone()));
+    roots
+}
+
+/// Hex SHA-256 of a path: Gemini CLI's project directory names, and a
+/// stable key for per-project sessions
+pub(crate) fn path_hash(path: &str) -> String {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(path.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Add reasoning to a timeline. Reasoning comes before the reply it leads
+/// to, so it opens an assistant entry without content that `push_reply`
+/// completes; reasoning followed by a tool call stays on its own.
+pub(crate) fn push_thinking(
+    timeline: &mut Vec<TimelineEntry>,
+    id: String,
+    timestamp: DateTime<Utc>,
+    thinking: String,
+) {
+    if thinking.trim().is_empty() {
+        return;
+    }
+    if let Some(TimelineEntry::Assistant(msg)) = timeline.last_mut() {
+        if let (true, Some(earlier)) = (msg.content.is_empty(), msg.thinking.as_mut()) {
+            earlier.push_str("\n\n");
+            earlier.push_str(&thinking);
+            return;
         }
     }
-    if let Some(adapter) = opencode::OpenCodeAdapter::new() {
-        if let Ok(session) = adapter.load_session(id) {
-            return Ok(session);
+    timeline.push(TimelineEntry::Assistant(AssistantMessage {
+        id,
+        timestamp,
+        content: String::new(),
+        thinking: Some(thinking),
+        usage: None,
+    }));
+}
+
+/// Add an assistant reply, completing the reasoning just before it if any
+/// (and keeping any usage already recorded on that reasoning)
+pub(crate) fn push_reply(timeline: &mut Vec<TimelineEntry>, reply: AssistantMessage) {
+    if let Some(TimelineEntry::Assistant(msg)) = timeline.last_mut() {
+        if msg.is_thinking_only() {
+            let thinking = msg.thinking.take();
+            let mut usage = reply.usage;
+            if let Some(earlier) = msg.usage {
+                *usage.get_or_insert_with(Default::default) += earlier;
+            }
+            *msg = AssistantMessage {
+                thinking,
+                usage,
+                ..reply
+            };
+            return;
         }
     }
+    timeline.push(TimelineEntry::Assistant(reply));
+}
 
-    Err(AdapterError::NotFound(id.to_string()))
+/// Total tokens per model and hour of use, in model then time order
+pub(crate) fn usage_by_model(
+    usages: impl IntoIterator<Item = (String, Option<DateTime<Utc>>, TokenUsage)>,
+) -> Vec<ModelUsage> {
+    let mut by_model: BTreeMap<(String, Option<DateTime<Utc>>), TokenUsage> = BTreeMap::new();
+    for (model, at, tokens) in usages {
+        let hour = at.and_then(|t| t.duration_trunc(chrono::TimeDelta::hours(1)).ok());
+        *by_model.entry((model, hour)).or_default() += tokens;
+    }
+    by_model
+        .into_iter()
+        .map(|((model, hour), tokens)| ModelUsage {
+            model,
+            hour,
+            tokens,
+        })
+        .collect()
 }
 
 /// Helper to expand ~ in paths
@@ -1,19 +1,20 @@
 //! OpenCode session adapter
 //!
-//! Parses sessions from ~/.local/share/opencode/storage/
+//! Parses sessions from ~/.local/share/opencode/storage/. Reasoning parts
+//! become the `thinking` of the text part that follows them.
 
-use super::{expand_home, AdapterError, SessionAdapter};
+use super::{expand_home, push_reply, push_thinking, usage_by_model, AdapterError, SessionAdapter};
 use crate::usf::{
-    AssistantMessage, CliType, ModelInfo, ProjectInfo, SessionMetadata, SessionSource,
-    SessionSummary, TimelineEntry, ToolCall, ToolResult, UniversalSession, UniversalTool,
-    UserMessage, USF_VERSION,
+    AssistantMessage, CliType, ModelInfo, ModelUsage, ProjectInfo, SessionMetadata, SessionSource,
+    SessionSummary, TimelineEntry, TokenUsage, ToolCall, ToolResult, UniversalSession,
+    UniversalTool, UserMessage, USF_VERSION,
 };
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const OPENCODE_DIR: &str = "~/.local/share/opencode";
 const STORAGE_DIR: &str = "storage";
@@ -26,7 +27,12 @@ pub struct OpenCodeAdapter {
 impl OpenCodeAdapter {
     /// Create a new adapter if OpenCode is installed
     pub fn new() -> Option<Self> {
-        let base_dir = expand_home(OPENCODE_DIR)?;
+        Self::with_base_dir(expand_home(OPENCODE_DIR)?)
+    }
+
+    /// Create an adapter reading from `base_dir` instead of the default
+    /// location, if it holds OpenCode data
+    pub fn with_base_dir(base_dir: PathBuf) -> Option<Self> {
         if base_dir.join(STORAGE_DIR).is_dir() {
             Some(Self { base_dir })
         } else {
@@ -64,97 +70,91 @@ impl OpenCodeAdapter {
         let project_info = self.load_project_info(&session_meta.project_id);
 
         // Load messages for this session
-        let messages_dir = self.messages_dir().join(session_id);
         let mut timeline: Vec<TimelineEntry> = Vec::new();
         let mut tool_call_map: HashMap<String, String> = HashMap::new();
-
-        if messages_dir.exists() {
-            let mut messages: Vec<OpenCodeMessage> = Vec::new();
-
-            for entry in fs::read_dir(&messages_dir)? {
-                let entry = entry?;
-                let path = entry.path();
-                if path.extension().map(|e| e == "json").unwrap_or(false) {
-                    if let Ok(msg) = self.load_json_file::<OpenCodeMessage>(&path) {
-                        messages.push(msg);
-                    }
-                }
-            }
-
-            // Sort messages by creation time
-            messages.sort_by_key(|m| m.time.created);
-
-            // Load parts for each message and build timeline
-            for msg in messages {
-                let parts = self.load_message_parts(&msg.id);
-
-                for part in parts {
-                    let ts = timestamp_to_datetime(part.time.start.max(msg.time.created));
-
-                    match part.part_type.as_str() {
-                        "text" => {
-                            if let Some(text) = part.text {
-                                if !text.is_empty() {
-                                    match msg.role.as_str() {
-                                        "user" => {
-                                            timeline.push(TimelineEntry::User(UserMessage {
+        let mut spend = Spend::default();
+
+        // Load parts for each message and build timeline
+        for msg in self.load_messages(session_id)? {
+            spend.add(&msg);
+            // The message's usage goes on its first text part
+            let mut usage = msg.usage();
+            let parts = self.load_message_parts(&msg.id);
+
+            for part in parts {
+                let ts = timestamp_to_datetime(part.time.start.max(msg.time.created));
+
+                match part.part_type.as_str() {
+                    "text" => {
+                        if let Some(text) = part.text {
+                            if !text.is_empty() {
+                                match msg.role.as_str() {
+                                    "user" => {
+                                        timeline.push(TimelineEntry::User(UserMessage {
+                                            id: part.id.clone(),
+                                            timestamp: ts,
+                                            content: text,
+                                        }));
+                                    }
+                                    "assistant" => {
+                                        push_reply(
+                                            &mut timeline,
+                                            AssistantMessage {
                                                 id: part.id.clone(),
                                                 timestamp: ts,
                                                 content: text,
-                                            }));
-                                        }
-                                        "assistant" => {
-                                            timeline.push(TimelineEntry::Assistant(
-                                                AssistantMessage {
-                                                    id: part.id.clone(),
-                                                    timestamp: ts,
-                                                    content: text,
-                                                    thinking: None,
-                                                },
-                                            ));
-                                        }
-                                        _ => {}
+                                                thinking: None,
+                                                usage: usage.take(),
+                                            },
+                                        );
                                     }
+                                    _ => {}
                                 }
                             }
                         }
-                        "tool-invocation" => {
-                            if let Some(tool_name) = part.tool_name {
-                                let tool = UniversalTool::from_opencode(&tool_name);
-                                let input = part
-                                    .tool_invocation_input
-                                    .map(|s| {
-                                        serde_json::from_str(&s).unwrap_or(serde_json::Value::Null)
-                                    })
-                                    .unwrap_or(serde_json::Value::Null);
-
-                                tool_call_map.insert(part.id.clone(), part.id.clone());
-
-                                timeline.push(TimelineEntry::ToolCall(ToolCall {
-                                    id: part.id.clone(),
-                                    timestamp: ts,
-                                    tool,
-                                    input,
-                                    original_tool: Some(tool_name),
-                                }));
-                            }
+                    }
+                    // Reasoning precedes the text part it led to
+                    "reasoning" => {
+                        if let Some(text) = part.text {
+                            push_thinking(&mut timeline, part.id, ts, text);
                         }
-                        "tool-result" => {
-                            // Tool results reference their invocation
-                            let call_id = part.tool_invocation_id.unwrap_or_default();
-                            let output = part.text;
-
-                            timeline.push(TimelineEntry::ToolResult(ToolResult {
-                                id: part.id,
+                    }
+                    "tool-invocation" => {
+                        if let Some(tool_name) = part.tool_name {
+                            let tool = UniversalTool::from_opencode(&tool_name);
+                            let input = part
+                                .tool_invocation_input
+                                .map(|s| {
+                                    serde_json::from_str(&s).unwrap_or(serde_json::Value::Null)
+                                })
+                                .unwrap_or(serde_json::Value::Null);
+
+                            tool_call_map.insert(part.id.clone(), part.id.clone());
+
+                            timeline.push(TimelineEntry::ToolCall(ToolCall {
+                                id: part.id.clone(),
                                 timestamp: ts,
-                                call_id,
-                                success: true, // OpenCode doesn't have explicit error flag in parts
-                                output,
-                                error: None,
+                                tool,
+                                input,
+                                original_tool: Some(tool_name),
                             }));
                         }
-                        _ => {}
                     }
+                    "tool-result" => {
+                        // Tool results reference their invocation
+                        let call_id = part.tool_invocation_id.unwrap_or_default();
+                        let output = part.text;
+
+                        timeline.push(TimelineEntry::ToolResult(ToolResult {
+                            id: part.id,
+                            timestamp: ts,
+                            call_id,
+                            success: true, // OpenCode doesn't have explicit error flag in parts
+                            output,
+                            error: None,
+                        }));
+                    }
+                    _ => {}
                 }
             }
         }
@@ -167,6 +167,7 @@ impl OpenCodeAdapter {
             .map(|p| p.directory.clone())
             .unwrap_or_else(|| "/unknown".to_string());
 
+        let model_usage = spend.model_usage();
         Ok(UniversalSession {
             id: format!("opencode-{}", session_id),
             version: USF_VERSION.to_string(),
@@ -180,20 +181,44 @@ impl OpenCodeAdapter {
                 git: None, // OpenCode doesn't store git info in sessions
             },
             model: ModelInfo {
-                provider: "unknown".to_string(),
-                model: "unknown".to_string(),
+                provider: spend.provider.unwrap_or_else(|| "unknown".to_string()),
+                model: spend.model.unwrap_or_else(|| "unknown".to_string()),
                 config: None,
             },
             timeline,
             metadata: SessionMetadata {
                 created,
                 last_modified,
-                tokens: None,
-                cost: None,
+                tokens: spend.tokens,
+                model_usage,
+                cost: spend.cost,
             },
         })
     }
 
+    /// A session's messages, oldest first
+    fn load_messages(&self, session_id: &str) -> Result<Vec<OpenCodeMessage>, AdapterError> {
+        let messages_dir = self.messages_dir().join(session_id);
+        let mut messages: Vec<OpenCodeMessage> = Vec::new();
+        if !messages_dir.exists() {
+            return Ok(messages);
+        }
+
+        for entry in fs::read_dir(&messages_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.extension().map(|e| e == "json").unwrap_or(false) {
+                if let Ok(msg) = self.load_json_file::<OpenCodeMessage>(&path) {
+                    messages.push(msg);
+                }
+            }
+        }
+
+        // Sort messages by creation time
+        messages.sort_by_key(|m| m.time.created);
+        Ok(messages)
+    }
+
     fn find_session_file(&self, session_id: &str) -> Result<PathBuf, AdapterError> {
         // Sessions are stored in directories named by project ID
         // Session files are named: ses_{id}.json
@@ -229,10 +254,7 @@ impl OpenCodeAdapter {
         Err(AdapterError::NotFound(session_id.to_string()))
     }
 
-    fn load_json_file<T: for<'de> Deserialize<'de>>(
-        &self,
-        path: &PathBuf,
-    ) -> Result<T, AdapterError> {
+    fn load_json_file<T: for<'de> Deserialize<'de>>(&self, path: &Path) -> Result<T, AdapterError> {
         let file = File::open(path)?;
         let reader = BufReader::new(file);
         Ok(serde_json::from_reader(reader)?)
@@ -330,15 +352,13 @@ impl OpenCodeAdapter {
                 )
             });
 
-        // Count messages
-        let messages_dir = self.messages_dir().join(&session_meta.id);
-        let message_count = if messages_dir.exists() {
-            fs::read_dir(&messages_dir)
-                .map(|entries| entries.count())
-                .unwrap_or(0)
-        } else {
-            0
-        };
+        // Count messages and add up their usage
+        let messages = self.load_messages(&session_meta.id).unwrap_or_default();
+        let message_count = messages.len();
+        let mut spend = Spend::default();
+        for msg in &messages {
+            spend.add(msg);
+        }
 
         SessionSummary {
             id: format!("opencode-{}", session_meta.id),
@@ -349,6 +369,11 @@ impl OpenCodeAdapter {
             last_modified: timestamp_to_datetime(session_meta.time.updated),
             message_count,
             git_branch: None,
+            model_usage: spend.model_usage(),
+            model: spend.model,
+            tokens: spend.tokens,
+            cost: spend.cost,
+            project_hash: None,
         }
     }
 }
@@ -366,12 +391,12 @@ impl SessionAdapter for OpenCodeAdapter {
         Some(self.base_dir.clone())
     }
 
-    fn list_sessions(&self) -> Result<Vec<SessionSummary>, AdapterError> {
-        let mut sessions = Vec::new();
+    fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError> {
+        let mut files = Vec::new();
         let sessions_dir = self.sessions_dir();
 
         if !sessions_dir.exists() {
-            return Ok(sessions);
+            return Ok(files);
         }
 
         // Sessions are in project subdirectories
@@ -395,16 +420,18 @@ impl SessionAdapter for OpenCodeAdapter {
                     continue;
                 }
 
-                if let Ok(session_meta) = self.load_json_file::<OpenCodeSession>(&session_path) {
-                    sessions.push(self.build_session_summary(&session_meta));
-                }
+                files.push(session_path);
             }
         }
 
-        // Sort by last_modified descending
-        sessions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+        Ok(files)
+    }
 
-        Ok(sessions)
+    /// Summarize from the session's metadata file (OpenCode rewrites it
+    /// whenever the session changes)
+    fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError> {
+        let session_meta = self.load_json_file::<OpenCodeSession>(path)?;
+        Ok(vec![self.build_session_summary(&session_meta)])
     }
 
     fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError> {
@@ -414,6 +441,40 @@ impl SessionAdapter for OpenCodeAdapter {
     }
 }
 
+/// Model and totals over a session's assistant messages
+#[derive(Default)]
+struct Spend {
+    model: Option<String>,
+    provider: Option<String>,
+    tokens: Option<TokenUsage>,
+    /// Usage of each message with the model that served it
+    by_message: Vec<(String, Option<DateTime<Utc>>, TokenUsage)>,
+    cost: Option<f64>,
+}
+
+impl Spend {
+    fn add(&mut self, msg: &OpenCodeMessage) {
+        if self.model.is_none() {
+            self.model = msg.model_id.clone();
+            self.provider = msg.provider_id.clone();
+        }
+        if let Some(usage) = msg.usage() {
+            *self.tokens.get_or_insert_with(TokenUsage::default) += usage;
+            let model = msg.model_id.as_deref().or(self.model.as_deref());
+            let at = timestamp_to_datetime(msg.time.created);
+            self.by_message
+                .push((model.unwrap_or("unknown").to_string(), Some(at), usage));
+        }
+        if let Some(cost) = msg.cost {
+            *self.cost.get_or_insert(0.0) += cost;
+        }
+    }
+
+    fn model_usage(&self) -> Vec<ModelUsage> {
+        usage_by_model(self.by_message.iter().cloned())
+    }
+}
+
 fn timestamp_to_datetime(ts: i64) -> DateTime<Utc> {
     // OpenCode timestamps are in milliseconds
     DateTime::from_timestamp_millis(ts).unwrap_or_else(Utc::now)
@@ -459,6 +520,46 @@ struct OpenCodeMessage {
     #[allow(dead_code)]
     session_id: String,
     time: OpenCodeMessageTime,
+    // Assistant messages only
+    #[serde(rename = "modelID")]
+    model_id: Option<String>,
+    #[serde(rename = "providerID")]
+    provider_id: Option<String>,
+    cost: Option<f64>,
+    tokens: Option<OpenCodeTokens>,
+}
+
+impl OpenCodeMessage {
+    fn usage(&self) -> Option<TokenUsage> {
+        let tokens = self.tokens.as_ref()?;
+        Some(TokenUsage {
+            input: tokens.input,
+            // Reasoning is billed as output
+            output: tokens.output + tokens.reasoning,
+            cache_read: tokens.cache.read,
+            cache_write: tokens.cache.write,
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenCodeTokens {
+    #[serde(default)]
+    input: u64,
+    #[serde(default)]
+    output: u64,
+    #[serde(default)]
+    reasoning: u64,
+    #[serde(default)]
+    cache: OpenCodeCache,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenCodeCache {
+    #[serde(default)]
+    read: u64,
+    #[serde(default)]
+    write: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -524,6 +625,91 @@ mod tests {
         assert_eq!(session.project_id, "proj_123");
     }
 
+    #[test]
+    fn test_message_usage() {
+        let json = r#"{"id":"msg_1","role":"assistant","sessionId":"ses_test","time":{"created":1759497134754},"modelID":"claude-sonnet-4","providerID":"anthropic","cost":0.012,"tokens":{"input":120,"output":40,"reasoning":10,"cache":{"read":2000,"write":300}}}"#;
+        let msg: OpenCodeMessage = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            msg.usage(),
+            Some(TokenUsage {
+                input: 120,
+                output: 50,
+                cache_read: 2000,
+                cache_write: 300,
+            })
+        );
+
+        let mut spend = Spend::default();
+        spend.add(&msg);
+        spend.add(&msg);
+        assert_eq!(spend.model.as_deref(), Some("claude-sonnet-4"));
+        assert_eq!(spend.tokens.unwrap().output, 100);
+        assert!((spend.cost.unwrap() - 0.024).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reasoning_parts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let storage = tmp.path().join(STORAGE_DIR);
+        let write = |path: &str, json: serde_json::Value| {
+            let path = storage.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, json.to_string()).unwrap();
+        };
+        write(
+            "session/prj/ses_1.json",
+            serde_json::json!({"id": "ses_1", "projectId": "prj",
+                               "time": {"created": 1000, "updated": 5000}}),
+        );
+        write(
+            "message/ses_1/msg_1.json",
+            serde_json::json!({"id": "msg_1", "role": "assistant", "sessionId": "ses_1",
+                               "time": {"created": 1000}}),
+        );
+        let part = |id: &str, kind: &str, text: &str, start: i64| {
+            serde_json::json!({"id": id, "type": kind, "text": text, "messageId": "msg_1",
+                               "sessionId": "ses_1", "time": {"start": start, "end": start}})
+        };
+        write(
+            "part/msg_1/prt_1.json",
+            part("prt_1", "reasoning", "Check the tests first", 1),
+        );
+        write(
+            "part/msg_1/prt_2.json",
+            part("prt_2", "reasoning", "They cover parsing", 2),
+        );
+        write(
+            "part/msg_1/prt_3.json",
+            part("prt_3", "text", "Tests pass.", 3),
+        );
+        write(
+            "part/msg_1/prt_4.json",
+            part("prt_4", "reasoning", "Maybe add more", 4),
+        );
+
+        let adapter = OpenCodeAdapter::with_base_dir(tmp.path().to_path_buf()).unwrap();
+        let session = adapter.load_session("opencode-ses_1").unwrap();
+        let replies: Vec<(&str, Option<&str>)> = session
+            .timeline
+            .iter()
+            .filter_map(|e| match e {
+                TimelineEntry::Assistant(m) => Some((m.content.as_str(), m.thinking.as_deref())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            replies,
+            vec![
+                (
+                    "Tests pass.",
+                    Some("Check the tests first\n\nThey cover parsing")
+                ),
+                ("", Some("Maybe add more")),
+            ]
+        );
+        assert_eq!(session.message_counts().assistant, 1);
+    }
+
     #[test]
     fn test_part_parsing() {
         let json = r#"{"id":"prt_test","type":"text","text":"Hello","synthetic":false,"time":{"start":0,"end":0},"messageId":"msg_test","sessionId":"ses_test"}"#;
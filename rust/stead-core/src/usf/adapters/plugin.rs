@@ -0,0 +1,382 @@
+//! External session adapters
+//!
+//! Agents stead doesn't know about can be supported without changing it:
+//! an executable declared in `~/.config/stead/adapters.toml` (override
+//! with `STEAD_ADAPTERS`) answers requests passed as its last arguments,
+//! writing JSON to stdout:
+//!
+//! - `list`: an array of session summaries (`id`, `project_path`,
+//!   `created`, and optionally `title`, `last_modified`, `message_count`,
+//!   `git_branch`, `model`, `tokens`, `cost`)
+//! - `load <id>`: one session in the Universal Session Format
+//!
+//! ```toml
+//! [[adapter]]
+//! name = "acme"
+//! command = "acme-agent"
+//! args = ["sessions"]
+//! timeout_secs = 10
+//! ```
+//!
+//! IDs are the plugin's own, prefixed with the adapter name (`acme-<id>`)
+//! like built-in adapters. Sessions are reported with the `universal` CLI
+//! type. Plugins don't read files stead can fingerprint, so their listings
+//! bypass the session index.
+
+use super::{expand_home, AdapterError, SessionAdapter};
+use crate::process::run_with_timeout;
+use crate::usf::{CliType, SessionSource, SessionSummary, TokenUsage, UniversalSession};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+const ADAPTERS_DIR: &str = "~/.config/stead";
+const ADAPTERS_FILE: &str = "adapters.toml";
+
+/// Environment variable overriding the adapters config path
+pub const ADAPTERS_ENV: &str = "STEAD_ADAPTERS";
+
+/// Names taken by built-in adapters (and the `universal` CLI type)
+const RESERVED_NAMES: &[&str] = &[
+    "claude",
+    "codex",
+    "opencode",
+    "gemini",
+    "aider",
+    "universal",
+];
+
+/// One `[[adapter]]` entry
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginSpec {
+    /// ID prefix and `--cli` name; letters, digits and `_` only
+    pub name: String,
+    /// Executable to run (`~/` is expanded)
+    pub command: String,
+    /// Arguments placed before the request
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Requests running longer than this are killed
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AdaptersFile {
+    #[serde(default)]
+    adapter: Vec<PluginSpec>,
+}
+
+/// A summary as written by a plugin's `list`
+#[derive(Debug, Deserialize)]
+struct ListedSession {
+    id: String,
+    project_path: String,
+    #[serde(default)]
+    title: String,
+    created: DateTime<Utc>,
+    #[serde(default)]
+    last_modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    message_count: usize,
+    #[serde(default)]
+    git_branch: Option<String>,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    tokens: Option<TokenUsage>,
+    #[serde(default)]
+    cost: Option<f64>,
+}
+
+/// Path of the adapters config (`STEAD_ADAPTERS` or the default location)
+pub fn config_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(ADAPTERS_ENV) {
+        if !path.is_empty() {
+            return Some(PathBuf::from(path));
+        }
+    }
+    Some(expand_home(ADAPTERS_DIR)?.join(ADAPTERS_FILE))
+}
+
+/// Plugins declared in the default config; none if it doesn't exist
+pub fn configured_plugins() -> Result<Vec<PluginSpec>, AdapterError> {
+    match config_path() {
+        Some(path) => load_specs(&path),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Parse and validate an adapters config; a missing file declares nothing
+pub fn load_specs(path: &Path) -> Result<Vec<PluginSpec>, AdapterError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let invalid =
+        |message: String| AdapterError::InvalidFormat(format!("{}: {}", path.display(), message));
+
+    let file: AdaptersFile = toml::from_str(&content).map_err(|e| invalid(e.message().into()))?;
+    let mut seen = std::collections::HashSet::new();
+    for spec in &file.adapter {
+        let valid_name = !spec.name.is_empty()
+            && spec
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_name {
+            return Err(invalid(format!(
+                "adapter name '{}' must be letters, digits and '_'",
+                spec.name
+            )));
+        }
+        if RESERVED_NAMES.contains(&spec.name.to_lowercase().as_str()) {
+            return Err(invalid(format!(
+                "adapter name '{}' is taken by a built-in adapter",
+                spec.name
+            )));
+        }
+        // Names are matched case-insensitively (`--cli`, ID prefixes)
+        if !seen.insert(spec.name.to_lowercase()) {
+            return Err(invalid(format!(
+                "adapter '{}' is declared twice",
+                spec.name
+            )));
+        }
+    }
+    Ok(file.adapter)
+}
+
+/// Adapter backed by an external executable
+pub struct PluginAdapter {
+    spec: PluginSpec,
+}
+
+impl PluginAdapter {
+    pub fn new(spec: PluginSpec) -> Self {
+        Self { spec }
+    }
+
+    /// Run one request and return its stdout
+    fn request(&self, request: &[&str]) -> Result<String, AdapterError> {
+        let program =
+            expand_home(&self.spec.command).unwrap_or_else(|| PathBuf::from(&self.spec.command));
+        let mut cmd = Command::new(program);
+        cmd.args(&self.spec.args).args(request);
+
+        let failed = |message: String| AdapterError::Plugin {
+            name: self.spec.name.clone(),
+            message,
+        };
+        let timeout = Duration::from_secs(self.spec.timeout_secs);
+        let output = run_with_timeout(cmd, Some(timeout))
+            .map_err(|e| failed(format!("can't run {}: {}", self.spec.command, e)))?;
+        if output.timed_out {
+            return Err(failed(format!(
+                "'{}' timed out after {}s",
+                request.join(" "),
+                self.spec.timeout_secs
+            )));
+        }
+        if !output.success {
+            return Err(failed(
+                output
+                    .combined()
+                    .unwrap_or_else(|| format!("'{}' failed", request.join(" "))),
+            ));
+        }
+        Ok(output.stdout)
+    }
+
+    fn prefixed(&self, id: &str) -> String {
+        format!("{}-{}", self.spec.name, id)
+    }
+}
+
+impl SessionAdapter for PluginAdapter {
+    fn cli_type(&self) -> CliType {
+        CliType::Universal
+    }
+
+    fn name(&self) -> &str {
+        &self.spec.name
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn base_dir(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn indexed(&self) -> bool {
+        false
+    }
+
+    fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError> {
+        Ok(Vec::new())
+    }
+
+    fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError> {
+        Err(AdapterError::InvalidFormat(format!(
+            "adapter {} doesn't read files: {}",
+            self.spec.name,
+            path.display()
+        )))
+    }
+
+    fn list_sessions(&self) -> Result<Vec<SessionSummary>, AdapterError> {
+        let listed: Vec<ListedSession> = serde_json::from_str(&self.request(&["list"])?)?;
+        let mut sessions: Vec<SessionSummary> = listed
+            .into_iter()
+            .map(|s| SessionSummary {
+                id: self.prefixed(&s.id),
+                cli: CliType::Universal,
+                project_path: s.project_path,
+                title: s.title,
+                created: s.created,
+                last_modified: s.last_modified.unwrap_or(s.created),
+                message_count: s.message_count,
+                git_branch: s.git_branch,
+                model: s.model,
+                tokens: s.tokens,
+                model_usage: Vec::new(),
+                cost: s.cost,
+                project_hash: None,
+            })
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_modified));
+        Ok(sessions)
+    }
+
+    fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError> {
+        let original = id
+            .strip_prefix(&format!("{}-", self.spec.name))
+            .unwrap_or(id);
+        let mut session: UniversalSession =
+            serde_json::from_str(&self.request(&["load", original])?)?;
+        session.id = self.prefixed(original);
+        session.source = SessionSource {
+            cli: CliType::Universal,
+            original_id: Some(original.to_string()),
+        };
+        Ok(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const SCRIPT: &str = r#"
+case "$1" in
+  list)
+    echo '[{"id": "7", "project_path": "/work/app", "title": "Fix the build",
+            "created": "2026-01-02T10:00:00Z", "message_count": 2},
+           {"id": "8", "project_path": "/work/app",
+            "created": "2026-01-03T10:00:00Z"}]'
+    ;;
+  load)
+    [ "$2" = "7" ] || { echo "no session $2" >&2; exit 1; }
+    echo '{"id": "x", "version": "1.0.0",
+           "source": {"cli": "universal"},
+           "project": {"path": "/work/app"},
+           "model": {"provider": "acme", "model": "acme-1"},
+           "timeline": [{"type": "user", "id": "u1",
+                         "timestamp": "2026-01-02T10:00:00Z", "content": "Fix the build"}],
+           "metadata": {"created": "2026-01-02T10:00:00Z",
+                        "last_modified": "2026-01-02T10:05:00Z"}}'
+    ;;
+esac
+"#;
+
+    fn plugin(tmp: &TempDir) -> PluginAdapter {
+        let script = tmp.path().join("acme.sh");
+        std::fs::write(&script, SCRIPT).unwrap();
+        PluginAdapter::new(PluginSpec {
+            name: "acme".to_string(),
+            command: "sh".to_string(),
+            args: vec![script.to_string_lossy().to_string()],
+            timeout_secs: 10,
+        })
+    }
+
+    #[test]
+    fn test_list_prefixes_ids() {
+        let tmp = TempDir::new().unwrap();
+        let sessions = plugin(&tmp).list_sessions().unwrap();
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].id, "acme-8");
+        assert_eq!(sessions[0].last_modified, sessions[0].created);
+        assert_eq!(sessions[1].id, "acme-7");
+        assert_eq!(sessions[1].title, "Fix the build");
+        assert_eq!(sessions[1].message_count, 2);
+        assert_eq!(sessions[1].cli, CliType::Universal);
+    }
+
+    #[test]
+    fn test_load_session() {
+        let tmp = TempDir::new().unwrap();
+        let adapter = plugin(&tmp);
+
+        let session = adapter.load_session("acme-7").unwrap();
+        assert_eq!(session.id, "acme-7");
+        assert_eq!(session.source.original_id.as_deref(), Some("7"));
+        assert_eq!(session.model.model, "acme-1");
+        assert_eq!(session.timeline.len(), 1);
+
+        let err = adapter.load_session("acme-9").unwrap_err().to_string();
+        assert!(err.contains("acme"), "{}", err);
+        assert!(err.contains("no session 9"), "{}", err);
+    }
+
+    #[test]
+    fn test_load_specs() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("adapters.toml");
+        assert!(load_specs(&path).unwrap().is_empty());
+
+        std::fs::write(
+            &path,
+            "[[adapter]]\nname = \"acme\"\ncommand = \"~/bin/acme\"\nargs = [\"sessions\"]\n",
+        )
+        .unwrap();
+        let specs = load_specs(&path).unwrap();
+        assert_eq!(specs.len(), 1);
+        assert_eq!(specs[0].args, vec!["sessions"]);
+        assert_eq!(specs[0].timeout_secs, 30);
+
+        for bad in ["acme-2", "claude", ""] {
+            std::fs::write(
+                &path,
+                format!("[[adapter]]\nname = \"{}\"\ncommand = \"x\"\n", bad),
+            )
+            .unwrap();
+            assert!(load_specs(&path).is_err(), "{}", bad);
+        }
+
+        for (first, second) in [("a", "a"), ("Acme", "acme")] {
+            std::fs::write(
+                &path,
+                format!(
+                    "[[adapter]]\nname = \"{}\"\ncommand = \"x\"\n[[adapter]]\nname = \"{}\"\ncommand = \"y\"\n",
+                    first, second
+                ),
+            )
+            .unwrap();
+            assert!(load_specs(&path).is_err(), "{} {}", first, second);
+        }
+    }
+}
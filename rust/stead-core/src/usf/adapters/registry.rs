@@ -0,0 +1,451 @@
+//! Adapter registry
+//!
+//! `AdapterRegistry` holds the session adapters a command works with:
+//! every installed CLI and configured plugin, or only those named with
+//! `only`, which are the only ones constructed, so `--cli claude` never
+//! scans another CLI's data. Discovery runs the adapters in parallel, each
+//! syncing the session index over its own connection, and an adapter that
+//! fails is reported by name in `Discovery::errors` instead of dropped.
+
+use super::plugin::{self, PluginAdapter};
+use super::tail::SessionTail;
+use super::{aider, claude, codex, gemini, opencode, AdapterError, SessionAdapter};
+use crate::usf::index::SessionIndex;
+use crate::usf::{CliType, SessionSummary, UniversalSession};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Built-in adapters, in listing order
+const BUILT_IN: [CliType; 5] = [
+    CliType::Claude,
+    CliType::Codex,
+    CliType::OpenCode,
+    CliType::Gemini,
+    CliType::Aider,
+];
+
+/// An adapter that couldn't be built or failed to list its sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AdapterFailure {
+    /// CLI or plugin name; `plugins` when the adapters config is unreadable
+    pub adapter: String,
+    pub message: String,
+}
+
+impl AdapterFailure {
+    fn new(adapter: &str, error: impl ToString) -> Self {
+        Self {
+            adapter: adapter.to_string(),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for AdapterFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.adapter, self.message)
+    }
+}
+
+/// Sessions found by `AdapterRegistry::discover`
+#[derive(Debug, Default)]
+pub struct Discovery {
+    /// Every adapter's sessions, newest first
+    pub sessions: Vec<SessionSummary>,
+    /// Adapters that couldn't be built or listed
+    pub errors: Vec<AdapterFailure>,
+}
+
+/// Builder for an `AdapterRegistry`
+#[derive(Debug, Clone)]
+pub struct AdapterRegistryBuilder {
+    only: Vec<String>,
+    base_dirs: HashMap<CliType, PathBuf>,
+    plugins_config: Option<PathBuf>,
+    index: Option<PathBuf>,
+}
+
+impl Default for AdapterRegistryBuilder {
+    fn default() -> Self {
+        Self {
+            only: Vec::new(),
+            base_dirs: HashMap::new(),
+            plugins_config: None,
+            index: SessionIndex::default_path(),
+        }
+    }
+}
+
+impl AdapterRegistryBuilder {
+    /// Build this adapter (a CLI or plugin name) and skip those not named;
+    /// repeat to name several
+    pub fn only(mut self, name: impl Into<String>) -> Self {
+        self.only.push(name.into().to_lowercase());
+        self
+    }
+
+    /// Read `cli`'s data from `dir` instead of its default location; for
+    /// Aider, `dir` is the project root whose chat history is read
+    pub fn base_dir(mut self, cli: CliType, dir: impl Into<PathBuf>) -> Self {
+        self.base_dirs.insert(cli, dir.into());
+        self
+    }
+
+    /// Read plugin declarations from `path` instead of the adapters config
+    pub fn plugins_config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.plugins_config = Some(path.into());
+        self
+    }
+
+    /// Cache summaries in the index at `path` instead of the default one
+    pub fn index(mut self, path: impl Into<PathBuf>) -> Self {
+        self.index = Some(path.into());
+        self
+    }
+
+    /// List sessions by parsing every file, without the session index
+    pub fn without_index(mut self) -> Self {
+        self.index = None;
+        self
+    }
+
+    /// Construct the selected adapters. Adapters whose CLI isn't installed
+    /// are left out, and reported as failures only if named with `only`.
+    pub fn build(self) -> AdapterRegistry {
+        let named = |name: &str| self.only.is_empty() || self.only.iter().any(|n| n == name);
+        let mut adapters: Vec<Box<dyn SessionAdapter>> = Vec::new();
+        let mut errors = Vec::new();
+
+        for cli in BUILT_IN.into_iter().filter(|cli| named(cli.as_str())) {
+            match built_in(cli, self.base_dirs.get(&cli)) {
+                Some(adapter) => adapters.push(adapter),
+                None if !self.only.is_empty() => {
+                    errors.push(AdapterFailure::new(cli.as_str(), not_installed(cli)))
+                }
+                None => {}
+            }
+        }
+
+        let wants_plugins =
+            self.only.is_empty() || self.only.iter().any(|n| built_in_named(n).is_none());
+        if wants_plugins {
+            let specs = match &self.plugins_config {
+                Some(path) => plugin::load_specs(path),
+                None => plugin::configured_plugins(),
+            };
+            match specs {
+                Ok(specs) => {
+                    for name in &self.only {
+                        let known = built_in_named(name).is_some()
+                            || specs.iter().any(|s| s.name.eq_ignore_ascii_case(name));
+                        if !known {
+                            errors.push(AdapterFailure::new(
+                                name,
+                                "Unknown CLI. Valid options: claude, codex, opencode, gemini, aider, or a configured adapter",
+                            ));
+                        }
+                    }
+                    adapters.extend(
+                        specs
+                            .into_iter()
+                            .filter(|spec| named(&spec.name.to_lowercase()))
+                            .map(|spec| {
+                                Box::new(PluginAdapter::new(spec)) as Box<dyn SessionAdapter>
+                            }),
+                    );
+                }
+                Err(e) => errors.push(AdapterFailure::new("plugins", e)),
+            }
+        }
+
+        AdapterRegistry {
+            adapters,
+            errors,
+            index: self.index,
+        }
+    }
+}
+
+/// The session adapters a command works with
+pub struct AdapterRegistry {
+    adapters: Vec<Box<dyn SessionAdapter>>,
+    errors: Vec<AdapterFailure>,
+    index: Option<PathBuf>,
+}
+
+impl AdapterRegistry {
+    pub fn builder() -> AdapterRegistryBuilder {
+        AdapterRegistryBuilder::default()
+    }
+
+    /// Every installed CLI and configured plugin, with the default index
+    pub fn installed() -> Self {
+        Self::builder().build()
+    }
+
+    /// Adapters in listing order: built-ins, then plugins
+    pub fn adapters(&self) -> impl Iterator<Item = &dyn SessionAdapter> {
+        self.adapters.iter().map(|a| a.as_ref())
+    }
+
+    /// The adapter with this name, if it was built
+    pub fn get(&self, name: &str) -> Option<&dyn SessionAdapter> {
+        self.adapters()
+            .find(|a| a.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Adapters that were named but couldn't be built
+    pub fn errors(&self) -> &[AdapterFailure] {
+        &self.errors
+    }
+
+    /// Sessions from every adapter, listed in parallel; `refresh` re-parses
+    /// every file instead of only those changed since the last sync. Build
+    /// failures are included in the errors.
+    pub fn discover(&self, refresh: bool) -> Discovery {
+        // Opening once up front creates or upgrades the schema before the
+        // per-adapter connections use it; without it, every file is parsed
+        let index = self
+            .index
+            .as_deref()
+            .filter(|path| SessionIndex::open(path).is_ok());
+
+        let mut discovery = Discovery {
+            sessions: Vec::new(),
+            errors: self.errors.clone(),
+        };
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .adapters()
+                .map(|adapter| {
+                    let handle = scope.spawn(move || list(adapter, index, refresh));
+                    (adapter.name(), handle)
+                })
+                .collect();
+            for (name, handle) in handles {
+                match handle.join() {
+                    Ok(Ok(sessions)) => discovery.sessions.extend(sessions),
+                    Ok(Err(e)) => discovery.errors.push(AdapterFailure::new(name, e)),
+                    Err(_) => discovery
+                        .errors
+                        .push(AdapterFailure::new(name, "panicked while listing sessions")),
+                }
+            }
+        });
+
+        discovery
+            .sessions
+            .sort_by_key(|s| std::cmp::Reverse(s.last_modified));
+        discovery
+    }
+
+    /// Load a full session by ID
+    pub fn load(&self, id: &str) -> Result<UniversalSession, AdapterError> {
+        self.with_owner(id, |adapter| adapter.load_session(id))
+    }
+
+    /// Open a session for incremental reading by ID
+    pub fn tail(&self, id: &str) -> Result<SessionTail, AdapterError> {
+        self.with_owner(id, |adapter| adapter.tail_session(id))
+    }
+
+    /// Run `f` with the adapter owning `id`: the one named by its prefix,
+    /// or else the first adapter that succeeds
+    fn with_owner<T>(
+        &self,
+        id: &str,
+        f: impl Fn(&dyn SessionAdapter) -> Result<T, AdapterError>,
+    ) -> Result<T, AdapterError> {
+        // ID format: cli-originalId
+        if let Some((prefix, _original_id)) = id.split_once('-') {
+            if let Some(adapter) = self.get(prefix) {
+                return f(adapter);
+            }
+            return Err(match built_in_named(prefix) {
+                Some(cli) => not_installed(cli),
+                None => AdapterError::InvalidFormat(format!("Unknown CLI prefix: {}", prefix)),
+            });
+        }
+
+        // Try each adapter if no prefix
+        self.adapters()
+            .find_map(|adapter| f(adapter).ok())
+            .ok_or_else(|| AdapterError::NotFound(id.to_string()))
+    }
+}
+
+/// Summaries for one adapter, through the index when it has one
+fn list(
+    adapter: &dyn SessionAdapter,
+    index: Option<&Path>,
+    refresh: bool,
+) -> Result<Vec<SessionSummary>, AdapterError> {
+    if let Some(path) = index.filter(|_| adapter.indexed()) {
+        let indexed = SessionIndex::open(path).and_then(|index| {
+            index.sync(adapter, refresh)?;
+            index.summaries(adapter)
+        });
+        // The index is only a cache; fall back to parsing every file
+        if let Ok(mut summaries) = indexed {
+            for summary in &mut summaries {
+                adapter.resolve_summary(summary);
+            }
+            return Ok(summaries);
+        }
+    }
+    adapter.list_sessions()
+}
+
+fn built_in_named(name: &str) -> Option<CliType> {
+    BUILT_IN.into_iter().find(|cli| cli.as_str() == name)
+}
+
+/// A built-in adapter, from `base_dir` or its default location, if its
+/// CLI has data there
+fn built_in(cli: CliType, base_dir: Option<&PathBuf>) -> Option<Box<dyn SessionAdapter>> {
+    let dir = base_dir.cloned();
+    Some(match cli {
+        CliType::Claude => Box::new(match dir {
+            Some(dir) => claude::ClaudeAdapter::with_base_dir(dir)?,
+            None => claude::ClaudeAdapter::new()?,
+        }),
+        CliType::Codex => Box::new(match dir {
+            Some(dir) => codex::CodexAdapter::with_base_dir(dir)?,
+            None => codex::CodexAdapter::new()?,
+        }),
+        CliType::OpenCode => Box::new(match dir {
+            Some(dir) => opencode::OpenCodeAdapter::with_base_dir(dir)?,
+            None => opencode::OpenCodeAdapter::new()?,
+        }),
+        CliType::Gemini => Box::new(match dir {
+            Some(dir) => gemini::GeminiAdapter::with_base_dir(dir)?,
+            None => gemini::GeminiAdapter::new()?,
+        }),
+        CliType::Aider => Box::new(match dir {
+            Some(dir) => aider::AiderAdapter::with_roots(vec![dir])?,
+            None => aider::AiderAdapter::new()?,
+        }),
+        CliType::Universal => return None,
+    })
+}
+
+fn not_installed(cli: CliType) -> AdapterError {
+    AdapterError::DirectoryNotFound(
+        match cli {
+            CliType::Claude => "~/.claude not found",
+            CliType::Codex => "~/.codex not found",
+            CliType::OpenCode => "~/.local/share/opencode not found",
+            CliType::Gemini => "~/.gemini not found",
+            CliType::Aider => "no Aider chat history in known projects",
+            CliType::Universal => "universal sessions have no adapter",
+        }
+        .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A ~/.claude with one session, and an adapters config with one
+    /// plugin whose listing fails
+    fn fixture() -> (TempDir, AdapterRegistryBuilder) {
+        let tmp = TempDir::new().unwrap();
+        let claude = tmp.path().join("claude");
+        let project = claude.join("projects/-work-app");
+        std::fs::create_dir_all(&project).unwrap();
+        let line = serde_json::json!({
+            "type": "user",
+            "sessionId": "abc",
+            "timestamp": "2026-01-01T10:00:00Z",
+            "cwd": "/work/app",
+            "message": {"role": "user", "content": [{"type": "text", "text": "Add a login page"}]}
+        });
+        std::fs::write(project.join("abc.jsonl"), format!("{}\n", line)).unwrap();
+
+        let config = tmp.path().join("adapters.toml");
+        std::fs::write(
+            &config,
+            "[[adapter]]\nname = \"broken\"\ncommand = \"sh\"\nargs = [\"-c\", \"echo down >&2; exit 3\"]\n",
+        )
+        .unwrap();
+
+        let builder = AdapterRegistry::builder()
+            .base_dir(CliType::Claude, &claude)
+            .base_dir(CliType::Codex, tmp.path().join("no-codex"))
+            .base_dir(CliType::OpenCode, tmp.path().join("no-opencode"))
+            .base_dir(CliType::Gemini, tmp.path().join("no-gemini"))
+            .base_dir(CliType::Aider, tmp.path().join("no-aider"))
+            .plugins_config(&config)
+            .index(tmp.path().join("sessions.db"));
+        (tmp, builder)
+    }
+
+    fn names(registry: &AdapterRegistry) -> Vec<&str> {
+        registry.adapters().map(|a| a.name()).collect()
+    }
+
+    #[test]
+    fn test_discover_reports_failing_adapters() {
+        let (_tmp, builder) = fixture();
+        let registry = builder.build();
+        assert_eq!(names(&registry), vec!["claude", "broken"]);
+        assert!(registry.errors().is_empty());
+
+        let discovery = registry.discover(false);
+        assert_eq!(discovery.sessions.len(), 1);
+        assert_eq!(discovery.sessions[0].id, "claude-abc");
+        assert_eq!(discovery.errors.len(), 1);
+        assert_eq!(discovery.errors[0].adapter, "broken");
+        assert!(discovery.errors[0].message.contains("down"));
+
+        // A second pass reads the index
+        assert_eq!(registry.discover(false).sessions.len(), 1);
+    }
+
+    #[test]
+    fn test_only_builds_named_adapters() {
+        let (_tmp, builder) = fixture();
+
+        let registry = builder.clone().only("Claude").build();
+        assert_eq!(names(&registry), vec!["claude"]);
+        assert!(registry.discover(false).errors.is_empty());
+
+        // Named but not installed, or not known at all
+        let registry = builder.only("codex").only("nope").build();
+        assert!(names(&registry).is_empty());
+        let failed: Vec<&str> = registry
+            .errors()
+            .iter()
+            .map(|e| e.adapter.as_str())
+            .collect();
+        assert_eq!(failed, vec!["codex", "nope"]);
+    }
+
+    #[test]
+    fn test_load_dispatches_by_prefix() {
+        let (_tmp, builder) = fixture();
+        let registry = builder.without_index().build();
+
+        let session = registry.load("claude-abc").unwrap();
+        assert_eq!(session.project.path, "/work/app");
+        assert!(registry.tail("claude-abc").is_ok());
+
+        assert!(matches!(
+            registry.load("codex-abc"),
+            Err(AdapterError::DirectoryNotFound(_))
+        ));
+        assert!(matches!(
+            registry.load("nope-abc"),
+            Err(AdapterError::InvalidFormat(_))
+        ));
+        // Without a prefix every adapter is tried
+        assert_eq!(registry.load("abc").unwrap().id, "claude-abc");
+        assert!(matches!(
+            registry.load("missing"),
+            Err(AdapterError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+//! Incremental parsing of append-only session files
+//!
+//! Claude Code and Codex write sessions as JSONL files that grow while the
+//! agent runs. A `SessionTail` remembers the byte offset it has read up to
+//! and the parser's state (session metadata, tool calls still waiting for a
+//! result), so each `read_new` only reads what was appended since the last
+//! call. A trailing line without its newline is left for the next read, in
+//! case the writer is mid-line.
+
+use super::AdapterError;
+use crate::usf::{TimelineEntry, UniversalSession};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Per-format state machine fed one JSONL line at a time
+pub trait LineParser: Send {
+    /// Apply one line, pushing any timeline entries it produces
+    fn parse_line(&mut self, line: &str, timeline: &mut Vec<TimelineEntry>);
+
+    /// Build a session from the metadata seen so far and `timeline`
+    fn session(&self, path: &Path, timeline: Vec<TimelineEntry>) -> UniversalSession;
+}
+
+/// Resumable reader over a growing session file
+pub struct SessionTail {
+    path: PathBuf,
+    offset: u64,
+    parser: Box<dyn LineParser>,
+    fresh: fn() -> Box<dyn LineParser>,
+}
+
+impl SessionTail {
+    /// Start reading `path` from the beginning with parser `P`
+    pub fn new<P: LineParser + Default + 'static>(path: &Path) -> Self {
+        let fresh: fn() -> Box<dyn LineParser> = || Box::new(P::default());
+        Self {
+            path: path.to_path_buf(),
+            offset: 0,
+            parser: fresh(),
+            fresh,
+        }
+    }
+
+    /// The file being read
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Bytes consumed so far (always at a line boundary)
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Timeline entries from complete lines appended since the last read.
+    /// If the file shrank it was rewritten, and is read again from the start.
+    pub fn read_new(&mut self) -> Result<Vec<TimelineEntry>, AdapterError> {
+        self.read(false)
+    }
+
+    /// Like `read_new`, but also consumes a final line without a newline;
+    /// used when parsing a finished file in one go
+    pub fn read_to_end(&mut self) -> Result<Vec<TimelineEntry>, AdapterError> {
+        self.read(true)
+    }
+
+    /// Session metadata seen so far, with `timeline` as its timeline
+    pub fn session(&self, timeline: Vec<TimelineEntry>) -> UniversalSession {
+        self.parser.session(&self.path, timeline)
+    }
+
+    fn read(&mut self, include_partial: bool) -> Result<Vec<TimelineEntry>, AdapterError> {
+        let mut file = File::open(&self.path)?;
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+            self.parser = (self.fresh)();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+
+        let mut reader = BufReader::new(file);
+        let mut timeline = Vec::new();
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let read = reader.read_until(b'\n', &mut buf)?;
+            if read == 0 || (!buf.ends_with(b"\n") && !include_partial) {
+                break;
+            }
+            self.offset += read as u64;
+
+            // Skip lines that aren't text, like the full parse skips bad JSON
+            if let Ok(line) = std::str::from_utf8(&buf) {
+                if !line.trim().is_empty() {
+                    self.parser.parse_line(line, &mut timeline);
+                }
+            }
+        }
+        Ok(timeline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usf::{CliType, UserMessage};
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    /// Every line becomes a user message; counts lines seen
+    #[derive(Default)]
+    struct Lines {
+        seen: usize,
+    }
+
+    impl LineParser for Lines {
+        fn parse_line(&mut self, line: &str, timeline: &mut Vec<TimelineEntry>) {
+            self.seen += 1;
+            timeline.push(TimelineEntry::User(UserMessage {
+                id: self.seen.to_string(),
+                timestamp: chrono::Utc::now(),
+                content: line.trim_end().to_string(),
+            }));
+        }
+
+        fn session(&self, _path: &Path, timeline: Vec<TimelineEntry>) -> UniversalSession {
+            let mut session = UniversalSession::new(CliType::Claude, None, "/p".into());
+            session.timeline = timeline;
+            session
+        }
+    }
+
+    fn contents(entries: &[TimelineEntry]) -> Vec<&str> {
+        entries
+            .iter()
+            .map(|e| match e {
+                TimelineEntry::User(m) => m.content.as_str(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reads_only_appended_complete_lines() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s.jsonl");
+        std::fs::write(&path, "one\ntwo\nthr").unwrap();
+
+        let mut tail = SessionTail::new::<Lines>(&path);
+        assert_eq!(contents(&tail.read_new().unwrap()), vec!["one", "two"]);
+        assert_eq!(tail.offset(), 8);
+        assert!(tail.read_new().unwrap().is_empty());
+
+        // The partial line is picked up once it's finished
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(b"ee\n\nfour").unwrap();
+        assert_eq!(contents(&tail.read_new().unwrap()), vec!["three"]);
+        assert_eq!(contents(&tail.read_to_end().unwrap()), vec!["four"]);
+        assert_eq!(tail.session(Vec::new()).timeline.len(), 0);
+    }
+
+    #[test]
+    fn test_rewritten_file_starts_over() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("s.jsonl");
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+
+        let mut tail = SessionTail::new::<Lines>(&path);
+        assert_eq!(tail.read_new().unwrap().len(), 2);
+
+        std::fs::write(&path, "new\n").unwrap();
+        let entries = tail.read_new().unwrap();
+        assert_eq!(contents(&entries), vec!["new"]);
+        // Fresh parser state, so ids restart
+        assert!(matches!(&entries[0], TimelineEntry::User(m) if m.id == "1"));
+    }
+}
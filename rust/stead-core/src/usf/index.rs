@@ -0,0 +1,402 @@
+//! Persistent session index
+//!
+//! Listing sessions means parsing thousands of files across adapters. The
+//! index keeps each file's summaries in SQLite at
+//! `~/.local/share/stead/sessions.db` (override with `STEAD_SESSION_INDEX`),
+//! keyed by path, mtime and size, so a listing only re-parses files that
+//! are new or changed and drops rows for files that are gone. Files that
+//! fail to parse are remembered too, so they aren't retried until they
+//! change.
+
+use crate::usf::adapters::{expand_home, AdapterError, SessionAdapter};
+use crate::usf::SessionSummary;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+const INDEX_DIR: &str = "~/.local/share/stead";
+const INDEX_FILE: &str = "sessions.db";
+
+/// Environment variable overriding the index database path
+pub const INDEX_ENV: &str = "STEAD_SESSION_INDEX";
+
+/// Bumped whenever the stored summary format changes; an index with a
+/// different version is rebuilt from scratch. Version 2 stores a list of
+/// summaries per file; version 3 adds model, tokens and cost; version 4
+/// keeps the project hash of unresolved Gemini summaries; version 5 adds
+/// usage per model.
+const INDEX_VERSION: u32 = 5;
+
+/// What a `sync` did
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SyncStats {
+    /// Files seen on disk
+    pub scanned: usize,
+    /// Files (re-)parsed because they were new or changed
+    pub parsed: usize,
+    /// Rows dropped for files that no longer exist
+    pub removed: usize,
+}
+
+/// SQLite cache of session summaries
+pub struct SessionIndex {
+    conn: Connection,
+}
+
+impl SessionIndex {
+    /// Path of the index database (`STEAD_SESSION_INDEX` or the default location)
+    pub fn default_path() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var(INDEX_ENV) {
+            if !path.is_empty() {
+                return Some(PathBuf::from(path));
+            }
+        }
+        Some(expand_home(INDEX_DIR)?.join(INDEX_FILE))
+    }
+
+    /// Open (or create) the index at the default path
+    pub fn open_default() -> Result<Self, AdapterError> {
+        let path = Self::default_path()
+            .ok_or_else(|| AdapterError::DirectoryNotFound("home directory".to_string()))?;
+        Self::open(&path)
+    }
+
+    /// Open (or create) an index database at `path`
+    pub fn open(path: &Path) -> Result<Self, AdapterError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(path).map_err(sql_err)?;
+        // The dashboard, the app and the CLI may sync at the same time
+        conn.busy_timeout(Duration::from_secs(5)).map_err(sql_err)?;
+        let index = Self { conn };
+        index.init_schema()?;
+        Ok(index)
+    }
+
+    /// Create an in-memory index (for tests)
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self, AdapterError> {
+        let index = Self {
+            conn: Connection::open_in_memory().map_err(sql_err)?,
+        };
+        index.init_schema()?;
+        Ok(index)
+    }
+
+    fn init_schema(&self) -> Result<(), AdapterError> {
+        let version: u32 = self
+            .conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(sql_err)?;
+        if version != INDEX_VERSION {
+            self.conn
+                .execute_batch("DROP TABLE IF EXISTS session_files;")
+                .map_err(sql_err)?;
+        }
+        self.conn
+            .execute_batch(&format!(
+                "CREATE TABLE IF NOT EXISTS session_files (
+                    path TEXT PRIMARY KEY,
+                    cli TEXT NOT NULL,
+                    mtime_ns INTEGER NOT NULL,
+                    size INTEGER NOT NULL,
+                    summaries TEXT
+                );
+                CREATE INDEX IF NOT EXISTS idx_session_files_cli ON session_files(cli);
+                PRAGMA user_version = {};",
+                INDEX_VERSION
+            ))
+            .map_err(sql_err)
+    }
+
+    /// Bring the adapter's rows up to date with its files on disk;
+    /// `force` re-parses every file
+    pub fn sync(
+        &self,
+        adapter: &dyn SessionAdapter,
+        force: bool,
+    ) -> Result<SyncStats, AdapterError> {
+        let cli = adapter.cli_type().as_str();
+        let mut known = self.fingerprints(adapter)?;
+        let mut stats = SyncStats::default();
+
+        // Parse before taking the write lock, so adapters syncing in
+        // parallel only wait for each other's writes
+        let mut changed = Vec::new();
+        for path in adapter.session_files()? {
+            let Ok(fingerprint) = fingerprint(&path) else {
+                continue;
+            };
+            stats.scanned += 1;
+            let key = path.to_string_lossy().to_string();
+            if known.remove(&key) == Some(fingerprint) && !force {
+                continue;
+            }
+
+            let summaries = adapter
+                .summarize_file(&path)
+                .ok()
+                .and_then(|s| serde_json::to_string(&s).ok());
+            changed.push((key, fingerprint, summaries));
+        }
+
+        let tx = self.conn.unchecked_transaction().map_err(sql_err)?;
+        for (key, fingerprint, summaries) in &changed {
+            tx.execute(
+                "INSERT OR REPLACE INTO session_files (path, cli, mtime_ns, size, summaries)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![key, cli, fingerprint.0, fingerprint.1, summaries],
+            )
+            .map_err(sql_err)?;
+            stats.parsed += 1;
+        }
+
+        for path in known.keys() {
+            tx.execute("DELETE FROM session_files WHERE path = ?1", params![path])
+                .map_err(sql_err)?;
+            stats.removed += 1;
+        }
+        tx.commit().map_err(sql_err)?;
+        Ok(stats)
+    }
+
+    /// Indexed summaries for the adapter's files, newest first
+    pub fn summaries(
+        &self,
+        adapter: &dyn SessionAdapter,
+    ) -> Result<Vec<SessionSummary>, AdapterError> {
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT path, summaries FROM session_files WHERE cli = ?1 AND summaries IS NOT NULL",
+            )
+            .map_err(sql_err)?;
+        let rows = stmt
+            .query_map(params![adapter.cli_type().as_str()], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(sql_err)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(sql_err)?;
+
+        let mut sessions: Vec<SessionSummary> = rows
+            .iter()
+            .filter(|(path, _)| owns(adapter, path))
+            .filter_map(|(_, json)| serde_json::from_str::<Vec<SessionSummary>>(json).ok())
+            .flatten()
+            .collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_modified));
+        Ok(sessions)
+    }
+
+    /// Stored (mtime, size) per path for the adapter's files
+    fn fingerprints(
+        &self,
+        adapter: &dyn SessionAdapter,
+    ) -> Result<HashMap<String, (i64, i64)>, AdapterError> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, mtime_ns, size FROM session_files WHERE cli = ?1")
+            .map_err(sql_err)?;
+        let rows = stmt
+            .query_map(params![adapter.cli_type().as_str()], |row| {
+                Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?)))
+            })
+            .map_err(sql_err)?
+            .collect::<Result<HashMap<_, _>, _>>()
+            .map_err(sql_err)?;
+        Ok(rows
+            .into_iter()
+            .filter(|(path, _)| owns(adapter, path))
+            .collect())
+    }
+}
+
+/// Whether an indexed path belongs to the adapter: rows are shared by every
+/// adapter of a CLI, so one with its own `base_dir` (say, a second profile)
+/// must neither list nor drop the rows under another directory
+fn owns(adapter: &dyn SessionAdapter, path: &str) -> bool {
+    adapter
+        .base_dir()
+        .is_none_or(|base| Path::new(path).starts_with(base))
+}
+
+/// (mtime in nanoseconds, size in bytes) of a file
+fn fingerprint(path: &Path) -> std::io::Result<(i64, i64)> {
+    let metadata = std::fs::metadata(path)?;
+    let mtime = metadata
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as i64)
+        .unwrap_or(0);
+    Ok((mtime, metadata.len() as i64))
+}
+
+fn sql_err(e: rusqlite::Error) -> AdapterError {
+    AdapterError::Io(std::io::Error::other(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::usf::{CliType, UniversalSession};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Adapter over `*.txt` files in a directory; each file's first line
+    /// is the session title and "bad" fails to parse
+    struct DirAdapter {
+        dir: PathBuf,
+        parses: AtomicUsize,
+    }
+
+    impl SessionAdapter for DirAdapter {
+        fn cli_type(&self) -> CliType {
+            CliType::Claude
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn base_dir(&self) -> Option<PathBuf> {
+            Some(self.dir.clone())
+        }
+
+        fn session_files(&self) -> Result<Vec<PathBuf>, AdapterError> {
+            let mut files: Vec<PathBuf> = std::fs::read_dir(&self.dir)?
+                .map(|e| e.map(|e| e.path()))
+                .collect::<Result<_, _>>()?;
+            files.sort();
+            Ok(files)
+        }
+
+        fn summarize_file(&self, path: &Path) -> Result<Vec<SessionSummary>, AdapterError> {
+            self.parses.fetch_add(1, Ordering::Relaxed);
+            let content = std::fs::read_to_string(path)?;
+            if content.starts_with("bad") {
+                return Err(AdapterError::InvalidFormat("bad".to_string()));
+            }
+            let stem = path.file_stem().unwrap().to_string_lossy();
+            let mut session =
+                UniversalSession::new(CliType::Claude, Some(stem.to_string()), "/p".into());
+            session
+                .timeline
+                .push(crate::usf::TimelineEntry::User(crate::usf::UserMessage {
+                    id: "u".into(),
+                    timestamp: chrono::Utc::now(),
+                    content: content.lines().next().unwrap_or("").to_string(),
+                }));
+            Ok(vec![SessionSummary::from(&session)])
+        }
+
+        fn load_session(&self, id: &str) -> Result<UniversalSession, AdapterError> {
+            Err(AdapterError::NotFound(id.to_string()))
+        }
+    }
+
+    fn titles(index: &SessionIndex, adapter: &DirAdapter) -> Vec<String> {
+        let mut titles: Vec<String> = index
+            .summaries(adapter)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.title)
+            .collect();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn test_only_changed_files_are_reparsed() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("a.txt"), "first").unwrap();
+        std::fs::write(tmp.path().join("b.txt"), "second").unwrap();
+        std::fs::write(tmp.path().join("c.txt"), "bad").unwrap();
+        let adapter = DirAdapter {
+            dir: tmp.path().to_path_buf(),
+            parses: AtomicUsize::new(0),
+        };
+        let index = SessionIndex::open_in_memory().unwrap();
+
+        let stats = index.sync(&adapter, false).unwrap();
+        assert_eq!((stats.scanned, stats.parsed, stats.removed), (3, 3, 0));
+        assert_eq!(titles(&index, &adapter), vec!["first", "second"]);
+
+        // Nothing changed: no parsing, including the unparseable file
+        let stats = index.sync(&adapter, false).unwrap();
+        assert_eq!(stats.parsed, 0);
+        assert_eq!(adapter.parses.load(Ordering::Relaxed), 3);
+
+        // A changed size is picked up; a deleted file is dropped
+        std::fs::write(tmp.path().join("a.txt"), "first, edited").unwrap();
+        std::fs::remove_file(tmp.path().join("b.txt")).unwrap();
+        let stats = index.sync(&adapter, false).unwrap();
+        assert_eq!((stats.parsed, stats.removed), (1, 1));
+        assert_eq!(titles(&index, &adapter), vec!["first, edited"]);
+
+        // Forcing re-parses everything
+        let stats = index.sync(&adapter, true).unwrap();
+        assert_eq!(stats.parsed, 2);
+    }
+
+    #[test]
+    fn test_adapters_with_other_base_dirs_keep_their_rows() {
+        let tmp = TempDir::new().unwrap();
+        let adapters: Vec<DirAdapter> = ["default", "profile"]
+            .iter()
+            .map(|name| {
+                let dir = tmp.path().join(name);
+                std::fs::create_dir(&dir).unwrap();
+                std::fs::write(dir.join("a.txt"), *name).unwrap();
+                DirAdapter {
+                    dir,
+                    parses: AtomicUsize::new(0),
+                }
+            })
+            .collect();
+        let index = SessionIndex::open_in_memory().unwrap();
+
+        for adapter in &adapters {
+            let stats = index.sync(adapter, false).unwrap();
+            assert_eq!((stats.parsed, stats.removed), (1, 0));
+        }
+        assert_eq!(titles(&index, &adapters[0]), vec!["default"]);
+        assert_eq!(titles(&index, &adapters[1]), vec!["profile"]);
+
+        // Syncing one directory leaves the other's rows alone
+        assert_eq!(index.sync(&adapters[0], false).unwrap().removed, 0);
+        assert_eq!(titles(&index, &adapters[1]), vec!["profile"]);
+    }
+
+    #[test]
+    fn test_index_persists_and_rebuilds_on_version_change() {
+        let tmp = TempDir::new().unwrap();
+        let sessions = tmp.path().join("sessions");
+        std::fs::create_dir(&sessions).unwrap();
+        std::fs::write(sessions.join("a.txt"), "first").unwrap();
+        let adapter = DirAdapter {
+            dir: sessions,
+            parses: AtomicUsize::new(0),
+        };
+        let db = tmp.path().join("index.db");
+
+        SessionIndex::open(&db)
+            .unwrap()
+            .sync(&adapter, false)
+            .unwrap();
+        let index = SessionIndex::open(&db).unwrap();
+        assert_eq!(index.sync(&adapter, false).unwrap().parsed, 0);
+        drop(index);
+
+        // An index written by another format version starts over
+        Connection::open(&db)
+            .unwrap()
+            .execute_batch("PRAGMA user_version = 999;")
+            .unwrap();
+        let index = SessionIndex::open(&db).unwrap();
+        assert!(titles(&index, &adapter).is_empty());
+        assert_eq!(index.sync(&adapter, false).unwrap().parsed, 1);
+    }
+}
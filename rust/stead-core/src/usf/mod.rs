@@ -1,9 +1,11 @@
 //! Universal Session Format
 //!
 //! This module provides a canonical representation for AI coding CLI sessions,
-//! enabling unified visibility across Claude Code, Codex CLI, and OpenCode.
+//! enabling unified visibility across Claude Code, Codex CLI, OpenCode, Gemini CLI, and Aider.
 
 pub mod adapters;
+pub mod index;
+pub mod pricing;
 pub mod schema;
 
 pub use schema::*;
@@ -0,0 +1,327 @@
+//! Model pricing for session cost estimates
+//!
+//! Only some CLIs report what a session cost (OpenCode, Aider). For the
+//! rest, cost is estimated from token usage and a price table shipped with
+//! stead (`pricing.toml`), which `~/.config/stead/pricing.toml` (override
+//! with `STEAD_PRICING`) can extend or correct. Sessions that switched
+//! models are priced per model. Estimates are made when displaying, so a
+//! price change applies without rebuilding the index.
+
+use super::adapters::{expand_home, AdapterError};
+use super::{ModelUsage, SessionSummary, TokenUsage, UniversalSession};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+const PRICING_DIR: &str = "~/.config/stead";
+const PRICING_FILE: &str = "pricing.toml";
+
+/// Environment variable overriding the user pricing path
+pub const PRICING_ENV: &str = "STEAD_PRICING";
+
+/// Prices shipped with stead
+const BUILTIN_PRICING: &str = include_str!("pricing.toml");
+
+/// Prices for one model, in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    /// Defaults to the input price
+    #[serde(default)]
+    pub cache_read: Option<f64>,
+    /// Defaults to the input price
+    #[serde(default)]
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPrice {
+    /// Cost of `usage` at these prices, in USD
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let per_token = |count: u64, price: f64| count as f64 * price / 1_000_000.0;
+        per_token(usage.input, self.input)
+            + per_token(usage.output, self.output)
+            + per_token(usage.cache_read, self.cache_read.unwrap_or(self.input))
+            + per_token(usage.cache_write, self.cache_write.unwrap_or(self.input))
+    }
+}
+
+/// What a session cost, in USD
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Cost {
+    pub usd: f64,
+    /// Computed from the price table rather than reported by the CLI
+    pub estimated: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PricingFile {
+    #[serde(default)]
+    models: BTreeMap<String, ModelPrice>,
+}
+
+/// Price table keyed by model name prefix
+#[derive(Debug, Clone, Default)]
+pub struct Pricing {
+    models: BTreeMap<String, ModelPrice>,
+}
+
+impl Pricing {
+    /// The prices shipped with stead
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_PRICING).expect("builtin pricing.toml is valid")
+    }
+
+    /// Parse a price table in the `pricing.toml` format
+    pub fn parse(content: &str) -> Result<Self, AdapterError> {
+        let file: PricingFile = toml::from_str(content)
+            .map_err(|e| AdapterError::InvalidFormat(e.message().to_string()))?;
+        let models = file
+            .models
+            .into_iter()
+            .map(|(name, price)| (name.to_lowercase(), price))
+            .collect();
+        Ok(Self { models })
+    }
+
+    /// Path of the user pricing file (`STEAD_PRICING` or the default location)
+    pub fn user_path() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var(PRICING_ENV) {
+            if !path.is_empty() {
+                return Some(PathBuf::from(path));
+            }
+        }
+        Some(expand_home(PRICING_DIR)?.join(PRICING_FILE))
+    }
+
+    /// Builtin prices with the user pricing file merged over them
+    pub fn load() -> Result<Self, AdapterError> {
+        let mut pricing = Self::builtin();
+        if let Some(path) = Self::user_path() {
+            pricing.merge_file(&path)?;
+        }
+        Ok(pricing)
+    }
+
+    /// Merge entries from `path` over these; a missing file changes nothing
+    pub fn merge_file(&mut self, path: &Path) -> Result<(), AdapterError> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let overrides = Self::parse(&content).map_err(|e| match e {
+            AdapterError::InvalidFormat(message) => {
+                AdapterError::InvalidFormat(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        })?;
+        self.models.extend(overrides.models);
+        Ok(())
+    }
+
+    /// Prices for `model`: the entry with the longest matching prefix
+    pub fn price(&self, model: &str) -> Option<&ModelPrice> {
+        let model = model.to_lowercase();
+        let name = model.rsplit('/').next().unwrap_or(&model);
+        self.models
+            .iter()
+            .filter(|(prefix, _)| name.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, price)| price)
+    }
+
+    /// Estimated cost of `usage` on `model`, if the model has a price
+    pub fn estimate(&self, model: &str, usage: &TokenUsage) -> Option<f64> {
+        Some(self.price(model)?.cost(usage))
+    }
+
+    /// Estimated cost of tokens split by model, if every model that used
+    /// tokens has a price
+    pub fn estimate_by_model(&self, usage: &[ModelUsage]) -> Option<f64> {
+        usage
+            .iter()
+            .filter(|u| u.tokens.total() > 0)
+            .map(|u| self.estimate(&u.model, &u.tokens))
+            .sum()
+    }
+
+    /// Cost of a listed session: as reported, else estimated from its tokens
+    pub fn summary_cost(&self, summary: &SessionSummary) -> Option<Cost> {
+        self.cost(
+            summary.cost,
+            summary.model.as_deref(),
+            summary.tokens.as_ref(),
+            &summary.model_usage,
+        )
+    }
+
+    /// Cost of a loaded session: as reported, else estimated from its tokens
+    pub fn session_cost(&self, session: &UniversalSession) -> Option<Cost> {
+        self.cost(
+            session.metadata.cost,
+            Some(session.model.model.as_str()),
+            session.metadata.tokens.as_ref(),
+            &session.metadata.model_usage,
+        )
+    }
+
+    /// Reported cost, else the per-model estimate, else the whole session
+    /// priced at its main model
+    fn cost(
+        &self,
+        reported: Option<f64>,
+        model: Option<&str>,
+        tokens: Option<&TokenUsage>,
+        model_usage: &[ModelUsage],
+    ) -> Option<Cost> {
+        if let Some(usd) = reported {
+            return Some(Cost {
+                usd,
+                estimated: false,
+            });
+        }
+        let usd = if model_usage.is_empty() {
+            self.estimate(model?, tokens?)?
+        } else {
+            self.estimate_by_model(model_usage)?
+        };
+        Some(Cost {
+            usd,
+            estimated: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn usage(input: u64, output: u64, cache_read: u64, cache_write: u64) -> TokenUsage {
+        TokenUsage {
+            input,
+            output,
+            cache_read,
+            cache_write,
+        }
+    }
+
+    #[test]
+    fn test_longest_prefix_wins() {
+        let pricing = Pricing::builtin();
+
+        let sonnet = pricing.price("claude-sonnet-4-5-20250929").unwrap();
+        assert_eq!(sonnet.input, 3.0);
+        assert_eq!(
+            pricing.price("claude-opus-4-1-20250805").unwrap().input,
+            15.0
+        );
+        assert_eq!(
+            pricing.price("claude-opus-4-5-20251101").unwrap().input,
+            5.0
+        );
+        assert_eq!(pricing.price("gpt-5-mini").unwrap().input, 0.25);
+        assert_eq!(pricing.price("gpt-5-codex").unwrap().input, 1.25);
+        assert_eq!(pricing.price("anthropic/Claude-Sonnet-4").unwrap(), sonnet);
+        assert!(pricing.price("llama-3").is_none());
+    }
+
+    #[test]
+    fn test_cost() {
+        let pricing = Pricing::builtin();
+
+        // 1M input at $3, 100k output at $15, 2M cache reads at $0.30
+        let cost = pricing
+            .estimate("claude-sonnet-4", &usage(1_000_000, 100_000, 2_000_000, 0))
+            .unwrap();
+        assert!((cost - 5.1).abs() < 1e-9, "{}", cost);
+
+        // No cache write price: billed as input
+        let cost = pricing
+            .estimate("gpt-4o", &usage(0, 0, 0, 1_000_000))
+            .unwrap();
+        assert!((cost - 2.5).abs() < 1e-9, "{}", cost);
+    }
+
+    #[test]
+    fn test_reported_cost_wins() {
+        let pricing = Pricing::builtin();
+        let tokens = Some(usage(1_000_000, 0, 0, 0));
+
+        assert_eq!(
+            pricing.cost(Some(0.5), Some("claude-sonnet-4"), tokens.as_ref(), &[]),
+            Some(Cost {
+                usd: 0.5,
+                estimated: false
+            })
+        );
+        assert_eq!(
+            pricing.cost(None, Some("claude-sonnet-4"), tokens.as_ref(), &[]),
+            Some(Cost {
+                usd: 3.0,
+                estimated: true
+            })
+        );
+        assert_eq!(
+            pricing.cost(None, Some("unknown"), tokens.as_ref(), &[]),
+            None
+        );
+        assert_eq!(pricing.cost(None, Some("claude-sonnet-4"), None, &[]), None);
+    }
+
+    #[test]
+    fn test_cost_per_model() {
+        let pricing = Pricing::builtin();
+        let model_usage = |model: &str, tokens: TokenUsage| ModelUsage {
+            model: model.to_string(),
+            tokens,
+            hour: None,
+        };
+        // 1M input on Sonnet at $3 plus 1M input on Haiku 3.5 at $0.80
+        let split = [
+            model_usage("claude-sonnet-4", usage(1_000_000, 0, 0, 0)),
+            model_usage("claude-3-5-haiku", usage(1_000_000, 0, 0, 0)),
+            model_usage("<synthetic>", usage(0, 0, 0, 0)),
+        ];
+        let total = Some(usage(2_000_000, 0, 0, 0));
+
+        let cost = pricing
+            .cost(None, Some("claude-sonnet-4"), total.as_ref(), &split)
+            .unwrap();
+        assert!((cost.usd - 3.8).abs() < 1e-9, "{}", cost.usd);
+        assert!(cost.estimated);
+
+        // A model without a price leaves the session unpriced
+        let unknown = [split[0].clone(), model_usage("llama-3", usage(1, 0, 0, 0))];
+        assert_eq!(
+            pricing.cost(None, Some("claude-sonnet-4"), total.as_ref(), &unknown),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_file() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("pricing.toml");
+        let mut pricing = Pricing::builtin();
+        pricing.merge_file(&path).unwrap();
+
+        std::fs::write(
+            &path,
+            "[models.\"claude-sonnet-4\"]\ninput = 2.0\noutput = 10.0\n\n\
+             [models.\"acme-1\"]\ninput = 1.0\noutput = 1.0\n",
+        )
+        .unwrap();
+        pricing.merge_file(&path).unwrap();
+        assert_eq!(pricing.price("claude-sonnet-4-5").unwrap().input, 2.0);
+        assert_eq!(pricing.price("acme-1").unwrap().output, 1.0);
+        assert_eq!(pricing.price("gpt-5").unwrap().input, 1.25);
+
+        std::fs::write(&path, "[models.x]\ninput = 1.0\n").unwrap();
+        let err = pricing.merge_file(&path).unwrap_err().to_string();
+        assert!(err.contains("pricing.toml"), "{}", err);
+    }
+}
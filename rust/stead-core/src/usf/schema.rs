@@ -1,7 +1,8 @@
 //! Universal Session Format Schema
 //!
 //! Defines the canonical representation for AI coding CLI sessions.
-//! Adapters convert from Claude Code, Codex CLI, and OpenCode formats to this schema.
+//! Adapters convert from Claude Code, Codex CLI, OpenCode, Gemini CLI, and Aider formats to this
+//! schema.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -62,6 +63,7 @@ impl UniversalSession {
                 created: Utc::now(),
                 last_modified: Utc::now(),
                 tokens: None,
+                model_usage: Vec::new(),
                 cost: None,
             },
         }
@@ -87,6 +89,8 @@ impl UniversalSession {
         for entry in &self.timeline {
             match entry {
                 TimelineEntry::User(_) => counts.user += 1,
+                // Reasoning-only entries aren't messages
+                TimelineEntry::Assistant(m) if m.is_thinking_only() => {}
                 TimelineEntry::Assistant(_) => counts.assistant += 1,
                 TimelineEntry::ToolCall(_) => counts.tool_calls += 1,
                 TimelineEntry::ToolResult(_) => counts.tool_results += 1,
@@ -108,12 +112,14 @@ pub struct MessageCounts {
 }
 
 /// Source CLI type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CliType {
     Claude,
     Codex,
     OpenCode,
+    Gemini,
+    Aider,
     Universal,
 }
 
@@ -123,6 +129,8 @@ impl CliType {
             CliType::Claude => "claude",
             CliType::Codex => "codex",
             CliType::OpenCode => "opencode",
+            CliType::Gemini => "gemini",
+            CliType::Aider => "aider",
             CliType::Universal => "universal",
         }
     }
@@ -183,17 +191,67 @@ pub struct ModelInfo {
 pub struct SessionMetadata {
     pub created: DateTime<Utc>,
     pub last_modified: DateTime<Utc>,
+    /// Tokens across the whole session
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tokens: Option<TokenUsage>,
+    /// `tokens` split by model (and by hour), when the CLI records which
+    /// model served each request
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_usage: Vec<ModelUsage>,
+    /// Cost reported by the CLI itself, in USD; see `Pricing` for estimates
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cost: Option<f64>,
 }
 
-/// Token usage statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Token usage statistics. `input` counts uncached input only; prompt
+/// cache traffic is in `cache_read` and `cache_write`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TokenUsage {
     pub input: u64,
     pub output: u64,
+    /// Input tokens served from the prompt cache
+    #[serde(default)]
+    pub cache_read: u64,
+    /// Input tokens written to the prompt cache
+    #[serde(default)]
+    pub cache_write: u64,
+}
+
+impl TokenUsage {
+    /// Every token counted, cached or not
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_read + self.cache_write
+    }
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_write += other.cache_write;
+    }
+}
+
+impl std::iter::Sum for TokenUsage {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut total, usage| {
+            total += usage;
+            total
+        })
+    }
+}
+
+/// Tokens one model used in a session, within one hour when the CLI
+/// timestamps its requests
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelUsage {
+    pub model: String,
+    /// Start of the hour (UTC) the tokens were used in; lets reports split
+    /// a long session across days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hour: Option<DateTime<Utc>>,
+    pub tokens: TokenUsage,
 }
 
 /// Timeline entry types
@@ -221,9 +279,21 @@ pub struct AssistantMessage {
     pub id: String,
     pub timestamp: DateTime<Utc>,
     pub content: String,
-    /// Extended thinking content (Claude-specific)
+    /// Reasoning behind the message: Claude's extended thinking, Codex's
+    /// reasoning (or its summary) and OpenCode's reasoning parts. Reasoning
+    /// that led to a tool call rather than a reply has no `content`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub thinking: Option<String>,
+    /// Tokens spent producing this message, when the CLI reports them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+impl AssistantMessage {
+    /// Reasoning with no reply text
+    pub fn is_thinking_only(&self) -> bool {
+        self.content.is_empty() && self.thinking.is_some()
+    }
 }
 
 /// Tool call
@@ -345,6 +415,32 @@ impl UniversalTool {
             _ => Self::Unknown,
         }
     }
+
+    /// Map Gemini CLI tool name to universal
+    pub fn from_gemini(name: &str) -> Self {
+        match name {
+            "read_file" | "read_many_files" => Self::Read,
+            "write_file" => Self::Write,
+            "replace" => Self::Edit,
+            "run_shell_command" => Self::Bash,
+            "search_file_content" | "grep" => Self::Search,
+            "glob" => Self::Glob,
+            "list_directory" => Self::List,
+            "web_fetch" => Self::WebFetch,
+            "google_web_search" => Self::WebSearch,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Map Aider actions to universal. Aider has no tool calls; its adapter
+    /// names applied edits "edit" and shell commands by their chat command.
+    pub fn from_aider(name: &str) -> Self {
+        match name {
+            "edit" => Self::Edit,
+            "/run" | "/test" | "!" => Self::Bash,
+            _ => Self::Unknown,
+        }
+    }
 }
 
 impl std::fmt::Display for UniversalTool {
@@ -380,6 +476,21 @@ pub struct SessionSummary {
     pub message_count: usize,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git_branch: Option<String>,
+    /// Main model, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<TokenUsage>,
+    /// `tokens` split by model, when known
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub model_usage: Vec<ModelUsage>,
+    /// Cost reported by the CLI itself, in USD; see `Pricing` for estimates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost: Option<f64>,
+    /// Hash naming the project when its path couldn't be resolved at
+    /// parse time (Gemini); the adapter resolves it again when listing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_hash: Option<String>,
 }
 
 impl From<&UniversalSession> for SessionSummary {
@@ -393,6 +504,11 @@ impl From<&UniversalSession> for SessionSummary {
             last_modified: session.metadata.last_modified,
             message_count: session.timeline.len(),
             git_branch: session.project.git.as_ref().map(|g| g.branch.clone()),
+            model: Some(session.model.model.clone()).filter(|m| m != "unknown"),
+            tokens: session.metadata.tokens,
+            model_usage: session.metadata.model_usage.clone(),
+            cost: session.metadata.cost,
+            project_hash: None,
         }
     }
 }
@@ -481,6 +597,7 @@ mod tests {
                 timestamp: Utc::now(),
                 content: "Hi!".to_string(),
                 thinking: None,
+                usage: None,
             }));
         session.timeline.push(TimelineEntry::ToolCall(ToolCall {
             id: "3".to_string(),
@@ -525,11 +642,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tool_mapping_gemini() {
+        assert_eq!(UniversalTool::from_gemini("read_file"), UniversalTool::Read);
+        assert_eq!(UniversalTool::from_gemini("replace"), UniversalTool::Edit);
+        assert_eq!(
+            UniversalTool::from_gemini("run_shell_command"),
+            UniversalTool::Bash
+        );
+        assert_eq!(
+            UniversalTool::from_gemini("google_web_search"),
+            UniversalTool::WebSearch
+        );
+        assert_eq!(
+            UniversalTool::from_gemini("save_memory"),
+            UniversalTool::Unknown
+        );
+    }
+
+    #[test]
+    fn test_tool_mapping_aider() {
+        assert_eq!(UniversalTool::from_aider("edit"), UniversalTool::Edit);
+        assert_eq!(UniversalTool::from_aider("/run"), UniversalTool::Bash);
+        assert_eq!(UniversalTool::from_aider("/test"), UniversalTool::Bash);
+        assert_eq!(UniversalTool::from_aider("/add"), UniversalTool::Unknown);
+    }
+
     #[test]
     fn test_cli_type_display() {
         assert_eq!(CliType::Claude.to_string(), "claude");
         assert_eq!(CliType::Codex.to_string(), "codex");
         assert_eq!(CliType::OpenCode.to_string(), "opencode");
+        assert_eq!(CliType::Gemini.to_string(), "gemini");
+        assert_eq!(CliType::Aider.to_string(), "aider");
     }
 
     #[test]
@@ -6,6 +6,9 @@
 uniffi::setup_scaffolding!();
 
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use stead_core::storage::Storage;
 
 // -- FFI Enum types --
 
@@ -16,6 +19,7 @@ pub enum FfiContractStatus {
     Claimed,
     Executing,
     Verifying,
+    AwaitingApproval,
     Completed,
     Failed,
     RollingBack,
@@ -23,6 +27,24 @@ pub enum FfiContractStatus {
     Cancelled,
 }
 
+impl From<FfiContractStatus> for stead_core::schema::ContractStatus {
+    fn from(s: FfiContractStatus) -> Self {
+        match s {
+            FfiContractStatus::Pending => Self::Pending,
+            FfiContractStatus::Ready => Self::Ready,
+            FfiContractStatus::Claimed => Self::Claimed,
+            FfiContractStatus::Executing => Self::Executing,
+            FfiContractStatus::Verifying => Self::Verifying,
+            FfiContractStatus::AwaitingApproval => Self::AwaitingApproval,
+            FfiContractStatus::Completed => Self::Completed,
+            FfiContractStatus::Failed => Self::Failed,
+            FfiContractStatus::RollingBack => Self::RollingBack,
+            FfiContractStatus::RolledBack => Self::RolledBack,
+            FfiContractStatus::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
 impl From<stead_core::schema::ContractStatus> for FfiContractStatus {
     fn from(s: stead_core::schema::ContractStatus) -> Self {
         match s {
@@ -31,6 +53,7 @@ impl From<stead_core::schema::ContractStatus> for FfiContractStatus {
             stead_core::schema::ContractStatus::Claimed => Self::Claimed,
             stead_core::schema::ContractStatus::Executing => Self::Executing,
             stead_core::schema::ContractStatus::Verifying => Self::Verifying,
+            stead_core::schema::ContractStatus::AwaitingApproval => Self::AwaitingApproval,
             stead_core::schema::ContractStatus::Completed => Self::Completed,
             stead_core::schema::ContractStatus::Failed => Self::Failed,
             stead_core::schema::ContractStatus::RollingBack => Self::RollingBack,
@@ -45,6 +68,8 @@ pub enum FfiCliType {
     Claude,
     Codex,
     OpenCode,
+    Gemini,
+    Aider,
     Universal,
 }
 
@@ -54,13 +79,75 @@ impl From<stead_core::usf::CliType> for FfiCliType {
             stead_core::usf::CliType::Claude => Self::Claude,
             stead_core::usf::CliType::Codex => Self::Codex,
             stead_core::usf::CliType::OpenCode => Self::OpenCode,
+            stead_core::usf::CliType::Gemini => Self::Gemini,
+            stead_core::usf::CliType::Aider => Self::Aider,
             stead_core::usf::CliType::Universal => Self::Universal,
         }
     }
 }
 
+#[derive(uniffi::Enum)]
+pub enum FfiAttentionReason {
+    FailedVerification,
+    ApprovalPending,
+    WaitingOnQuestion,
+    StaleClaim,
+}
+
+impl From<stead_core::attention::AttentionReason> for FfiAttentionReason {
+    fn from(r: stead_core::attention::AttentionReason) -> Self {
+        use stead_core::attention::AttentionReason;
+        match r {
+            AttentionReason::FailedVerification => Self::FailedVerification,
+            AttentionReason::ApprovalPending => Self::ApprovalPending,
+            AttentionReason::WaitingOnQuestion => Self::WaitingOnQuestion,
+            AttentionReason::StaleClaim => Self::StaleClaim,
+        }
+    }
+}
+
+/// Aggregate project state, for the menu-bar icon
+#[derive(uniffi::Enum)]
+pub enum FfiAttentionState {
+    Idle,
+    Working,
+    NeedsInput,
+    Failing,
+}
+
+impl From<stead_core::attention::AttentionState> for FfiAttentionState {
+    fn from(s: stead_core::attention::AttentionState) -> Self {
+        use stead_core::attention::AttentionState;
+        match s {
+            AttentionState::Idle => Self::Idle,
+            AttentionState::Working => Self::Working,
+            AttentionState::NeedsInput => Self::NeedsInput,
+            AttentionState::Failing => Self::Failing,
+        }
+    }
+}
+
 // -- FFI Record types --
 
+#[derive(uniffi::Record)]
+pub struct FfiApproval {
+    pub approver: String,
+    pub approved: bool,
+    pub reason: Option<String>,
+    pub decided_at: String,
+}
+
+impl From<stead_core::schema::Approval> for FfiApproval {
+    fn from(a: stead_core::schema::Approval) -> Self {
+        Self {
+            approver: a.approver,
+            approved: a.approved,
+            reason: a.reason,
+            decided_at: a.decided_at.to_rfc3339(),
+        }
+    }
+}
+
 #[derive(uniffi::Record)]
 pub struct FfiContract {
     pub id: String,
@@ -73,6 +160,13 @@ pub struct FfiContract {
     pub owner: Option<String>,
     pub blocked_by: Vec<String>,
     pub blocks: Vec<String>,
+    pub parent_id: Option<String>,
+    pub await_children: bool,
+    pub requires_approval: bool,
+    pub approval: Option<FfiApproval>,
+    pub tags: Vec<String>,
+    /// Project root the contract belongs to
+    pub project_path: String,
 }
 
 impl From<stead_core::schema::Contract> for FfiContract {
@@ -88,10 +182,47 @@ impl From<stead_core::schema::Contract> for FfiContract {
             owner: c.owner,
             blocked_by: c.blocked_by,
             blocks: c.blocks,
+            parent_id: c.parent_id,
+            await_children: c.await_children,
+            requires_approval: c.requires_approval,
+            approval: c.approval.map(FfiApproval::from),
+            tags: c.tags,
+            project_path: String::new(),
         }
     }
 }
 
+impl From<stead_core::storage::registry::ProjectContract> for FfiContract {
+    fn from(pc: stead_core::storage::registry::ProjectContract) -> Self {
+        Self {
+            project_path: pc.project,
+            ..FfiContract::from(pc.contract)
+        }
+    }
+}
+
+/// A registered project whose contracts couldn't be read
+#[derive(uniffi::Record)]
+pub struct FfiProjectError {
+    pub project_path: String,
+    pub message: String,
+}
+
+impl From<stead_core::storage::registry::ProjectError> for FfiProjectError {
+    fn from(e: stead_core::storage::registry::ProjectError) -> Self {
+        Self {
+            project_path: e.project,
+            message: e.message,
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct FfiAllContracts {
+    pub contracts: Vec<FfiContract>,
+    pub errors: Vec<FfiProjectError>,
+}
+
 #[derive(uniffi::Record)]
 pub struct FfiSessionSummary {
     pub id: String,
@@ -119,6 +250,53 @@ impl From<stead_core::usf::SessionSummary> for FfiSessionSummary {
     }
 }
 
+#[derive(uniffi::Record)]
+pub struct FfiAttentionItem {
+    pub reason: FfiAttentionReason,
+    pub contract_id: Option<String>,
+    pub session_id: Option<String>,
+    pub title: String,
+    pub detail: Option<String>,
+    pub since: String,
+}
+
+impl From<stead_core::attention::AttentionItem> for FfiAttentionItem {
+    fn from(i: stead_core::attention::AttentionItem) -> Self {
+        Self {
+            reason: i.reason.into(),
+            contract_id: i.contract_id,
+            session_id: i.session_id,
+            title: i.title,
+            detail: i.detail,
+            since: i.since.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct FfiAttention {
+    pub state: FfiAttentionState,
+    /// Most urgent first
+    pub items: Vec<FfiAttentionItem>,
+    pub active: u32,
+}
+
+/// A change to one contract, as reported to a `ContractObserver`
+#[derive(uniffi::Enum)]
+pub enum FfiContractEvent {
+    Created {
+        contract: FfiContract,
+    },
+    Updated {
+        contract: FfiContract,
+        previous_status: FfiContractStatus,
+    },
+    Deleted {
+        id: String,
+        project_path: String,
+    },
+}
+
 // -- FFI Error type --
 
 #[derive(Debug, thiserror::Error, uniffi::Error)]
@@ -127,27 +305,300 @@ pub enum FfiError {
     Storage { message: String },
     #[error("Not found: {id}")]
     NotFound { id: String },
+    #[error("Invalid transition: {message}")]
+    InvalidTransition { message: String },
+}
+
+impl From<stead_core::storage::StorageError> for FfiError {
+    fn from(e: stead_core::storage::StorageError) -> Self {
+        match e {
+            stead_core::storage::StorageError::NotFound(id) => FfiError::NotFound { id },
+            other => FfiError::Storage {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
+impl From<stead_core::commands::approve::SignOffError> for FfiError {
+    fn from(e: stead_core::commands::approve::SignOffError) -> Self {
+        use stead_core::commands::approve::SignOffError;
+        match e {
+            SignOffError::NotFound(id) => FfiError::NotFound { id },
+            SignOffError::Storage(e) => e.into(),
+            other => FfiError::InvalidTransition {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
+/// Storage for the project containing a directory, using its configured backend
+struct ProjectStorage {
+    /// Canonical project root, reported on every contract
+    root: String,
+    config: stead_core::config::Config,
+    /// None when the project has no contracts yet; reads never create storage
+    storage: Option<Box<dyn Storage>>,
+}
+
+impl ProjectStorage {
+    fn open(cwd: &str) -> Result<Self, FfiError> {
+        let root = stead_core::project::resolve_from(Path::new(cwd), None)
+            .map_err(stead_core::storage::StorageError::Io)?;
+        let config = stead_core::config::Config::load(&root).map_err(|e| FfiError::Storage {
+            message: e.to_string(),
+        })?;
+        let storage = stead_core::storage::open_existing(&root, config.storage.backend)?;
+        Ok(Self {
+            root: root.canonicalize().unwrap_or(root).display().to_string(),
+            config,
+            storage,
+        })
+    }
+
+    /// Every contract in the project, empty when it has no storage
+    fn load_all(&self) -> Result<Vec<stead_core::schema::Contract>, FfiError> {
+        match &self.storage {
+            Some(storage) => Ok(storage.load_all_contracts()?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The project's storage, or NotFound for `id` when it has none
+    fn storage_for(&self, id: &str) -> Result<&dyn Storage, FfiError> {
+        self.storage
+            .as_deref()
+            .ok_or_else(|| FfiError::NotFound { id: id.to_string() })
+    }
+
+    /// Convert a contract loaded from this project, tagging it with the root
+    fn contract(&self, contract: stead_core::schema::Contract) -> FfiContract {
+        FfiContract {
+            project_path: self.root.clone(),
+            ..FfiContract::from(contract)
+        }
+    }
+}
+
+impl ProjectStorage {
+    fn event(&self, event: stead_core::storage::watch::ContractEvent) -> FfiContractEvent {
+        use stead_core::storage::watch::ContractEvent;
+        match event {
+            ContractEvent::Created { contract } => FfiContractEvent::Created {
+                contract: self.contract(contract),
+            },
+            ContractEvent::Updated {
+                contract,
+                previous_status,
+            } => FfiContractEvent::Updated {
+                contract: self.contract(contract),
+                previous_status: previous_status.into(),
+            },
+            ContractEvent::Deleted { id } => FfiContractEvent::Deleted {
+                id,
+                project_path: self.root.clone(),
+            },
+        }
+    }
+}
+
+fn load_required(
+    storage: &dyn Storage,
+    id: String,
+) -> Result<stead_core::schema::Contract, FfiError> {
+    storage.load_contract(&id)?.ok_or(FfiError::NotFound { id })
 }
 
 // -- Exported functions --
 
 #[uniffi::export]
 pub fn list_contracts(cwd: String) -> Result<Vec<FfiContract>, FfiError> {
-    let contracts =
-        stead_core::storage::list_contracts(Path::new(&cwd)).map_err(|e| FfiError::Storage {
-            message: e.to_string(),
-        })?;
-    Ok(contracts.into_iter().map(FfiContract::from).collect())
+    let project = ProjectStorage::open(&cwd)?;
+    let contracts = project.load_all()?;
+    Ok(contracts.into_iter().map(|c| project.contract(c)).collect())
+}
+
+/// List contracts from every project in the global registry, newest first.
+/// Projects that couldn't be read are listed in `errors` rather than failing the call.
+/// Fails when the registry is off; this never turns it on.
+#[uniffi::export]
+pub fn list_all_contracts() -> Result<FfiAllContracts, FfiError> {
+    use stead_core::storage::{registry::Registry, StorageError};
+    let registry = Registry::open_if_enabled()?.ok_or(StorageError::RegistryDisabled)?;
+    let all = registry.load_all_contracts(&stead_core::storage::ContractQuery::new())?;
+    Ok(FfiAllContracts {
+        contracts: all.contracts.into_iter().map(FfiContract::from).collect(),
+        errors: all.errors.into_iter().map(FfiProjectError::from).collect(),
+    })
 }
 
 #[uniffi::export]
 pub fn get_contract(id: String, cwd: String) -> Result<FfiContract, FfiError> {
-    let contract = stead_core::storage::read_contract(&id, Path::new(&cwd))
-        .map_err(|e| FfiError::Storage {
-            message: e.to_string(),
-        })?
-        .ok_or(FfiError::NotFound { id })?;
-    Ok(FfiContract::from(contract))
+    let project = ProjectStorage::open(&cwd)?;
+    let contract = load_required(project.storage_for(&id)?, id)?;
+    Ok(project.contract(contract))
+}
+
+/// Sign off on a contract that is awaiting approval
+#[uniffi::export]
+pub fn approve_contract(
+    id: String,
+    cwd: String,
+    approver: String,
+) -> Result<FfiContract, FfiError> {
+    let project = ProjectStorage::open(&cwd)?;
+    let contract = stead_core::commands::approve::approve(
+        project.storage_for(&id)?,
+        &project.config,
+        &id,
+        &approver,
+    )?;
+    Ok(project.contract(contract))
+}
+
+/// Reject a contract that is awaiting approval
+#[uniffi::export]
+pub fn reject_contract(
+    id: String,
+    cwd: String,
+    approver: String,
+    reason: String,
+) -> Result<FfiContract, FfiError> {
+    let project = ProjectStorage::open(&cwd)?;
+    let contract = stead_core::commands::reject::reject(
+        project.storage_for(&id)?,
+        &project.config,
+        &id,
+        &approver,
+        &reason,
+    )?;
+    Ok(project.contract(contract))
+}
+
+/// What needs a human in the project containing `cwd`, most urgent first.
+/// `include_sessions` also scans recent AI CLI sessions for open questions.
+#[uniffi::export]
+pub fn attention(cwd: String, include_sessions: bool) -> Result<FfiAttention, FfiError> {
+    let project = ProjectStorage::open(&cwd)?;
+    let contracts = project.load_all()?;
+    let sessions = if include_sessions {
+        stead_core::attention::recent_sessions(Path::new(&project.root), &project.config.attention)
+    } else {
+        Vec::new()
+    };
+
+    let report = stead_core::attention::assess(
+        &contracts,
+        &sessions,
+        &project.config.attention,
+        chrono::Utc::now(),
+    );
+    Ok(FfiAttention {
+        state: report.state.into(),
+        items: report
+            .items
+            .into_iter()
+            .map(FfiAttentionItem::from)
+            .collect(),
+        active: report.active as u32,
+    })
+}
+
+/// Contract statuses in attention order (most urgent first), so frontends
+/// sort the same way as the CLI
+#[uniffi::export]
+pub fn attention_order() -> Vec<FfiContractStatus> {
+    stead_core::attention::STATUS_ORDER
+        .into_iter()
+        .map(FfiContractStatus::from)
+        .collect()
+}
+
+/// Receives contract changes from `watch_contracts`, on a background thread
+#[uniffi::export(callback_interface)]
+pub trait ContractObserver: Send + Sync {
+    fn on_events(&self, events: Vec<FfiContractEvent>);
+    /// A poll failed; watching continues
+    fn on_error(&self, message: String);
+}
+
+/// Handle for a running `watch_contracts`; dropping it also stops the watch
+#[derive(uniffi::Object)]
+pub struct ContractSubscription {
+    stopped: Arc<AtomicBool>,
+}
+
+#[uniffi::export]
+impl ContractSubscription {
+    /// Stop watching; the observer gets no further calls after the current poll
+    pub fn cancel(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+impl Drop for ContractSubscription {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Watch a project's contracts, calling `observer` with each batch of
+/// changes instead of polling `list_contracts`. `interval_ms` of 0 uses
+/// the default poll interval. Fails with NotFound for the project root
+/// when the project has no contracts yet.
+#[uniffi::export]
+pub fn watch_contracts(
+    cwd: String,
+    interval_ms: u64,
+    observer: Box<dyn ContractObserver>,
+) -> Result<Arc<ContractSubscription>, FfiError> {
+    use stead_core::storage::watch::{ContractWatcher, DEFAULT_POLL_INTERVAL};
+
+    // Open once up front so a bad project fails the call, not the thread
+    let project = ProjectStorage::open(&cwd)?;
+    project.storage_for(&project.root)?;
+    drop(project);
+    let interval = match interval_ms {
+        0 => DEFAULT_POLL_INTERVAL,
+        ms => std::time::Duration::from_millis(ms),
+    };
+    let stopped = Arc::new(AtomicBool::new(false));
+    let subscription = Arc::new(ContractSubscription {
+        stopped: stopped.clone(),
+    });
+
+    // Storage isn't Send, so the thread opens its own (which SQLite's
+    // data_version needs anyway)
+    std::thread::spawn(move || {
+        let setup = ProjectStorage::open(&cwd).and_then(|mut project| {
+            let storage = project.storage.take().ok_or_else(|| FfiError::NotFound {
+                id: project.root.clone(),
+            })?;
+            let watcher = ContractWatcher::new(&*storage)?;
+            Ok((project, storage, watcher))
+        });
+        let (project, storage, mut watcher) = match setup {
+            Ok(setup) => setup,
+            Err(e) => return observer.on_error(e.to_string()),
+        };
+
+        while !stopped.load(Ordering::SeqCst) {
+            std::thread::sleep(interval);
+            if stopped.load(Ordering::SeqCst) {
+                break;
+            }
+            match watcher.poll(&*storage) {
+                Ok(events) if events.is_empty() => {}
+                Ok(events) => {
+                    observer.on_events(events.into_iter().map(|e| project.event(e)).collect())
+                }
+                Err(e) => observer.on_error(FfiError::from(e).to_string()),
+            }
+        }
+    });
+
+    Ok(subscription)
 }
 
 #[uniffi::export]
@@ -156,12 +607,12 @@ pub fn list_sessions(
     project: Option<String>,
     limit: u32,
 ) -> Vec<FfiSessionSummary> {
-    let mut sessions = stead_core::usf::adapters::discover_all_sessions();
-
+    // Only the filtered CLI's adapter is built
+    let mut registry = stead_core::usf::adapters::AdapterRegistry::builder();
     if let Some(cli) = &cli_filter {
-        let cli_lower = cli.to_lowercase();
-        sessions.retain(|s| s.cli.as_str() == cli_lower);
+        registry = registry.only(cli);
     }
+    let mut sessions = registry.build().discover(false).sessions;
 
     if let Some(proj) = &project {
         let proj_lower = proj.to_lowercase();